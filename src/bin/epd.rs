@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use chess_game_engine::{
+    epd::{parse_suite, run_suite},
+    nnue::Evaluator,
+    search::{Limits, Search},
+    tuning::Weights,
+};
+
+// cargo run --release --bin epd -- <suite.epd> [depth] [milliseconds per position]
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let Some(path) = args.get(1) else {
+        eprintln!("usage: epd <suite.epd> [depth] [milliseconds per position]");
+        std::process::exit(2);
+    };
+    let depth: u32 = args.get(2).and_then(|n| n.parse().ok()).unwrap_or(3);
+    let time = Duration::from_millis(args.get(3).and_then(|n| n.parse().ok()).unwrap_or(1000));
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) => {
+            eprintln!("{path}: {error}");
+            std::process::exit(1);
+        }
+    };
+    let suite = match parse_suite(&text) {
+        Ok(suite) => suite,
+        Err(error) => {
+            eprintln!("{path}: {error}");
+            std::process::exit(1);
+        }
+    };
+
+    let search = Search::new(Evaluator::Classical(Weights::default()));
+    let limits = Limits {
+        depth: Some(depth),
+        time: Some(time),
+        ..Limits::default()
+    };
+    let report = run_suite(&suite, |game| search.best_move(game, limits));
+
+    for (epd, result) in suite.iter().zip(&report.results) {
+        let played = match (result.played, epd.game()) {
+            (Some(mv), Ok(game)) => game.to_san(mv),
+            _ => "-".to_owned(),
+        };
+        println!(
+            "{:<24} {:<8} {} {:>2}/{}",
+            result.id.as_deref().unwrap_or("?"),
+            played,
+            if result.solved { "solved" } else { "failed" },
+            result.points,
+            epd.max_points()
+        );
+    }
+    println!(
+        "solved {}/{}, STS score {}/{}",
+        report.solved,
+        suite.len(),
+        report.points,
+        report.max_points
+    );
+}
@@ -0,0 +1,64 @@
+use std::io::{BufWriter, Write};
+
+use chess_game_engine::{
+    epd::parse_suite,
+    nnue::Evaluator,
+    search::{Limits, Search},
+    selfplay::SelfPlay,
+    tuning::Weights,
+};
+
+// cargo run --release --bin selfplay -- <games> <output> [nodes per move] [openings.epd]
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let (Some(games), Some(output)) = (args.get(1).and_then(|n| n.parse().ok()), args.get(2))
+    else {
+        eprintln!("usage: selfplay <games> <output> [nodes per move] [openings.epd]");
+        std::process::exit(2);
+    };
+    let nodes: u64 = args.get(3).and_then(|n| n.parse().ok()).unwrap_or(5000);
+    let openings = match args
+        .get(4)
+        .map(|path| (path, std::fs::read_to_string(path)))
+    {
+        None => Vec::new(),
+        Some((path, Ok(text))) => parse_suite(&text).unwrap_or_else(|error| {
+            eprintln!("{path}: {error}");
+            std::process::exit(1);
+        }),
+        Some((path, Err(error))) => {
+            eprintln!("{path}: {error}");
+            std::process::exit(1);
+        }
+    };
+    let mut file = match std::fs::File::create(output) {
+        Ok(file) => BufWriter::new(file),
+        Err(error) => {
+            eprintln!("{output}: {error}");
+            std::process::exit(1);
+        }
+    };
+
+    // fixed nodes keep the games the same from run to run
+    let mut search = Search::new(Evaluator::Classical(Weights::default()));
+    let mut selfplay = SelfPlay::new(openings, 8, 1);
+    let mut positions = 0;
+    for _ in 0..games {
+        search.clear();
+        let samples = selfplay
+            .play_game(|game| {
+                let result = search.go(game, Limits::nodes(nodes));
+                Some((result.best_move?, result.score))
+            })
+            .unwrap();
+        for sample in &samples {
+            match sample.to_bytes() {
+                Ok(bytes) => file.write_all(&bytes).unwrap(),
+                Err(error) => eprintln!("skipped: {error}"),
+            }
+        }
+        positions += samples.len();
+    }
+    file.flush().unwrap();
+    println!("{positions} positions of {games} games written to {output}");
+}
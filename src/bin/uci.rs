@@ -0,0 +1,161 @@
+use std::{
+    io::BufRead,
+    sync::{mpsc, Arc},
+    thread,
+    time::Duration,
+};
+
+use chess_game_engine::{
+    errors::GameError,
+    game::Game,
+    moves::Move,
+    nnue::Evaluator,
+    pieces::Side,
+    search::{Limits, Mode, Search, SearchHandle, SearchResult, MAX_MULTI_PV, MAX_THREADS},
+    transposition::{DEFAULT_HASH_MB, MAX_HASH_MB},
+    tuning::Weights,
+};
+
+// moves a sudden death clock is shared out over
+const MOVES_TO_GO: u32 = 30;
+
+// cargo run --release --bin uci, then talk UCI on stdin and stdout
+fn main() {
+    // stdin on a thread of its own, so a search finishing by itself is answered at once
+    let (sender, commands) = mpsc::channel();
+    thread::spawn(move || {
+        for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut search = Arc::new(Search::new(Evaluator::Classical(Weights::default())));
+    let mut game = start_position();
+    let mut running: Option<SearchHandle> = None;
+    loop {
+        let line = match commands.recv_timeout(Duration::from_millis(1)) {
+            Ok(line) => line,
+            Err(mpsc::RecvTimeoutError::Timeout) => String::new(),
+            // end of input is as good as `quit`
+            Err(mpsc::RecvTimeoutError::Disconnected) => "quit".to_owned(),
+        };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["uci"] => {
+                println!("id name chess_game_engine {}", env!("CARGO_PKG_VERSION"));
+                println!(
+                    "option name Hash type spin default {DEFAULT_HASH_MB} min 1 max {MAX_HASH_MB}"
+                );
+                println!("option name Threads type spin default 1 min 1 max {MAX_THREADS}");
+                println!("option name MultiPV type spin default 1 min 1 max {MAX_MULTI_PV}");
+                println!("option name Ponder type check default false");
+                println!("uciok");
+            }
+            ["isready"] => println!("readyok"),
+            ["setoption", "name", option, "value", value] => {
+                // options only change between searches
+                let Some(search) = Arc::get_mut(&mut search) else {
+                    continue;
+                };
+                let value = value.parse().unwrap_or(0);
+                match option.to_ascii_lowercase().as_str() {
+                    "hash" => search.set_hash(value),
+                    "threads" => search.set_threads(value),
+                    "multipv" => search.set_multi_pv(value),
+                    _ => {}
+                }
+            }
+            ["ucinewgame"] => {
+                if let Some(search) = Arc::get_mut(&mut search) {
+                    search.clear();
+                }
+            }
+            ["position", ..] => match position(&words[1..]) {
+                Ok(position) => game = position,
+                Err(error) => println!("info string {error}"),
+            },
+            ["go", ..] if running.is_none() => {
+                let (limits, mode) = go(&words[1..], game.whose_turn());
+                let progress = |result: &SearchResult, elapsed| {
+                    for info in result.uci_info(elapsed) {
+                        println!("{info}");
+                    }
+                };
+                running = Some(search.spawn(game.clone(), limits, mode, progress));
+            }
+            ["stop"] => running.iter().for_each(SearchHandle::stop),
+            ["ponderhit"] => running.iter().for_each(SearchHandle::ponderhit),
+            ["quit"] => {
+                if let Some(handle) = running.take() {
+                    handle.stop();
+                    handle.wait();
+                }
+                break;
+            }
+            _ => {}
+        }
+        if running.as_ref().is_some_and(SearchHandle::is_finished) {
+            let result = running.take().map(SearchHandle::wait).unwrap_or_default();
+            let best_move = result.best_move.map_or("0000".to_owned(), |mv| mv.to_uci());
+            match result.pv.get(1) {
+                Some(ponder) => println!("bestmove {best_move} ponder {}", ponder.to_uci()),
+                None => println!("bestmove {best_move}"),
+            }
+        }
+    }
+}
+
+fn start_position() -> Game {
+    let mut game = Game::new();
+    game.start();
+    game
+}
+
+// `startpos` or `fen <fen>`, then the moves played from it
+fn position(words: &[&str]) -> Result<Game, GameError> {
+    let (mut game, rest) = match words {
+        ["startpos", rest @ ..] => (start_position(), rest),
+        ["fen", rest @ ..] => {
+            let fields = rest.iter().take_while(|&&word| word != "moves").count();
+            (Game::from_fen(&rest[..fields].join(" "))?, &rest[fields..])
+        }
+        _ => return Err(GameError::InvalidNotation(words.join(" "))),
+    };
+    for text in rest.iter().skip_while(|&&word| word == "moves") {
+        let mv = Move::from_uci(text).ok_or(GameError::InvalidNotation(text.to_string()))?;
+        game.make_move(mv)?;
+    }
+    Ok(game)
+}
+
+// the limits of `go`, a clock is shared out over the moves still to go with most of the
+// increment on top, but never more than half of what's left
+fn go(words: &[&str], side: Side) -> (Limits, Mode) {
+    let mut limits = Limits::default();
+    let mut mode = Mode::Normal;
+    let (mut left, mut increment, mut moves_to_go) = (None, 0, MOVES_TO_GO);
+    let mut words = words.iter();
+    while let Some(&word) = words.next() {
+        let mut value = || words.next().and_then(|value| value.parse::<u64>().ok());
+        // whether a `w` or `b` clock is of the side to move
+        let own = word.starts_with('w') == (side == Side::White);
+        match word {
+            "infinite" => mode = Mode::Infinite,
+            "ponder" => mode = Mode::Ponder,
+            "depth" => limits.depth = value().map(|depth| depth as u32),
+            "nodes" => limits.nodes = value(),
+            "movetime" => limits.time = value().map(Duration::from_millis),
+            "movestogo" => moves_to_go = value().map_or(MOVES_TO_GO, |n| n.max(1) as u32),
+            "wtime" | "btime" => left = value().filter(|_| own).or(left),
+            "winc" | "binc" => increment = value().filter(|_| own).unwrap_or(increment),
+            _ => {}
+        }
+    }
+    if let (Some(left), None) = (left, limits.time) {
+        let share = left / moves_to_go as u64 + increment * 3 / 4;
+        limits.time = Some(Duration::from_millis(share.min(left / 2)));
+    }
+    (limits, mode)
+}
@@ -3,22 +3,35 @@ use std::fmt::Debug;
 use crate::{
     characters::positions,
     errors::GameError,
-    pieces::{Character, Side},
+    moves::Moving,
+    pieces::{Character, Piece, Side},
     position::Pos,
 };
 
+// `pinned` may only move along the line towards `by`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pin {
+    pub pinned: Pos,
+    pub by: Pos,
+}
+
 #[derive(Debug, Clone)]
 pub struct ChessBoard {
     matrix: [Option<Character>; 64],
+    // pieces which came from a pawn promotion, crazyhouse demotes them on capture
+    promoted: [bool; 64],
 }
 
 impl ChessBoard {
     pub fn new() -> Self {
-        ChessBoard { matrix: [None; 64] }
+        ChessBoard {
+            matrix: [None; 64],
+            promoted: [false; 64],
+        }
     }
 
     pub fn get_matrix(&self) -> [Option<Character>; 64] {
-        self.matrix.clone()
+        self.matrix
     }
 
     pub fn place_character_init(&mut self) {
@@ -77,32 +90,304 @@ impl ChessBoard {
         }
     }
 
+    // nothing stands on a `Pos` off the board
     pub fn character_at(&self, pos: Pos) -> Option<Character> {
-        let index = pos.index();
+        if !Pos::is_valid(pos.file(), pos.rank()) {
+            return None;
+        }
+        let index = pos.matrix_index();
         self.matrix[index]
     }
 
     pub fn pick_character(&mut self, pos: Pos) -> Result<Character, GameError> {
         if let Some(chracter) = self.character_at(pos) {
             // somehow replace these lines as are redundent
-            let index = pos.index();
+            let index = pos.matrix_index();
             self.matrix[index] = None;
 
             Ok(chracter)
         } else {
-            Err(GameError::EmptyCell)
+            Err(GameError::EmptyCell(pos))
         }
     }
 
     pub fn place_character(&mut self, character: Character, pos: Pos) -> Option<Character> {
-        let index = pos.index();
+        let index = pos.matrix_index();
         self.matrix[index].replace(character)
     }
 
+    pub fn positions(&self, side: Side) -> Vec<Pos> {
+        (0..64)
+            .filter(|&index| self.matrix[index].is_some_and(|c| c.side() == side))
+            .map(Pos::from_matrix_index)
+            .collect()
+    }
+
+    pub fn king_position(&self, side: Side) -> Option<Pos> {
+        self.matrix
+            .iter()
+            .position(|c| *c == Some(Character::King(side)))
+            .map(Pos::from_matrix_index)
+    }
+
+    // pseudo legal moves of the character standing at `pos`, ignoring checks
+    pub fn moves_from(&self, pos: Pos) -> Vec<Pos> {
+        match self.character_at(pos) {
+            Some(character) => {
+                let piece = Piece::new(character, pos, Some(self.clone()));
+                let mut moves = piece.possible_moves();
+                moves.retain(|&to| to != pos);
+                moves
+            }
+            None => Vec::new(),
+        }
+    }
+
+    // squares the character at `pos` attacks, own pieces included as it defends them
+    pub fn attacks_from(&self, pos: Pos) -> Vec<Pos> {
+        match self.character_at(pos) {
+            Some(character) => Piece::new(character, pos, Some(self.clone())).attacks(),
+            None => Vec::new(),
+        }
+    }
+
+    // every square attacked by `side`, each listed once
+    pub fn attacked_squares(&self, side: Side) -> Vec<Pos> {
+        let mut attacked = [false; 64];
+        for from in self.positions(side) {
+            for pos in self.attacks_from(from) {
+                attacked[pos.matrix_index()] = true;
+            }
+        }
+        (0..64)
+            .filter(|&index| attacked[index])
+            .map(Pos::from_matrix_index)
+            .collect()
+    }
+
+    // positions of `by` characters attacking `pos`
+    pub fn attackers(&self, pos: Pos, by: Side) -> Vec<Pos> {
+        self.positions(by)
+            .into_iter()
+            .filter(|&from| self.attacks_from(from).contains(&pos))
+            .collect()
+    }
+
+    pub fn is_attacked(&self, pos: Pos, by: Side) -> bool {
+        !self.attackers(pos, by).is_empty()
+    }
+
+    // whether the character at `pos` is protected by its own side
+    pub fn is_defended(&self, pos: Pos) -> bool {
+        self.character_at(pos)
+            .is_some_and(|character| self.is_attacked(pos, character.side()))
+    }
+
+    // `side` characters attacked by the opponent and left undefended
+    pub fn hanging_pieces(&self, side: Side) -> Vec<Pos> {
+        self.positions(side)
+            .into_iter()
+            .filter(|&pos| self.is_attacked(pos, side.opponent()) && !self.is_defended(pos))
+            .collect()
+    }
+
+    // `by` sliders which would attack `pos` once a single piece in between moved away
+    pub fn xray_attackers(&self, pos: Pos, by: Side) -> Vec<Pos> {
+        self.xrays(pos, by)
+            .into_iter()
+            .map(|(attacker, _)| attacker)
+            .collect()
+    }
+
+    // characters of `side` which can't leave the line between an opponent slider and their king
+    pub fn pins(&self, side: Side) -> Vec<Pin> {
+        let Some(king) = self.king_position(side) else {
+            return Vec::new();
+        };
+        self.xrays(king, side.opponent())
+            .into_iter()
+            .filter(|&(_, blocker)| self.character_at(blocker).is_some_and(|c| c.side() == side))
+            .map(|(by, pinned)| Pin { pinned, by })
+            .collect()
+    }
+
+    // material won by `character` landing on `to` once both sides trade off there with their
+    // least valuable attackers, sliders behind them join in as the line opens
+    pub fn see(&self, character: Character, from: Option<Pos>, to: Pos) -> i32 {
+        let mut board = self.clone();
+        if let Some(from) = from {
+            board.matrix[from.matrix_index()] = None;
+        }
+        let mut gains = vec![self.character_at(to).map_or(0, |c| c.value())];
+        let mut on_square = character.value();
+        board.place_character(character, to);
+        let mut side = character.side().opponent();
+        while let Some(attacker) = board
+            .attackers(to, side)
+            .into_iter()
+            .filter_map(|pos| board.character_at(pos).map(|c| (pos, c)))
+            .min_by_key(|(_, c)| c.value())
+        {
+            let (pos, character) = attacker;
+            gains.push(on_square - gains[gains.len() - 1]);
+            on_square = character.value();
+            board.matrix[pos.matrix_index()] = None;
+            board.place_character(character, to);
+            side = side.opponent();
+        }
+        // either side may stop trading once it would lose material
+        for index in (1..gains.len()).rev() {
+            gains[index - 1] = -(-gains[index - 1]).max(gains[index]);
+        }
+        gains[0]
+    }
+
+    // (attacker, blocker) pairs of `by` sliders hitting `pos` through exactly one piece
+    fn xrays(&self, pos: Pos, by: Side) -> Vec<(Pos, Pos)> {
+        let mut xrays = Vec::new();
+        for from in self.positions(by) {
+            let is_slider = matches!(
+                self.character_at(from),
+                Some(Character::Queen(_) | Character::Rook(_) | Character::Bishop(_))
+            );
+            let attacks = self.attacks_from(from);
+            if !is_slider || attacks.contains(&pos) {
+                continue;
+            }
+            for blocker in attacks {
+                if blocker == pos || self.character_at(blocker).is_none() {
+                    continue;
+                }
+                let mut board = self.clone();
+                board.matrix[blocker.matrix_index()] = None;
+                if board.attacks_from(from).contains(&pos) {
+                    xrays.push((from, blocker));
+                }
+            }
+        }
+        xrays
+    }
+
+    pub fn is_in_check(&self, side: Side) -> bool {
+        self.king_position(side)
+            .is_some_and(|king| self.is_attacked(king, side.opponent()))
+    }
+
+    pub fn clear(&mut self) {
+        *self = ChessBoard::new();
+    }
+
+    // upside down, rank 1 becomes rank 8
+    pub fn flip(&mut self) {
+        let board = self.clone();
+        for index in 0..64 {
+            let (row, col) = (index / 8, index % 8);
+            self.matrix[index] = board.matrix[(7 - row) * 8 + col];
+            self.promoted[index] = board.promoted[(7 - row) * 8 + col];
+        }
+    }
+
+    // left to right, file a becomes file h
+    pub fn mirror(&mut self) {
+        let board = self.clone();
+        for index in 0..64 {
+            let (row, col) = (index / 8, index % 8);
+            self.matrix[index] = board.matrix[row * 8 + 7 - col];
+            self.promoted[index] = board.promoted[row * 8 + 7 - col];
+        }
+    }
+
+    pub fn swap_colours(&mut self) {
+        for character in self.matrix.iter_mut().flatten() {
+            *character = character.with_side(character.side().opponent());
+        }
+    }
+
+    pub fn is_promoted(&self, pos: Pos) -> bool {
+        self.promoted[pos.matrix_index()]
+    }
+
+    pub fn set_promoted(&mut self, pos: Pos, promoted: bool) {
+        self.promoted[pos.matrix_index()] = promoted;
+    }
+
+    pub fn move_promoted(&mut self, from: Pos, to: Pos) {
+        let promoted = self.promoted[from.matrix_index()];
+        self.promoted[from.matrix_index()] = false;
+        self.promoted[to.matrix_index()] = promoted;
+    }
+
+    // piece placement field of FEN, promoted pieces are suffixed with `~`
+    pub fn fen_placement(&self) -> String {
+        let mut placement = String::new();
+        for row in 0..8 {
+            let mut empty = 0;
+            for col in 0..8 {
+                let index = row * 8 + col;
+                match self.matrix[index] {
+                    Some(character) => {
+                        if empty > 0 {
+                            placement.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        placement.push(character.letter());
+                        if self.promoted[index] {
+                            placement.push('~');
+                        }
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                placement.push_str(&empty.to_string());
+            }
+            if row < 7 {
+                placement.push('/');
+            }
+        }
+        placement
+    }
+
+    pub fn from_fen_placement(placement: &str) -> Result<ChessBoard, GameError> {
+        let mut board = ChessBoard::new();
+        let rows: Vec<&str> = placement.split('/').collect();
+        if rows.len() != 8 {
+            return Err(GameError::InvalidFen(placement.to_owned()));
+        }
+        for (row, row_str) in rows.iter().enumerate() {
+            let mut col = 0;
+            for c in row_str.chars() {
+                if let Some(empty) = c.to_digit(10) {
+                    col += empty as usize;
+                    if col > 8 {
+                        return Err(GameError::InvalidFen(placement.to_owned()));
+                    }
+                } else if c == '~' {
+                    if col == 0 || board.matrix[row * 8 + col - 1].is_none() {
+                        return Err(GameError::InvalidFen(placement.to_owned()));
+                    }
+                    board.promoted[row * 8 + col - 1] = true;
+                } else {
+                    let character = Character::from_letter(c)
+                        .ok_or_else(|| GameError::InvalidFen(placement.to_owned()))?;
+                    if col >= 8 {
+                        return Err(GameError::InvalidFen(placement.to_owned()));
+                    }
+                    board.matrix[row * 8 + col] = Some(character);
+                    col += 1;
+                }
+            }
+            if col != 8 {
+                return Err(GameError::InvalidFen(placement.to_owned()));
+            }
+        }
+        Ok(board)
+    }
+
     pub fn show(&self) {
         // don't read this code :DD
         let mut board_str = (0..8).fold(String::new(), |mut st, r| {
-            st.push(('0' as u8 + 8 - r) as char);
+            st.push((b'0' + 8 - r) as char);
             st.push('|');
             let mut file = (0..8)
                 .map(|i| self.matrix[8 * r as usize + i].map_or(' ', |x| x.symbol()))
@@ -118,7 +403,7 @@ impl ChessBoard {
 
         board_str.push_str("  ");
         let files = (0..8)
-            .map(|x| ('a' as u8 + x) as char)
+            .map(|x| (b'a' + x) as char)
             .fold(board_str, |mut st, c| {
                 st.push(c);
                 st.push(' ');
@@ -147,7 +432,7 @@ fn pos_test() {
 
     // safe position
     let maybe_pos = Pos::new('z', 100);
-    assert_eq!(maybe_pos, Err(GameError::InvalidPosition));
+    assert_eq!(maybe_pos, Err(GameError::InvalidPosition("z100".into())));
 
     let mut board = ChessBoard::new();
     board.place_character_init();
@@ -158,3 +443,102 @@ fn pos_test() {
     println!("{:?}", pos.rank());
     println!("{:?}", pos.file());
 }
+
+#[test]
+fn fen_placement_test() {
+    let mut board = ChessBoard::new();
+    board.place_character_init();
+    let placement = board.fen_placement();
+    assert_eq!(placement, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR");
+
+    let parsed = ChessBoard::from_fen_placement(&placement).unwrap();
+    assert_eq!(parsed.get_matrix(), board.get_matrix());
+
+    let promoted = ChessBoard::from_fen_placement("4Q~3/8/8/8/8/8/8/4k2K").unwrap();
+    assert!(promoted.is_promoted(Pos('e', 8)));
+    assert_eq!(promoted.fen_placement(), "4Q~3/8/8/8/8/8/8/4k2K");
+
+    assert!(ChessBoard::from_fen_placement("8/8/8").is_err());
+    assert!(ChessBoard::from_fen_placement("9/8/8/8/8/8/8/8").is_err());
+    assert!(ChessBoard::from_fen_placement("x7/8/8/8/8/8/8/8").is_err());
+    assert!(ChessBoard::from_fen_placement("8/8/8/8/8/8/8/9~").is_err());
+    assert!(ChessBoard::from_fen_placement("8/8/8/8/8/8/9~/P7").is_err());
+}
+
+#[test]
+fn transform_test() {
+    let mut board = ChessBoard::from_fen_placement("4k3/8/8/8/8/8/1P6/R3K3").unwrap();
+    board.set_promoted(Pos('a', 1), true);
+
+    board.flip();
+    assert_eq!(board.fen_placement(), "R~3K3/1P6/8/8/8/8/8/4k3");
+    board.mirror();
+    assert_eq!(board.fen_placement(), "3K3R~/6P1/8/8/8/8/8/3k4");
+    board.swap_colours();
+    assert_eq!(board.fen_placement(), "3k3r~/6p1/8/8/8/8/8/3K4");
+
+    board.clear();
+    assert_eq!(board.fen_placement(), "8/8/8/8/8/8/8/8");
+}
+
+#[test]
+fn attack_test() {
+    // white: Ke1 Rd1 Bb5 Pe4, black: Ke8 Nd7 Qd8 Pf5
+    let board = ChessBoard::from_fen_placement("3qk3/3n4/8/1B3p2/4P3/8/8/3RK3").unwrap();
+
+    let pawn_attacks = board.attacks_from(Pos('e', 4));
+    assert_eq!(pawn_attacks.len(), 2);
+    assert!(pawn_attacks.contains(&Pos('d', 5)) && pawn_attacks.contains(&Pos('f', 5)));
+    assert!(board.attacks_from(Pos('d', 1)).contains(&Pos('e', 1)));
+
+    let attacked = board.attacked_squares(Side::White);
+    assert!(attacked.contains(&Pos('d', 7)) && attacked.contains(&Pos('f', 5)));
+    assert!(!attacked.contains(&Pos('d', 8)));
+
+    assert_eq!(
+        board.attackers(Pos('d', 7), Side::White),
+        vec![Pos('b', 5), Pos('d', 1)]
+    );
+    assert!(board.is_defended(Pos('d', 7)));
+    assert!(!board.is_defended(Pos('b', 5)));
+    assert_eq!(board.hanging_pieces(Side::Black), vec![Pos('f', 5)]);
+    assert_eq!(board.hanging_pieces(Side::White), vec![Pos('e', 4)]);
+
+    // the rook hits the queen through the knight, which the bishop pins
+    assert_eq!(
+        board.xray_attackers(Pos('d', 8), Side::White),
+        vec![Pos('d', 1)]
+    );
+    assert_eq!(
+        board.pins(Side::Black),
+        vec![Pin {
+            pinned: Pos('d', 7),
+            by: Pos('b', 5)
+        }]
+    );
+    assert_eq!(board.pins(Side::White), vec![]);
+
+    let board = ChessBoard::from_fen_placement("4k3/8/8/3n4/8/8/8/4K3").unwrap();
+    assert_eq!(board.hanging_pieces(Side::Black), vec![]);
+    let board = ChessBoard::from_fen_placement("4k3/8/8/3n4/8/8/3R4/4K3").unwrap();
+    assert_eq!(board.hanging_pieces(Side::Black), vec![Pos('d', 5)]);
+}
+
+#[test]
+fn see_test() {
+    let board = ChessBoard::from_fen_placement("1k1r4/1pp4p/p7/4p3/8/P5P1/1PP4P/2K1R3").unwrap();
+    let rook = Character::Rook(Side::White);
+    assert_eq!(board.see(rook, Some(Pos('e', 1)), Pos('e', 5)), 100);
+
+    // the queen behind the rook and the rook behind the queen join through x-rays
+    let board =
+        ChessBoard::from_fen_placement("1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3").unwrap();
+    let knight = Character::Knight(Side::White);
+    assert_eq!(board.see(knight, Some(Pos('d', 3)), Pos('e', 5)), -200);
+
+    // quiet move onto a square guarded by a pawn
+    let board = ChessBoard::from_fen_placement("4k3/8/3p4/8/8/8/8/2B1K3").unwrap();
+    let bishop = Character::Bishop(Side::White);
+    assert_eq!(board.see(bishop, Some(Pos('c', 1)), Pos('e', 5)), -300);
+    assert_eq!(board.see(bishop, Some(Pos('c', 1)), Pos('d', 2)), 0);
+}
@@ -0,0 +1,732 @@
+use std::sync::OnceLock;
+
+use crate::{game::Game, moves::Move};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Opening {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub variation: Option<&'static str>,
+    // from the start position in SAN, separated by spaces
+    pub moves: &'static str,
+}
+
+impl Opening {
+    const fn new(code: &'static str, name: &'static str, moves: &'static str) -> Opening {
+        Opening {
+            code,
+            name,
+            variation: None,
+            moves,
+        }
+    }
+
+    const fn variation(
+        code: &'static str,
+        name: &'static str,
+        variation: &'static str,
+        moves: &'static str,
+    ) -> Opening {
+        Opening {
+            code,
+            name,
+            variation: Some(variation),
+            moves,
+        }
+    }
+
+    // the moves replayed from the start position, up to the first one that isn't legal
+    pub fn reference_moves(&self) -> Vec<Move> {
+        self.replay().1
+    }
+
+    // `ECO`, `Opening` and, when there is one, `Variation` tags of a PGN header
+    pub fn pgn_tags(&self) -> String {
+        let mut tags = format!("[ECO \"{}\"]\n[Opening \"{}\"]\n", self.code, self.name);
+        if let Some(variation) = self.variation {
+            tags.push_str(&format!("[Variation \"{variation}\"]\n"));
+        }
+        tags
+    }
+
+    fn replay(&self) -> (Game, Vec<Move>) {
+        let mut game = Game::new();
+        game.start();
+        let mut moves = Vec::new();
+        for san in self.moves.split_whitespace() {
+            let Ok(mv) = game.parse_san(san) else { break };
+            if game.make_move(mv).is_err() {
+                break;
+            }
+            moves.push(mv);
+        }
+        (game, moves)
+    }
+
+    // key of the position the reference moves lead to and their number, `None` when one
+    // of them isn't legal
+    fn key(&self) -> Option<(u64, usize)> {
+        let (game, moves) = self.replay();
+        let plies = self.moves.split_whitespace().count();
+        (moves.len() == plies).then(|| (game.zobrist_key(), plies))
+    }
+}
+
+// every code from A00 to E99, some with their best known lines as well
+#[rustfmt::skip]
+pub const OPENINGS: &[Opening] = &[
+    Opening::new("A00", "Polish Opening", "b4"),
+    Opening::new("A00", "Grob Opening", "g4"),
+    Opening::new("A00", "Hungarian Opening", "g3"),
+    Opening::new("A00", "Van't Kruijs Opening", "e3"),
+    Opening::new("A00", "Mieses Opening", "d3"),
+    Opening::new("A00", "Saragossa Opening", "c3"),
+    Opening::new("A00", "Clemenz Opening", "h3"),
+    Opening::new("A00", "Ware Opening", "a4"),
+    Opening::new("A00", "Anderssen Opening", "a3"),
+    Opening::new("A00", "Barnes Opening", "f3"),
+    Opening::new("A00", "Amar Opening", "Nh3"),
+    Opening::new("A00", "Durkin Opening", "Na3"),
+    Opening::new("A00", "Van Geet Opening", "Nc3"),
+    Opening::new("A01", "Nimzo-Larsen Attack", "b3"),
+    Opening::new("A02", "Bird Opening", "f4"),
+    Opening::variation("A03", "Bird Opening", "Dutch Variation", "f4 d5"),
+    Opening::new("A04", "Zukertort Opening", "Nf3"),
+    Opening::variation("A05", "Zukertort Opening", "Quiet System", "Nf3 Nf6"),
+    Opening::new("A06", "Zukertort Opening", "Nf3 d5"),
+    Opening::new("A07", "King's Indian Attack", "Nf3 d5 g3"),
+    Opening::variation("A08", "King's Indian Attack", "French Variation", "Nf3 d5 g3 c5 Bg2"),
+    Opening::new("A09", "Reti Opening", "Nf3 d5 c4"),
+    Opening::new("A10", "English Opening", "c4"),
+    Opening::variation("A11", "English Opening", "Caro-Kann Defensive System", "c4 c6"),
+    Opening::variation("A12", "English Opening", "Caro-Kann Defensive System", "c4 c6 Nf3 d5 b3"),
+    Opening::variation("A13", "English Opening", "Agincourt Defense", "c4 e6"),
+    Opening::variation("A14", "English Opening", "Agincourt Defense, Catalan Defense", "c4 e6 Nf3 d5 g3 Nf6 Bg2 Be7 O-O"),
+    Opening::variation("A15", "English Opening", "Anglo-Indian Defense", "c4 Nf6"),
+    Opening::variation("A16", "English Opening", "Anglo-Indian Defense, Queen's Knight Variation", "c4 Nf6 Nc3"),
+    Opening::variation("A17", "English Opening", "Anglo-Indian Defense, Hedgehog System", "c4 Nf6 Nc3 e6"),
+    Opening::variation("A18", "English Opening", "Mikenas-Carls Variation", "c4 Nf6 Nc3 e6 e4"),
+    Opening::variation("A19", "English Opening", "Mikenas-Carls, Sicilian Variation", "c4 Nf6 Nc3 e6 e4 c5"),
+    Opening::variation("A20", "English Opening", "King's English Variation", "c4 e5"),
+    Opening::variation("A21", "English Opening", "King's English Variation, Reversed Sicilian", "c4 e5 Nc3"),
+    Opening::variation("A22", "English Opening", "King's English Variation, Two Knights Variation", "c4 e5 Nc3 Nf6"),
+    Opening::variation("A23", "English Opening", "King's English Variation, Bremen System, Keres Variation", "c4 e5 Nc3 Nf6 g3 c6"),
+    Opening::variation("A24", "English Opening", "King's English Variation, Bremen System with ...g6", "c4 e5 Nc3 Nf6 g3 g6"),
+    Opening::variation("A25", "English Opening", "King's English Variation, Reversed Closed Sicilian", "c4 e5 Nc3 Nc6"),
+    Opening::variation("A26", "English Opening", "King's English Variation, Botvinnik System", "c4 e5 Nc3 Nc6 g3 g6 Bg2 Bg7 d3 d6"),
+    Opening::variation("A27", "English Opening", "King's English Variation, Three Knights System", "c4 e5 Nc3 Nc6 Nf3"),
+    Opening::variation("A28", "English Opening", "King's English Variation, Four Knights Variation", "c4 e5 Nc3 Nc6 Nf3 Nf6"),
+    Opening::variation("A29", "English Opening", "King's English Variation, Four Knights, Fianchetto Line", "c4 e5 Nc3 Nc6 Nf3 Nf6 g3"),
+    Opening::variation("A30", "English Opening", "Symmetrical Variation", "c4 c5"),
+    Opening::variation("A31", "English Opening", "Symmetrical Variation, Anti-Benoni Variation", "c4 c5 Nf3 Nf6 d4"),
+    Opening::variation("A32", "English Opening", "Symmetrical Variation, Anti-Benoni Variation, Spielmann Defense", "c4 c5 Nf3 Nf6 d4 cxd4 Nxd4 e6"),
+    Opening::variation("A33", "English Opening", "Symmetrical Variation, Anti-Benoni Variation, Geller Variation", "c4 c5 Nf3 Nf6 d4 cxd4 Nxd4 e6 Nc3 Nc6"),
+    Opening::variation("A34", "English Opening", "Symmetrical Variation, Normal Variation", "c4 c5 Nc3"),
+    Opening::variation("A35", "English Opening", "Symmetrical Variation, Two Knights Variation", "c4 c5 Nc3 Nc6"),
+    Opening::variation("A36", "English Opening", "Symmetrical Variation, Fianchetto Variation", "c4 c5 Nc3 Nc6 g3"),
+    Opening::variation("A37", "English Opening", "Symmetrical Variation, Two Knights Line", "c4 c5 Nc3 Nc6 g3 g6 Bg2 Bg7 Nf3"),
+    Opening::variation("A38", "English Opening", "Symmetrical Variation, Full Symmetry Line", "c4 c5 Nc3 Nc6 g3 g6 Bg2 Bg7 Nf3 Nf6"),
+    Opening::variation("A39", "English Opening", "Symmetrical Variation, Mecking Variation", "c4 c5 Nc3 Nc6 g3 g6 Bg2 Bg7 Nf3 Nf6 O-O O-O d4"),
+    Opening::new("A40", "Queen's Pawn Game", "d4"),
+    Opening::new("A40", "English Defense", "d4 e6 c4 b6"),
+    Opening::variation("A41", "Queen's Pawn Game", "Old Indian Defense", "d4 d6"),
+    Opening::variation("A42", "Modern Defense", "Averbakh System", "d4 d6 c4 g6 Nc3 Bg7 e4"),
+    Opening::variation("A43", "Benoni Defense", "Old Benoni", "d4 c5"),
+    Opening::variation("A44", "Benoni Defense", "Old Benoni, Czech Benoni", "d4 c5 d5 e5"),
+    Opening::new("A45", "Indian Defense", "d4 Nf6"),
+    Opening::new("A45", "Trompowsky Attack", "d4 Nf6 Bg5"),
+    Opening::variation("A46", "Indian Defense", "Knights Variation", "d4 Nf6 Nf3"),
+    Opening::new("A46", "Torre Attack", "d4 Nf6 Nf3 e6 Bg5"),
+    Opening::variation("A47", "Queen's Indian Defense", "Marienbad System", "d4 Nf6 Nf3 b6"),
+    Opening::new("A48", "East Indian Defense", "d4 Nf6 Nf3 g6"),
+    Opening::new("A48", "London System", "d4 Nf6 Nf3 g6 Bf4"),
+    Opening::variation("A49", "East Indian Defense", "Przepiorka Variation", "d4 Nf6 Nf3 g6 g3"),
+    Opening::variation("A50", "Indian Defense", "Normal Variation", "d4 Nf6 c4"),
+    Opening::variation("A51", "Budapest Gambit", "Fajarowicz Variation", "d4 Nf6 c4 e5 dxe5 Ne4"),
+    Opening::new("A52", "Budapest Gambit", "d4 Nf6 c4 e5 dxe5 Ng4"),
+    Opening::new("A53", "Old Indian Defense", "d4 Nf6 c4 d6"),
+    Opening::variation("A54", "Old Indian Defense", "Two Knights Variation", "d4 Nf6 c4 d6 Nc3 e5 Nf3"),
+    Opening::variation("A55", "Old Indian Defense", "Normal Variation", "d4 Nf6 c4 d6 Nc3 e5 Nf3 Nbd7 e4"),
+    Opening::new("A56", "Benoni Defense", "d4 Nf6 c4 c5"),
+    Opening::variation("A56", "Benoni Defense", "Czech Benoni", "d4 Nf6 c4 c5 d5 e5"),
+    Opening::new("A57", "Benko Gambit", "d4 Nf6 c4 c5 d5 b5"),
+    Opening::variation("A58", "Benko Gambit Accepted", "Fully Accepted Variation", "d4 Nf6 c4 c5 d5 b5 cxb5 a6 bxa6"),
+    Opening::variation("A59", "Benko Gambit Accepted", "King Walk Variation", "d4 Nf6 c4 c5 d5 b5 cxb5 a6 bxa6 Bxa6 Nc3 d6 e4"),
+    Opening::variation("A60", "Benoni Defense", "Modern Variation", "d4 Nf6 c4 c5 d5 e6"),
+    Opening::new("A61", "Benoni Defense", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 Nf3 g6"),
+    Opening::variation("A62", "Benoni Defense", "Fianchetto Variation", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 Nf3 g6 g3 Bg7 Bg2 O-O"),
+    Opening::variation("A63", "Benoni Defense", "Fianchetto Variation, 9...Nbd7", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 Nf3 g6 g3 Bg7 Bg2 O-O O-O Nbd7"),
+    Opening::variation("A64", "Benoni Defense", "Fianchetto Variation, 11...Re8", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 Nf3 g6 g3 Bg7 Bg2 O-O O-O Nbd7 Nd2 a6 a4 Re8"),
+    Opening::variation("A65", "Benoni Defense", "King's Pawn Line", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4"),
+    Opening::variation("A66", "Benoni Defense", "Pawn Storm Variation", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4 g6 f4"),
+    Opening::variation("A67", "Benoni Defense", "Taimanov Variation", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4 g6 f4 Bg7 Bb5+"),
+    Opening::variation("A68", "Benoni Defense", "Four Pawns Attack", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4 g6 f4 Bg7 Nf3 O-O"),
+    Opening::variation("A69", "Benoni Defense", "Four Pawns Attack, Main Line", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4 g6 f4 Bg7 Nf3 O-O Be2 Re8"),
+    Opening::variation("A70", "Benoni Defense", "Classical Variation", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4 g6 Nf3"),
+    Opening::variation("A71", "Benoni Defense", "Classical Variation, 8.Bg5", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4 g6 Nf3 Bg7 Bg5"),
+    Opening::variation("A72", "Benoni Defense", "Classical Variation, 8.Be2", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4 g6 Nf3 Bg7 Be2 O-O"),
+    Opening::variation("A73", "Benoni Defense", "Classical Variation, 9.O-O", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4 g6 Nf3 Bg7 Be2 O-O O-O"),
+    Opening::variation("A74", "Benoni Defense", "Classical Variation, 9...a6 10.a4", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4 g6 Nf3 Bg7 Be2 O-O O-O a6 a4"),
+    Opening::variation("A75", "Benoni Defense", "Classical Variation, Argentine Counterattack", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4 g6 Nf3 Bg7 Be2 O-O O-O a6 a4 Bg4"),
+    Opening::variation("A76", "Benoni Defense", "Classical Variation, Main Line", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4 g6 Nf3 Bg7 Be2 O-O O-O Re8"),
+    Opening::variation("A77", "Benoni Defense", "Classical Variation, Czerniak Defense, 10.Nd2", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4 g6 Nf3 Bg7 Be2 O-O O-O Re8 Nd2"),
+    Opening::variation("A78", "Benoni Defense", "Classical Variation, Czerniak Defense", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4 g6 Nf3 Bg7 Be2 O-O O-O Re8 Nd2 Na6"),
+    Opening::variation("A79", "Benoni Defense", "Classical Variation, Czerniak Defense, 11.f3", "d4 Nf6 c4 c5 d5 e6 Nc3 exd5 cxd5 d6 e4 g6 Nf3 Bg7 Be2 O-O O-O Re8 Nd2 Na6 f3"),
+    Opening::new("A80", "Dutch Defense", "d4 f5"),
+    Opening::variation("A81", "Dutch Defense", "Fianchetto Variation", "d4 f5 g3"),
+    Opening::variation("A82", "Dutch Defense", "Staunton Gambit", "d4 f5 e4"),
+    Opening::variation("A83", "Dutch Defense", "Staunton Gambit Accepted", "d4 f5 e4 fxe4 Nc3 Nf6 Bg5"),
+    Opening::variation("A84", "Dutch Defense", "Normal Variation", "d4 f5 c4"),
+    Opening::variation("A85", "Dutch Defense", "Queen's Knight Variation", "d4 f5 c4 Nf6 Nc3"),
+    Opening::variation("A86", "Dutch Defense", "Fianchetto Variation", "d4 f5 c4 Nf6 g3"),
+    Opening::variation("A87", "Dutch Defense", "Leningrad Variation", "d4 f5 c4 Nf6 g3 g6 Bg2 Bg7 Nf3"),
+    Opening::variation("A88", "Dutch Defense", "Leningrad Variation, Warsaw Variation", "d4 f5 c4 Nf6 g3 g6 Bg2 Bg7 Nf3 O-O O-O d6 Nc3 c6"),
+    Opening::variation("A89", "Dutch Defense", "Leningrad Variation, Matulovic Variation", "d4 f5 c4 Nf6 g3 g6 Bg2 Bg7 Nf3 O-O O-O d6 Nc3 Nc6"),
+    Opening::variation("A90", "Dutch Defense", "Classical Variation", "d4 f5 c4 Nf6 g3 e6 Bg2"),
+    Opening::variation("A91", "Dutch Defense", "Classical Variation, 4...Be7", "d4 f5 c4 Nf6 g3 e6 Bg2 Be7"),
+    Opening::variation("A92", "Dutch Defense", "Classical Variation, 5.Nf3 O-O", "d4 f5 c4 Nf6 g3 e6 Bg2 Be7 Nf3 O-O"),
+    Opening::variation("A93", "Dutch Defense", "Stonewall Variation, Botvinnik Variation", "d4 f5 c4 Nf6 g3 e6 Bg2 Be7 Nf3 O-O O-O d5 b3"),
+    Opening::variation("A94", "Dutch Defense", "Stonewall Variation with Ba3", "d4 f5 c4 Nf6 g3 e6 Bg2 Be7 Nf3 O-O O-O d5 b3 c6 Ba3"),
+    Opening::variation("A95", "Dutch Defense", "Stonewall Variation", "d4 f5 c4 Nf6 g3 e6 Bg2 Be7 Nf3 O-O O-O d5 Nc3 c6"),
+    Opening::variation("A96", "Dutch Defense", "Classical Variation, 6...d6", "d4 f5 c4 Nf6 g3 e6 Bg2 Be7 Nf3 O-O O-O d6"),
+    Opening::variation("A97", "Dutch Defense", "Ilyin-Zhenevsky Variation", "d4 f5 c4 Nf6 g3 e6 Bg2 Be7 Nf3 O-O O-O d6 Nc3 Qe8"),
+    Opening::variation("A98", "Dutch Defense", "Ilyin-Zhenevsky Variation, Alatortsev-Lisitsyn Line", "d4 f5 c4 Nf6 g3 e6 Bg2 Be7 Nf3 O-O O-O d6 Nc3 Qe8 Qc2"),
+    Opening::variation("A99", "Dutch Defense", "Ilyin-Zhenevsky Variation, Modern Main Line", "d4 f5 c4 Nf6 g3 e6 Bg2 Be7 Nf3 O-O O-O d6 Nc3 Qe8 b3"),
+    Opening::new("B00", "King's Pawn Game", "e4"),
+    Opening::new("B00", "Nimzowitsch Defense", "e4 Nc6"),
+    Opening::new("B00", "Owen Defense", "e4 b6"),
+    Opening::new("B00", "St. George Defense", "e4 a6"),
+    Opening::new("B01", "Scandinavian Defense", "e4 d5"),
+    Opening::variation("B01", "Scandinavian Defense", "Mieses-Kotroc Variation", "e4 d5 exd5 Qxd5"),
+    Opening::variation("B01", "Scandinavian Defense", "Modern Variation", "e4 d5 exd5 Nf6"),
+    Opening::new("B02", "Alekhine Defense", "e4 Nf6"),
+    Opening::new("B03", "Alekhine Defense", "e4 Nf6 e5 Nd5 d4"),
+    Opening::variation("B03", "Alekhine Defense", "Four Pawns Attack", "e4 Nf6 e5 Nd5 d4 d6 c4 Nb6 f4"),
+    Opening::variation("B04", "Alekhine Defense", "Modern Variation", "e4 Nf6 e5 Nd5 d4 d6 Nf3"),
+    Opening::variation("B05", "Alekhine Defense", "Modern Variation, Main Line", "e4 Nf6 e5 Nd5 d4 d6 Nf3 Bg4"),
+    Opening::new("B06", "Modern Defense", "e4 g6"),
+    Opening::new("B07", "Pirc Defense", "e4 d6 d4 Nf6"),
+    Opening::variation("B08", "Pirc Defense", "Classical Variation", "e4 d6 d4 Nf6 Nc3 g6 Nf3"),
+    Opening::variation("B09", "Pirc Defense", "Austrian Attack", "e4 d6 d4 Nf6 Nc3 g6 f4"),
+    Opening::new("B10", "Caro-Kann Defense", "e4 c6"),
+    Opening::variation("B11", "Caro-Kann Defense", "Two Knights Attack, Mindeno Variation", "e4 c6 Nc3 d5 Nf3 Bg4"),
+    Opening::new("B12", "Caro-Kann Defense", "e4 c6 d4 d5"),
+    Opening::variation("B12", "Caro-Kann Defense", "Advance Variation", "e4 c6 d4 d5 e5"),
+    Opening::variation("B13", "Caro-Kann Defense", "Exchange Variation", "e4 c6 d4 d5 exd5 cxd5"),
+    Opening::variation("B14", "Caro-Kann Defense", "Panov Attack", "e4 c6 d4 d5 exd5 cxd5 c4 Nf6 Nc3 e6"),
+    Opening::new("B15", "Caro-Kann Defense", "e4 c6 d4 d5 Nc3"),
+    Opening::variation("B16", "Caro-Kann Defense", "Bronstein-Larsen Variation", "e4 c6 d4 d5 Nc3 dxe4 Nxe4 Nf6 Nxf6+ gxf6"),
+    Opening::variation("B17", "Caro-Kann Defense", "Karpov Variation", "e4 c6 d4 d5 Nc3 dxe4 Nxe4 Nd7"),
+    Opening::variation("B18", "Caro-Kann Defense", "Classical Variation", "e4 c6 d4 d5 Nc3 dxe4 Nxe4 Bf5"),
+    Opening::variation("B19", "Caro-Kann Defense", "Classical Variation, Spassky Variation", "e4 c6 d4 d5 Nc3 dxe4 Nxe4 Bf5 Ng3 Bg6 h4 h6 Nf3 Nd7"),
+    Opening::new("B20", "Sicilian Defense", "e4 c5"),
+    Opening::variation("B21", "Sicilian Defense", "Smith-Morra Gambit", "e4 c5 d4 cxd4 c3"),
+    Opening::variation("B21", "Sicilian Defense", "McDonnell Attack", "e4 c5 f4"),
+    Opening::variation("B22", "Sicilian Defense", "Alapin Variation", "e4 c5 c3"),
+    Opening::variation("B23", "Sicilian Defense", "Closed", "e4 c5 Nc3"),
+    Opening::variation("B24", "Sicilian Defense", "Closed, Fianchetto", "e4 c5 Nc3 Nc6 g3"),
+    Opening::variation("B25", "Sicilian Defense", "Closed", "e4 c5 Nc3 Nc6 g3 g6 Bg2 Bg7 d3 d6"),
+    Opening::variation("B26", "Sicilian Defense", "Closed, 6.Be3", "e4 c5 Nc3 Nc6 g3 g6 Bg2 Bg7 d3 d6 Be3"),
+    Opening::new("B27", "Sicilian Defense", "e4 c5 Nf3"),
+    Opening::variation("B27", "Sicilian Defense", "Hyperaccelerated Dragon", "e4 c5 Nf3 g6"),
+    Opening::variation("B28", "Sicilian Defense", "O'Kelly Variation", "e4 c5 Nf3 a6"),
+    Opening::variation("B29", "Sicilian Defense", "Nimzowitsch Variation", "e4 c5 Nf3 Nf6"),
+    Opening::variation("B30", "Sicilian Defense", "Old Sicilian", "e4 c5 Nf3 Nc6"),
+    Opening::variation("B30", "Sicilian Defense", "Rossolimo Variation", "e4 c5 Nf3 Nc6 Bb5"),
+    Opening::variation("B31", "Sicilian Defense", "Rossolimo Variation, Fianchetto", "e4 c5 Nf3 Nc6 Bb5 g6"),
+    Opening::variation("B32", "Sicilian Defense", "Open", "e4 c5 Nf3 Nc6 d4 cxd4 Nxd4"),
+    Opening::variation("B32", "Sicilian Defense", "Lowenthal Variation", "e4 c5 Nf3 Nc6 d4 cxd4 Nxd4 e5"),
+    Opening::variation("B33", "Sicilian Defense", "Open, Lasker-Pelikan Variation", "e4 c5 Nf3 Nc6 d4 cxd4 Nxd4 Nf6"),
+    Opening::variation("B33", "Sicilian Defense", "Sveshnikov Variation", "e4 c5 Nf3 Nc6 d4 cxd4 Nxd4 Nf6 Nc3 e5 Ndb5 d6"),
+    Opening::variation("B34", "Sicilian Defense", "Accelerated Dragon, Exchange Variation", "e4 c5 Nf3 Nc6 d4 cxd4 Nxd4 g6 Nxc6"),
+    Opening::variation("B35", "Sicilian Defense", "Accelerated Dragon, Modern Bc4 Variation", "e4 c5 Nf3 Nc6 d4 cxd4 Nxd4 g6 Nc3 Bg7 Be3 Nf6 Bc4"),
+    Opening::variation("B36", "Sicilian Defense", "Accelerated Dragon, Maroczy Bind", "e4 c5 Nf3 Nc6 d4 cxd4 Nxd4 g6 c4"),
+    Opening::variation("B37", "Sicilian Defense", "Accelerated Dragon, Maroczy Bind, 5...Bg7", "e4 c5 Nf3 Nc6 d4 cxd4 Nxd4 g6 c4 Bg7"),
+    Opening::variation("B38", "Sicilian Defense", "Accelerated Dragon, Maroczy Bind, 6.Be3", "e4 c5 Nf3 Nc6 d4 cxd4 Nxd4 g6 c4 Bg7 Be3"),
+    Opening::variation("B39", "Sicilian Defense", "Accelerated Dragon, Maroczy Bind, Breyer Variation", "e4 c5 Nf3 Nc6 d4 cxd4 Nxd4 g6 c4 Bg7 Be3 Nf6 Nc3 Ng4"),
+    Opening::variation("B40", "Sicilian Defense", "French Variation", "e4 c5 Nf3 e6"),
+    Opening::variation("B41", "Sicilian Defense", "Kan Variation", "e4 c5 Nf3 e6 d4 cxd4 Nxd4 a6"),
+    Opening::variation("B42", "Sicilian Defense", "Kan Variation, Modern Variation", "e4 c5 Nf3 e6 d4 cxd4 Nxd4 a6 Bd3"),
+    Opening::variation("B43", "Sicilian Defense", "Kan Variation, Wing Attack", "e4 c5 Nf3 e6 d4 cxd4 Nxd4 a6 Nc3"),
+    Opening::variation("B44", "Sicilian Defense", "Taimanov Variation", "e4 c5 Nf3 e6 d4 cxd4 Nxd4 Nc6"),
+    Opening::variation("B45", "Sicilian Defense", "Taimanov Variation, Normal Variation", "e4 c5 Nf3 e6 d4 cxd4 Nxd4 Nc6 Nc3"),
+    Opening::variation("B46", "Sicilian Defense", "Taimanov Variation, 5...a6", "e4 c5 Nf3 e6 d4 cxd4 Nxd4 Nc6 Nc3 a6"),
+    Opening::variation("B47", "Sicilian Defense", "Taimanov Variation, Bastrikov Variation", "e4 c5 Nf3 e6 d4 cxd4 Nxd4 Nc6 Nc3 Qc7"),
+    Opening::variation("B48", "Sicilian Defense", "Taimanov Variation, English Attack", "e4 c5 Nf3 e6 d4 cxd4 Nxd4 Nc6 Nc3 Qc7 Be3"),
+    Opening::variation("B49", "Sicilian Defense", "Taimanov Variation, Bastrikov Variation, 7.Be2", "e4 c5 Nf3 e6 d4 cxd4 Nxd4 Nc6 Nc3 Qc7 Be3 a6 Be2"),
+    Opening::variation("B50", "Sicilian Defense", "Modern Variations", "e4 c5 Nf3 d6"),
+    Opening::variation("B51", "Sicilian Defense", "Moscow Variation", "e4 c5 Nf3 d6 Bb5+"),
+    Opening::variation("B52", "Sicilian Defense", "Moscow Variation, Main Line", "e4 c5 Nf3 d6 Bb5+ Bd7"),
+    Opening::variation("B53", "Sicilian Defense", "Chekhover Variation", "e4 c5 Nf3 d6 d4 cxd4 Qxd4"),
+    Opening::variation("B54", "Sicilian Defense", "Modern Variations, Main Line", "e4 c5 Nf3 d6 d4 cxd4 Nxd4"),
+    Opening::variation("B55", "Sicilian Defense", "Prins Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 f3"),
+    Opening::variation("B56", "Sicilian Defense", "Classical Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3"),
+    Opening::variation("B57", "Sicilian Defense", "Classical Variation, Sozin Attack", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 Nc6 Bc4"),
+    Opening::variation("B58", "Sicilian Defense", "Classical Variation, 6.Be2", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 Nc6 Be2"),
+    Opening::variation("B59", "Sicilian Defense", "Boleslavsky Variation, 7.Nb3", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 Nc6 Be2 e5 Nb3"),
+    Opening::variation("B60", "Sicilian Defense", "Richter-Rauzer Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 Nc6 Bg5"),
+    Opening::variation("B61", "Sicilian Defense", "Richter-Rauzer Variation, Larsen Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 Nc6 Bg5 Bd7 Qd2"),
+    Opening::variation("B62", "Sicilian Defense", "Richter-Rauzer Variation, 6...e6", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 Nc6 Bg5 e6"),
+    Opening::variation("B63", "Sicilian Defense", "Richter-Rauzer Variation, Classical Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 Nc6 Bg5 e6 Qd2"),
+    Opening::variation("B64", "Sicilian Defense", "Richter-Rauzer Variation, Classical Variation, 9.f4", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 Nc6 Bg5 e6 Qd2 Be7 O-O-O O-O f4"),
+    Opening::variation("B65", "Sicilian Defense", "Richter-Rauzer Variation, Classical Variation, 9...Nxd4", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 Nc6 Bg5 e6 Qd2 Be7 O-O-O O-O f4 Nxd4 Qxd4"),
+    Opening::variation("B66", "Sicilian Defense", "Richter-Rauzer Variation, Neo-Modern Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 Nc6 Bg5 e6 Qd2 a6"),
+    Opening::variation("B67", "Sicilian Defense", "Richter-Rauzer Variation, Neo-Modern Variation, 8...Bd7", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 Nc6 Bg5 e6 Qd2 a6 O-O-O Bd7"),
+    Opening::variation("B68", "Sicilian Defense", "Richter-Rauzer Variation, Neo-Modern Variation, 9...Be7", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 Nc6 Bg5 e6 Qd2 a6 O-O-O Bd7 f4 Be7"),
+    Opening::variation("B69", "Sicilian Defense", "Richter-Rauzer Variation, Neo-Modern Variation, 11.Bxf6", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 Nc6 Bg5 e6 Qd2 a6 O-O-O Bd7 f4 Be7 Nf3 b5 Bxf6"),
+    Opening::variation("B70", "Sicilian Defense", "Dragon Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 g6"),
+    Opening::variation("B71", "Sicilian Defense", "Dragon Variation, Levenfish Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 g6 f4"),
+    Opening::variation("B72", "Sicilian Defense", "Dragon Variation, 6.Be3", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 g6 Be3"),
+    Opening::variation("B73", "Sicilian Defense", "Dragon Variation, Classical Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 g6 Be3 Bg7 Be2 Nc6 O-O"),
+    Opening::variation("B74", "Sicilian Defense", "Dragon Variation, Classical Variation, Normal Line", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 g6 Be3 Bg7 Be2 Nc6 O-O O-O Nb3"),
+    Opening::variation("B75", "Sicilian Defense", "Dragon Variation, Yugoslav Attack", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 g6 Be3 Bg7 f3"),
+    Opening::variation("B76", "Sicilian Defense", "Dragon Variation, Yugoslav Attack, 7...O-O", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 g6 Be3 Bg7 f3 O-O"),
+    Opening::variation("B77", "Sicilian Defense", "Dragon Variation, Yugoslav Attack, 9.Bc4", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 g6 Be3 Bg7 f3 O-O Qd2 Nc6 Bc4"),
+    Opening::variation("B78", "Sicilian Defense", "Dragon Variation, Yugoslav Attack, 10.O-O-O", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 g6 Be3 Bg7 f3 O-O Qd2 Nc6 Bc4 Bd7 O-O-O"),
+    Opening::variation("B79", "Sicilian Defense", "Dragon Variation, Yugoslav Attack, 12.h4", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 g6 Be3 Bg7 f3 O-O Qd2 Nc6 Bc4 Bd7 O-O-O Qa5 Bb3 Rfc8 h4"),
+    Opening::variation("B80", "Sicilian Defense", "Scheveningen Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 e6"),
+    Opening::variation("B81", "Sicilian Defense", "Scheveningen Variation, Keres Attack", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 e6 g4"),
+    Opening::variation("B82", "Sicilian Defense", "Scheveningen Variation, 6.f4", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 e6 f4"),
+    Opening::variation("B83", "Sicilian Defense", "Scheveningen Variation, 6.Be2", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 e6 Be2"),
+    Opening::variation("B84", "Sicilian Defense", "Scheveningen Variation, Classical Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 e6 Be2 a6"),
+    Opening::variation("B85", "Sicilian Defense", "Scheveningen Variation, Classical Variation, Main Line", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 e6 Be2 a6 O-O Qc7 f4 Nc6"),
+    Opening::variation("B86", "Sicilian Defense", "Sozin Attack", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 e6 Bc4"),
+    Opening::variation("B87", "Sicilian Defense", "Sozin Attack, Flank Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 e6 Bc4 a6 Bb3 b5"),
+    Opening::variation("B88", "Sicilian Defense", "Sozin Attack, Leonhardt Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 e6 Bc4 Nc6"),
+    Opening::variation("B89", "Sicilian Defense", "Sozin Attack, 7.Be3", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 e6 Bc4 Nc6 Be3"),
+    Opening::variation("B90", "Sicilian Defense", "Najdorf Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6"),
+    Opening::variation("B90", "Sicilian Defense", "Najdorf Variation, English Attack", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6 Be3"),
+    Opening::variation("B91", "Sicilian Defense", "Najdorf Variation, Zagreb Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6 g3"),
+    Opening::variation("B92", "Sicilian Defense", "Najdorf Variation, Opocensky Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6 Be2"),
+    Opening::variation("B93", "Sicilian Defense", "Najdorf Variation, Amsterdam Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6 f4"),
+    Opening::variation("B94", "Sicilian Defense", "Najdorf Variation, 6.Bg5", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6 Bg5"),
+    Opening::variation("B95", "Sicilian Defense", "Najdorf Variation, 6...e6", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6 Bg5 e6"),
+    Opening::variation("B96", "Sicilian Defense", "Najdorf Variation, 7.f4", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6 Bg5 e6 f4"),
+    Opening::variation("B97", "Sicilian Defense", "Najdorf Variation, Poisoned Pawn Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6 Bg5 e6 f4 Qb6"),
+    Opening::variation("B98", "Sicilian Defense", "Najdorf Variation, 7...Be7", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6 Bg5 e6 f4 Be7"),
+    Opening::variation("B99", "Sicilian Defense", "Najdorf Variation, Main Line", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6 Bg5 e6 f4 Be7 Qf3 Qc7 O-O-O Nbd7"),
+    Opening::new("C00", "French Defense", "e4 e6"),
+    Opening::variation("C01", "French Defense", "Exchange Variation", "e4 e6 d4 d5 exd5"),
+    Opening::variation("C02", "French Defense", "Advance Variation", "e4 e6 d4 d5 e5"),
+    Opening::variation("C03", "French Defense", "Tarrasch Variation", "e4 e6 d4 d5 Nd2"),
+    Opening::variation("C04", "French Defense", "Tarrasch Variation, Guimard Defense", "e4 e6 d4 d5 Nd2 Nc6"),
+    Opening::variation("C05", "French Defense", "Tarrasch Variation, Closed Variation", "e4 e6 d4 d5 Nd2 Nf6"),
+    Opening::variation("C06", "French Defense", "Tarrasch Variation, Closed Variation, Main Line", "e4 e6 d4 d5 Nd2 Nf6 e5 Nfd7 Bd3 c5 c3 Nc6 Ne2"),
+    Opening::variation("C07", "French Defense", "Tarrasch Variation, Open System", "e4 e6 d4 d5 Nd2 c5"),
+    Opening::variation("C08", "French Defense", "Tarrasch Variation, Open System, Euwe-Keres Line", "e4 e6 d4 d5 Nd2 c5 exd5 exd5"),
+    Opening::variation("C09", "French Defense", "Tarrasch Variation, Open System, Main Line", "e4 e6 d4 d5 Nd2 c5 exd5 exd5 Ngf3 Nc6"),
+    Opening::variation("C10", "French Defense", "Paulsen Variation", "e4 e6 d4 d5 Nc3"),
+    Opening::variation("C10", "French Defense", "Rubinstein Variation", "e4 e6 d4 d5 Nc3 dxe4"),
+    Opening::variation("C11", "French Defense", "Classical Variation", "e4 e6 d4 d5 Nc3 Nf6"),
+    Opening::variation("C11", "French Defense", "Steinitz Variation", "e4 e6 d4 d5 Nc3 Nf6 e5"),
+    Opening::variation("C12", "French Defense", "MacCutcheon Variation", "e4 e6 d4 d5 Nc3 Nf6 Bg5 Bb4"),
+    Opening::variation("C13", "French Defense", "Classical Variation, Normal Variation", "e4 e6 d4 d5 Nc3 Nf6 Bg5 Be7"),
+    Opening::variation("C14", "French Defense", "Classical Variation, Main Line", "e4 e6 d4 d5 Nc3 Nf6 Bg5 Be7 e5 Nfd7 Bxe7 Qxe7"),
+    Opening::variation("C15", "French Defense", "Winawer Variation", "e4 e6 d4 d5 Nc3 Bb4"),
+    Opening::variation("C16", "French Defense", "Winawer Variation, Advance Variation", "e4 e6 d4 d5 Nc3 Bb4 e5"),
+    Opening::variation("C17", "French Defense", "Winawer Variation, Advance Variation, 4...c5", "e4 e6 d4 d5 Nc3 Bb4 e5 c5"),
+    Opening::variation("C18", "French Defense", "Winawer Variation, Classical Variation", "e4 e6 d4 d5 Nc3 Bb4 e5 c5 a3 Bxc3+ bxc3"),
+    Opening::variation("C19", "French Defense", "Winawer Variation, Poisoned Pawn Variation", "e4 e6 d4 d5 Nc3 Bb4 e5 c5 a3 Bxc3+ bxc3 Ne7 Qg4"),
+    Opening::variation("C19", "French Defense", "Winawer Variation, Advance Variation, 7.Nf3", "e4 e6 d4 d5 Nc3 Bb4 e5 c5 a3 Bxc3+ bxc3 Ne7 Nf3"),
+    Opening::new("C20", "King's Pawn Game", "e4 e5"),
+    Opening::variation("C20", "King's Pawn Game", "Wayward Queen Attack", "e4 e5 Qh5"),
+    Opening::new("C21", "Center Game", "e4 e5 d4"),
+    Opening::new("C21", "Danish Gambit", "e4 e5 d4 exd4 c3"),
+    Opening::variation("C22", "Center Game", "Paulsen Attack Variation", "e4 e5 d4 exd4 Qxd4 Nc6"),
+    Opening::new("C23", "Bishop's Opening", "e4 e5 Bc4"),
+    Opening::variation("C24", "Bishop's Opening", "Berlin Defense", "e4 e5 Bc4 Nf6"),
+    Opening::new("C25", "Vienna Game", "e4 e5 Nc3"),
+    Opening::variation("C26", "Vienna Game", "Falkbeer Variation", "e4 e5 Nc3 Nf6"),
+    Opening::variation("C27", "Vienna Game", "Frankenstein-Dracula Variation", "e4 e5 Nc3 Nf6 Bc4 Nxe4"),
+    Opening::variation("C28", "Vienna Game", "Stanley Variation, Three Knights Variation", "e4 e5 Nc3 Nf6 Bc4 Nc6"),
+    Opening::variation("C29", "Vienna Game", "Vienna Gambit", "e4 e5 Nc3 Nf6 f4"),
+    Opening::new("C30", "King's Gambit", "e4 e5 f4"),
+    Opening::variation("C30", "King's Gambit Declined", "Classical Variation", "e4 e5 f4 Bc5"),
+    Opening::variation("C31", "King's Gambit Declined", "Falkbeer Countergambit", "e4 e5 f4 d5"),
+    Opening::variation("C32", "King's Gambit Declined", "Falkbeer Countergambit, Morphy Gambit", "e4 e5 f4 d5 exd5 e4 d3 Nf6"),
+    Opening::new("C33", "King's Gambit Accepted", "e4 e5 f4 exf4"),
+    Opening::variation("C33", "King's Gambit Accepted", "Bishop's Gambit", "e4 e5 f4 exf4 Bc4"),
+    Opening::variation("C34", "King's Gambit Accepted", "King's Knight's Gambit", "e4 e5 f4 exf4 Nf3"),
+    Opening::variation("C34", "King's Gambit Accepted", "Fischer Defense", "e4 e5 f4 exf4 Nf3 d6"),
+    Opening::variation("C35", "King's Gambit Accepted", "Cunningham Defense", "e4 e5 f4 exf4 Nf3 Be7"),
+    Opening::variation("C36", "King's Gambit Accepted", "Modern Defense", "e4 e5 f4 exf4 Nf3 d5"),
+    Opening::variation("C37", "King's Gambit Accepted", "King's Knight's Gambit, Muzio Gambit Line", "e4 e5 f4 exf4 Nf3 g5 Bc4"),
+    Opening::variation("C37", "King's Gambit Accepted", "Quaade Gambit", "e4 e5 f4 exf4 Nf3 g5 Nc3"),
+    Opening::variation("C38", "King's Gambit Accepted", "Traxler Variation", "e4 e5 f4 exf4 Nf3 g5 Bc4 Bg7"),
+    Opening::variation("C39", "King's Gambit Accepted", "Kieseritzky Gambit Line", "e4 e5 f4 exf4 Nf3 g5 h4"),
+    Opening::new("C40", "King's Knight Opening", "e4 e5 Nf3"),
+    Opening::new("C40", "Latvian Gambit", "e4 e5 Nf3 f5"),
+    Opening::new("C40", "Elephant Gambit", "e4 e5 Nf3 d5"),
+    Opening::new("C41", "Philidor Defense", "e4 e5 Nf3 d6"),
+    Opening::new("C42", "Petrov's Defense", "e4 e5 Nf3 Nf6"),
+    Opening::variation("C42", "Petrov's Defense", "Classical Attack", "e4 e5 Nf3 Nf6 Nxe5 d6 Nf3 Nxe4 d4"),
+    Opening::variation("C43", "Petrov's Defense", "Modern Attack", "e4 e5 Nf3 Nf6 d4"),
+    Opening::variation("C44", "King's Knight Opening", "Normal Variation", "e4 e5 Nf3 Nc6"),
+    Opening::new("C44", "Ponziani Opening", "e4 e5 Nf3 Nc6 c3"),
+    Opening::new("C44", "Scotch Game", "e4 e5 Nf3 Nc6 d4"),
+    Opening::new("C44", "Scotch Gambit", "e4 e5 Nf3 Nc6 d4 exd4 Bc4"),
+    Opening::new("C45", "Scotch Game", "e4 e5 Nf3 Nc6 d4 exd4 Nxd4"),
+    Opening::variation("C45", "Scotch Game", "Classical Variation", "e4 e5 Nf3 Nc6 d4 exd4 Nxd4 Bc5"),
+    Opening::variation("C45", "Scotch Game", "Schmidt Variation", "e4 e5 Nf3 Nc6 d4 exd4 Nxd4 Nf6"),
+    Opening::new("C46", "Three Knights Opening", "e4 e5 Nf3 Nc6 Nc3"),
+    Opening::new("C47", "Four Knights Game", "e4 e5 Nf3 Nc6 Nc3 Nf6"),
+    Opening::variation("C47", "Four Knights Game", "Scotch Variation", "e4 e5 Nf3 Nc6 Nc3 Nf6 d4"),
+    Opening::variation("C48", "Four Knights Game", "Spanish Variation", "e4 e5 Nf3 Nc6 Nc3 Nf6 Bb5"),
+    Opening::variation("C48", "Four Knights Game", "Spanish Variation, Rubinstein Variation", "e4 e5 Nf3 Nc6 Nc3 Nf6 Bb5 Nd4"),
+    Opening::variation("C49", "Four Knights Game", "Double Spanish", "e4 e5 Nf3 Nc6 Nc3 Nf6 Bb5 Bb4"),
+    Opening::new("C50", "Italian Game", "e4 e5 Nf3 Nc6 Bc4"),
+    Opening::variation("C50", "Italian Game", "Giuoco Piano", "e4 e5 Nf3 Nc6 Bc4 Bc5"),
+    Opening::variation("C50", "Italian Game", "Hungarian Defense", "e4 e5 Nf3 Nc6 Bc4 Be7"),
+    Opening::variation("C50", "Italian Game", "Giuoco Pianissimo", "e4 e5 Nf3 Nc6 Bc4 Bc5 d3"),
+    Opening::variation("C51", "Italian Game", "Evans Gambit", "e4 e5 Nf3 Nc6 Bc4 Bc5 b4"),
+    Opening::variation("C52", "Italian Game", "Evans Gambit, Main Line", "e4 e5 Nf3 Nc6 Bc4 Bc5 b4 Bxb4 c3 Ba5"),
+    Opening::variation("C53", "Italian Game", "Classical Variation", "e4 e5 Nf3 Nc6 Bc4 Bc5 c3"),
+    Opening::variation("C54", "Italian Game", "Classical Variation, Center Attack", "e4 e5 Nf3 Nc6 Bc4 Bc5 c3 Nf6 d4 exd4 cxd4"),
+    Opening::variation("C55", "Italian Game", "Two Knights Defense", "e4 e5 Nf3 Nc6 Bc4 Nf6"),
+    Opening::variation("C55", "Italian Game", "Two Knights Defense, Modern Bishop's Opening", "e4 e5 Nf3 Nc6 Bc4 Nf6 d3"),
+    Opening::variation("C56", "Italian Game", "Scotch Gambit, Canal Variation", "e4 e5 Nf3 Nc6 Bc4 Nf6 d4 exd4 O-O Nxe4"),
+    Opening::variation("C57", "Italian Game", "Two Knights Defense, Knight Attack", "e4 e5 Nf3 Nc6 Bc4 Nf6 Ng5"),
+    Opening::variation("C57", "Italian Game", "Two Knights Defense, Fried Liver Attack", "e4 e5 Nf3 Nc6 Bc4 Nf6 Ng5 d5 exd5 Nxd5 Nxf7"),
+    Opening::variation("C57", "Italian Game", "Two Knights Defense, Traxler Counterattack", "e4 e5 Nf3 Nc6 Bc4 Nf6 Ng5 Bc5"),
+    Opening::variation("C58", "Italian Game", "Two Knights Defense, Polerio Defense", "e4 e5 Nf3 Nc6 Bc4 Nf6 Ng5 d5 exd5 Na5"),
+    Opening::variation("C59", "Italian Game", "Two Knights Defense, Polerio Defense, Main Line", "e4 e5 Nf3 Nc6 Bc4 Nf6 Ng5 d5 exd5 Na5 Bb5+ c6 dxc6 bxc6 Be2 h6"),
+    Opening::new("C60", "Ruy Lopez", "e4 e5 Nf3 Nc6 Bb5"),
+    Opening::variation("C60", "Ruy Lopez", "Cozio Defense", "e4 e5 Nf3 Nc6 Bb5 Nge7"),
+    Opening::variation("C61", "Ruy Lopez", "Bird Variation", "e4 e5 Nf3 Nc6 Bb5 Nd4"),
+    Opening::variation("C62", "Ruy Lopez", "Steinitz Defense", "e4 e5 Nf3 Nc6 Bb5 d6"),
+    Opening::variation("C63", "Ruy Lopez", "Schliemann Defense", "e4 e5 Nf3 Nc6 Bb5 f5"),
+    Opening::variation("C64", "Ruy Lopez", "Classical Variation", "e4 e5 Nf3 Nc6 Bb5 Bc5"),
+    Opening::variation("C65", "Ruy Lopez", "Berlin Defense", "e4 e5 Nf3 Nc6 Bb5 Nf6"),
+    Opening::variation("C66", "Ruy Lopez", "Berlin Defense, Improved Steinitz Defense", "e4 e5 Nf3 Nc6 Bb5 Nf6 O-O d6"),
+    Opening::variation("C67", "Ruy Lopez", "Berlin Defense, Rio Gambit Accepted", "e4 e5 Nf3 Nc6 Bb5 Nf6 O-O Nxe4"),
+    Opening::variation("C67", "Ruy Lopez", "Berlin Defense, Berlin Wall", "e4 e5 Nf3 Nc6 Bb5 Nf6 O-O Nxe4 d4 Nd6 Bxc6 dxc6 dxe5 Nf5 Qxd8+ Kxd8"),
+    Opening::variation("C68", "Ruy Lopez", "Exchange Variation", "e4 e5 Nf3 Nc6 Bb5 a6 Bxc6"),
+    Opening::variation("C69", "Ruy Lopez", "Exchange Variation, Gligoric Variation", "e4 e5 Nf3 Nc6 Bb5 a6 Bxc6 dxc6 O-O f6"),
+    Opening::variation("C70", "Ruy Lopez", "Morphy Defense", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4"),
+    Opening::variation("C71", "Ruy Lopez", "Morphy Defense, Modern Steinitz Defense", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 d6"),
+    Opening::variation("C72", "Ruy Lopez", "Morphy Defense, Modern Steinitz Defense, 5.O-O", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 d6 O-O"),
+    Opening::variation("C73", "Ruy Lopez", "Morphy Defense, Modern Steinitz Defense, Richter Variation", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 d6 Bxc6+ bxc6 d4"),
+    Opening::variation("C74", "Ruy Lopez", "Morphy Defense, Modern Steinitz Defense, Siesta Variation Line", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 d6 c3"),
+    Opening::variation("C75", "Ruy Lopez", "Morphy Defense, Modern Steinitz Defense, 5...Bd7", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 d6 c3 Bd7"),
+    Opening::variation("C76", "Ruy Lopez", "Morphy Defense, Modern Steinitz Defense, Fianchetto Variation", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 d6 c3 Bd7 d4 g6"),
+    Opening::variation("C77", "Ruy Lopez", "Morphy Defense, Anderssen Variation Line", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6"),
+    Opening::variation("C78", "Ruy Lopez", "Morphy Defense, 5.O-O", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O"),
+    Opening::variation("C78", "Ruy Lopez", "Morphy Defense, Arkhangelsk Variation", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O b5 Bb3 Bb7"),
+    Opening::variation("C79", "Ruy Lopez", "Steinitz Defense Deferred", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O d6"),
+    Opening::variation("C80", "Ruy Lopez", "Open", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Nxe4"),
+    Opening::variation("C81", "Ruy Lopez", "Open, Howell Attack", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Nxe4 d4 b5 Bb3 d5 dxe5 Be6 Qe2"),
+    Opening::variation("C82", "Ruy Lopez", "Open, 9.c3", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Nxe4 d4 b5 Bb3 d5 dxe5 Be6 c3"),
+    Opening::variation("C83", "Ruy Lopez", "Open, Classical Defense", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Nxe4 d4 b5 Bb3 d5 dxe5 Be6 c3 Be7"),
+    Opening::variation("C84", "Ruy Lopez", "Closed", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7"),
+    Opening::variation("C85", "Ruy Lopez", "Closed, Delayed Exchange", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Bxc6"),
+    Opening::variation("C86", "Ruy Lopez", "Worrall Attack", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Qe2"),
+    Opening::variation("C87", "Ruy Lopez", "Closed, Averbakh Variation", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 d6"),
+    Opening::variation("C88", "Ruy Lopez", "Closed, 7.Bb3", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3"),
+    Opening::variation("C88", "Ruy Lopez", "Closed, Anti-Marshall", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 O-O a4"),
+    Opening::variation("C89", "Ruy Lopez", "Marshall Attack", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 O-O c3 d5"),
+    Opening::variation("C90", "Ruy Lopez", "Closed, 8.c3", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 d6 c3 O-O"),
+    Opening::variation("C91", "Ruy Lopez", "Closed, 9.d4", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 d6 c3 O-O d4"),
+    Opening::variation("C92", "Ruy Lopez", "Closed, 9.h3", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 d6 c3 O-O h3"),
+    Opening::variation("C92", "Ruy Lopez", "Closed, Zaitsev System", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 d6 c3 O-O h3 Bb7"),
+    Opening::variation("C93", "Ruy Lopez", "Closed, Smyslov Defense", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 d6 c3 O-O h3 h6"),
+    Opening::variation("C94", "Ruy Lopez", "Closed, Breyer Defense", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 d6 c3 O-O h3 Nb8"),
+    Opening::variation("C95", "Ruy Lopez", "Closed, Breyer Defense, 10.d4", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 d6 c3 O-O h3 Nb8 d4"),
+    Opening::variation("C96", "Ruy Lopez", "Closed, Chigorin Defense", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 d6 c3 O-O h3 Na5 Bc2"),
+    Opening::variation("C97", "Ruy Lopez", "Closed, Chigorin Defense, 11...Qc7", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 d6 c3 O-O h3 Na5 Bc2 c5 d4 Qc7"),
+    Opening::variation("C98", "Ruy Lopez", "Closed, Chigorin Defense, Rauzer Attack", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 d6 c3 O-O h3 Na5 Bc2 c5 d4 Qc7 Nbd2 Nc6"),
+    Opening::variation("C99", "Ruy Lopez", "Closed, Chigorin Defense, Panov System", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 d6 c3 O-O h3 Na5 Bc2 c5 d4 Qc7 Nbd2 cxd4 cxd4"),
+    Opening::new("D00", "Queen's Pawn Game", "d4 d5"),
+    Opening::new("D00", "Blackmar-Diemer Gambit", "d4 d5 e4 dxe4 Nc3"),
+    Opening::variation("D00", "Queen's Pawn Game", "Accelerated London System", "d4 d5 Bf4"),
+    Opening::new("D01", "Richter-Veresov Attack", "d4 d5 Nc3 Nf6 Bg5"),
+    Opening::variation("D02", "Queen's Pawn Game", "Zukertort Variation", "d4 d5 Nf3"),
+    Opening::new("D02", "London System", "d4 d5 Nf3 Nf6 Bf4"),
+    Opening::new("D03", "Torre Attack", "d4 d5 Nf3 Nf6 Bg5"),
+    Opening::variation("D04", "Queen's Pawn Game", "Colle System", "d4 d5 Nf3 Nf6 e3"),
+    Opening::variation("D05", "Queen's Pawn Game", "Colle System, 3...e6", "d4 d5 Nf3 Nf6 e3 e6"),
+    Opening::new("D06", "Queen's Gambit", "d4 d5 c4"),
+    Opening::variation("D06", "Queen's Gambit Refused", "Baltic Defense", "d4 d5 c4 Bf5"),
+    Opening::variation("D06", "Queen's Gambit Refused", "Marshall Defense", "d4 d5 c4 Nf6"),
+    Opening::variation("D07", "Queen's Gambit Declined", "Chigorin Defense", "d4 d5 c4 Nc6"),
+    Opening::variation("D08", "Queen's Gambit Declined", "Albin Countergambit", "d4 d5 c4 e5"),
+    Opening::variation("D09", "Queen's Gambit Declined", "Albin Countergambit, Fianchetto Variation", "d4 d5 c4 e5 dxe5 d4 Nf3 Nc6 g3"),
+    Opening::new("D10", "Slav Defense", "d4 d5 c4 c6"),
+    Opening::variation("D10", "Slav Defense", "Exchange Variation", "d4 d5 c4 c6 cxd5 cxd5"),
+    Opening::variation("D11", "Slav Defense", "Modern Line", "d4 d5 c4 c6 Nf3"),
+    Opening::variation("D12", "Slav Defense", "Quiet Variation", "d4 d5 c4 c6 Nf3 Nf6 e3 Bf5"),
+    Opening::variation("D13", "Slav Defense", "Exchange Variation, 4.Nf3", "d4 d5 c4 c6 Nf3 Nf6 cxd5 cxd5"),
+    Opening::variation("D14", "Slav Defense", "Exchange Variation, Symmetrical Line", "d4 d5 c4 c6 Nf3 Nf6 cxd5 cxd5 Nc3 Nc6 Bf4 Bf5"),
+    Opening::variation("D15", "Slav Defense", "Three Knights Variation", "d4 d5 c4 c6 Nf3 Nf6 Nc3"),
+    Opening::variation("D15", "Slav Defense", "Chebanenko Variation", "d4 d5 c4 c6 Nf3 Nf6 Nc3 a6"),
+    Opening::variation("D16", "Slav Defense", "Alapin Variation", "d4 d5 c4 c6 Nf3 Nf6 Nc3 dxc4 a4"),
+    Opening::variation("D17", "Slav Defense", "Czech Variation", "d4 d5 c4 c6 Nf3 Nf6 Nc3 dxc4 a4 Bf5"),
+    Opening::variation("D18", "Slav Defense", "Czech Variation, Classical System", "d4 d5 c4 c6 Nf3 Nf6 Nc3 dxc4 a4 Bf5 e3"),
+    Opening::variation("D19", "Slav Defense", "Czech Variation, Dutch Variation", "d4 d5 c4 c6 Nf3 Nf6 Nc3 dxc4 a4 Bf5 e3 e6 Bxc4 Bb4 O-O"),
+    Opening::new("D20", "Queen's Gambit Accepted", "d4 d5 c4 dxc4"),
+    Opening::variation("D20", "Queen's Gambit Accepted", "Central Variation", "d4 d5 c4 dxc4 e4"),
+    Opening::variation("D21", "Queen's Gambit Accepted", "3.Nf3", "d4 d5 c4 dxc4 Nf3"),
+    Opening::variation("D22", "Queen's Gambit Accepted", "Alekhine Defense", "d4 d5 c4 dxc4 Nf3 a6"),
+    Opening::variation("D23", "Queen's Gambit Accepted", "3...Nf6", "d4 d5 c4 dxc4 Nf3 Nf6"),
+    Opening::variation("D24", "Queen's Gambit Accepted", "4.Nc3", "d4 d5 c4 dxc4 Nf3 Nf6 Nc3"),
+    Opening::variation("D25", "Queen's Gambit Accepted", "Normal Variation", "d4 d5 c4 dxc4 Nf3 Nf6 e3"),
+    Opening::variation("D26", "Queen's Gambit Accepted", "Classical Defense", "d4 d5 c4 dxc4 Nf3 Nf6 e3 e6"),
+    Opening::variation("D27", "Queen's Gambit Accepted", "Classical Defense, Main Line", "d4 d5 c4 dxc4 Nf3 Nf6 e3 e6 Bxc4 c5 O-O a6"),
+    Opening::variation("D28", "Queen's Gambit Accepted", "Classical Defense, 7.Qe2", "d4 d5 c4 dxc4 Nf3 Nf6 e3 e6 Bxc4 c5 O-O a6 Qe2"),
+    Opening::variation("D29", "Queen's Gambit Accepted", "Classical Defense, 8...Bb7", "d4 d5 c4 dxc4 Nf3 Nf6 e3 e6 Bxc4 c5 O-O a6 Qe2 b5 Bb3 Bb7"),
+    Opening::new("D30", "Queen's Gambit Declined", "d4 d5 c4 e6"),
+    Opening::variation("D30", "Queen's Gambit Declined", "Three Knights Variation", "d4 d5 c4 e6 Nf3"),
+    Opening::variation("D31", "Queen's Gambit Declined", "Queen's Knight Variation", "d4 d5 c4 e6 Nc3"),
+    Opening::variation("D31", "Semi-Slav Defense", "Marshall Gambit", "d4 d5 c4 e6 Nc3 c6 e4"),
+    Opening::new("D32", "Tarrasch Defense", "d4 d5 c4 e6 Nc3 c5"),
+    Opening::variation("D33", "Tarrasch Defense", "Prague Variation", "d4 d5 c4 e6 Nc3 c5 cxd5 exd5 Nf3 Nc6 g3"),
+    Opening::variation("D34", "Tarrasch Defense", "Prague Variation, Main Line", "d4 d5 c4 e6 Nc3 c5 cxd5 exd5 Nf3 Nc6 g3 Nf6 Bg2 Be7"),
+    Opening::variation("D35", "Queen's Gambit Declined", "Normal Defense", "d4 d5 c4 e6 Nc3 Nf6"),
+    Opening::variation("D35", "Queen's Gambit Declined", "Exchange Variation", "d4 d5 c4 e6 Nc3 Nf6 cxd5 exd5"),
+    Opening::variation("D36", "Queen's Gambit Declined", "Exchange Variation, Reshevsky Variation", "d4 d5 c4 e6 Nc3 Nf6 cxd5 exd5 Bg5 c6 Qc2"),
+    Opening::variation("D37", "Queen's Gambit Declined", "Three Knights Variation", "d4 d5 c4 e6 Nc3 Nf6 Nf3"),
+    Opening::variation("D37", "Queen's Gambit Declined", "Harrwitz Attack", "d4 d5 c4 e6 Nc3 Nf6 Nf3 Be7 Bf4"),
+    Opening::variation("D38", "Queen's Gambit Declined", "Ragozin Defense", "d4 d5 c4 e6 Nc3 Nf6 Nf3 Bb4"),
+    Opening::variation("D39", "Queen's Gambit Declined", "Ragozin Defense, Vienna Variation", "d4 d5 c4 e6 Nc3 Nf6 Nf3 Bb4 Bg5 dxc4"),
+    Opening::variation("D40", "Queen's Gambit Declined", "Semi-Tarrasch Defense", "d4 d5 c4 e6 Nc3 Nf6 Nf3 c5"),
+    Opening::variation("D41", "Queen's Gambit Declined", "Semi-Tarrasch Defense, 5.cxd5", "d4 d5 c4 e6 Nc3 Nf6 Nf3 c5 cxd5"),
+    Opening::variation("D42", "Queen's Gambit Declined", "Semi-Tarrasch Defense, Main Line", "d4 d5 c4 e6 Nc3 Nf6 Nf3 c5 cxd5 Nxd5 e3 Nc6 Bd3"),
+    Opening::new("D43", "Semi-Slav Defense", "d4 d5 c4 e6 Nc3 Nf6 Nf3 c6"),
+    Opening::variation("D43", "Semi-Slav Defense", "Moscow Variation", "d4 d5 c4 e6 Nc3 Nf6 Nf3 c6 Bg5 h6"),
+    Opening::variation("D44", "Semi-Slav Defense", "Botvinnik System", "d4 d5 c4 e6 Nc3 Nf6 Nf3 c6 Bg5 dxc4"),
+    Opening::variation("D45", "Semi-Slav Defense", "Normal Variation", "d4 d5 c4 e6 Nc3 Nf6 Nf3 c6 e3"),
+    Opening::variation("D45", "Semi-Slav Defense", "Anti-Meran Variation", "d4 d5 c4 e6 Nc3 Nf6 Nf3 c6 e3 Nbd7 Qc2"),
+    Opening::variation("D46", "Semi-Slav Defense", "Main Line", "d4 d5 c4 e6 Nc3 Nf6 Nf3 c6 e3 Nbd7 Bd3"),
+    Opening::variation("D47", "Semi-Slav Defense", "Meran Variation", "d4 d5 c4 e6 Nc3 Nf6 Nf3 c6 e3 Nbd7 Bd3 dxc4 Bxc4"),
+    Opening::variation("D48", "Semi-Slav Defense", "Meran Variation, 8...a6", "d4 d5 c4 e6 Nc3 Nf6 Nf3 c6 e3 Nbd7 Bd3 dxc4 Bxc4 b5 Bd3 a6"),
+    Opening::variation("D49", "Semi-Slav Defense", "Meran Variation, Blumenfeld Variation", "d4 d5 c4 e6 Nc3 Nf6 Nf3 c6 e3 Nbd7 Bd3 dxc4 Bxc4 b5 Bd3 a6 e4 c5 e5 cxd4 Nxb5"),
+    Opening::variation("D50", "Queen's Gambit Declined", "Modern Variation", "d4 d5 c4 e6 Nc3 Nf6 Bg5"),
+    Opening::variation("D51", "Queen's Gambit Declined", "Modern Variation, 4...Nbd7", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Nbd7"),
+    Opening::variation("D52", "Queen's Gambit Declined", "Cambridge Springs Defense", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Nbd7 e3 c6 Nf3 Qa5"),
+    Opening::variation("D53", "Queen's Gambit Declined", "Modern Variation, Heral Variation", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7"),
+    Opening::variation("D54", "Queen's Gambit Declined", "Anti-Neo-Orthodox Variation", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Rc1"),
+    Opening::variation("D55", "Queen's Gambit Declined", "Neo-Orthodox Variation", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3"),
+    Opening::variation("D56", "Queen's Gambit Declined", "Lasker Defense", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3 h6 Bh4 Ne4"),
+    Opening::variation("D57", "Queen's Gambit Declined", "Lasker Defense, Main Line", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3 h6 Bh4 Ne4 Bxe7 Qxe7 cxd5 Nxc3 bxc3"),
+    Opening::variation("D58", "Queen's Gambit Declined", "Tartakower Defense", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3 h6 Bh4 b6"),
+    Opening::variation("D59", "Queen's Gambit Declined", "Tartakower Defense, 8.cxd5 Nxd5", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3 h6 Bh4 b6 cxd5 Nxd5"),
+    Opening::variation("D60", "Queen's Gambit Declined", "Orthodox Defense", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3 Nbd7"),
+    Opening::variation("D61", "Queen's Gambit Declined", "Orthodox Defense, Rubinstein Variation", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3 Nbd7 Qc2"),
+    Opening::variation("D62", "Queen's Gambit Declined", "Orthodox Defense, Rubinstein Variation, Flohr Line", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3 Nbd7 Qc2 c5 cxd5"),
+    Opening::variation("D63", "Queen's Gambit Declined", "Orthodox Defense, 7.Rc1", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3 Nbd7 Rc1"),
+    Opening::variation("D64", "Queen's Gambit Declined", "Orthodox Defense, Rubinstein Attack", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3 Nbd7 Rc1 c6 Qc2"),
+    Opening::variation("D65", "Queen's Gambit Declined", "Orthodox Defense, Rubinstein Attack, Main Line", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3 Nbd7 Rc1 c6 Qc2 a6 cxd5"),
+    Opening::variation("D66", "Queen's Gambit Declined", "Orthodox Defense, Main Line", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3 Nbd7 Rc1 c6 Bd3"),
+    Opening::variation("D67", "Queen's Gambit Declined", "Orthodox Defense, Main Line, Capablanca Variation", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3 Nbd7 Rc1 c6 Bd3 dxc4 Bxc4 Nd5"),
+    Opening::variation("D68", "Queen's Gambit Declined", "Orthodox Defense, Classical Variation", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3 Nbd7 Rc1 c6 Bd3 dxc4 Bxc4 Nd5 Bxe7 Qxe7 O-O Nxc3 Rxc3 e5"),
+    Opening::variation("D69", "Queen's Gambit Declined", "Orthodox Defense, Classical Variation, 13.dxe5", "d4 d5 c4 e6 Nc3 Nf6 Bg5 Be7 e3 O-O Nf3 Nbd7 Rc1 c6 Bd3 dxc4 Bxc4 Nd5 Bxe7 Qxe7 O-O Nxc3 Rxc3 e5 dxe5 Nxe5 Nxe5 Qxe5"),
+    Opening::new("D70", "Neo-Grunfeld Defense", "d4 Nf6 c4 g6 f3 d5"),
+    Opening::new("D71", "Neo-Grunfeld Defense", "d4 Nf6 c4 g6 g3 d5"),
+    Opening::variation("D72", "Neo-Grunfeld Defense", "Exchange Variation, 7.Ne2", "d4 Nf6 c4 g6 g3 d5 Bg2 Bg7 cxd5 Nxd5 e4 Nb6 Ne2"),
+    Opening::variation("D73", "Neo-Grunfeld Defense", "Goglidze Attack", "d4 Nf6 c4 g6 g3 d5 Bg2 Bg7 Nf3"),
+    Opening::variation("D74", "Neo-Grunfeld Defense", "6.cxd5 Nxd5 7.O-O", "d4 Nf6 c4 g6 g3 d5 Bg2 Bg7 Nf3 O-O cxd5 Nxd5 O-O"),
+    Opening::variation("D75", "Neo-Grunfeld Defense", "6.cxd5 Nxd5 7.O-O c5 8.Nc3", "d4 Nf6 c4 g6 g3 d5 Bg2 Bg7 Nf3 O-O cxd5 Nxd5 O-O c5 Nc3"),
+    Opening::variation("D76", "Neo-Grunfeld Defense", "6.cxd5 Nxd5 7.O-O Nb6", "d4 Nf6 c4 g6 g3 d5 Bg2 Bg7 Nf3 O-O cxd5 Nxd5 O-O Nb6"),
+    Opening::variation("D77", "Neo-Grunfeld Defense", "Classical Variation", "d4 Nf6 c4 g6 g3 d5 Bg2 Bg7 Nf3 O-O O-O"),
+    Opening::variation("D78", "Neo-Grunfeld Defense", "Classical Variation, Original Defense", "d4 Nf6 c4 g6 g3 d5 Bg2 Bg7 Nf3 O-O O-O c6"),
+    Opening::variation("D79", "Neo-Grunfeld Defense", "Ultra-Delayed Exchange Variation", "d4 Nf6 c4 g6 g3 d5 Bg2 Bg7 Nf3 O-O O-O c6 cxd5 cxd5"),
+    Opening::new("D80", "Grunfeld Defense", "d4 Nf6 c4 g6 Nc3 d5"),
+    Opening::variation("D81", "Grunfeld Defense", "Russian Variation, Accelerated Variation", "d4 Nf6 c4 g6 Nc3 d5 Qb3"),
+    Opening::variation("D82", "Grunfeld Defense", "Brinckmann Attack", "d4 Nf6 c4 g6 Nc3 d5 Bf4"),
+    Opening::variation("D83", "Grunfeld Defense", "Brinckmann Attack, Grunfeld Gambit", "d4 Nf6 c4 g6 Nc3 d5 Bf4 Bg7 e3 O-O"),
+    Opening::variation("D84", "Grunfeld Defense", "Grunfeld Gambit Accepted", "d4 Nf6 c4 g6 Nc3 d5 Bf4 Bg7 e3 O-O cxd5 Nxd5 Nxd5 Qxd5 Bxc7"),
+    Opening::variation("D85", "Grunfeld Defense", "Exchange Variation", "d4 Nf6 c4 g6 Nc3 d5 cxd5 Nxd5"),
+    Opening::variation("D85", "Grunfeld Defense", "Exchange Variation, Modern Exchange Variation", "d4 Nf6 c4 g6 Nc3 d5 cxd5 Nxd5 e4 Nxc3 bxc3 Bg7 Nf3"),
+    Opening::variation("D86", "Grunfeld Defense", "Exchange Variation, Classical Variation", "d4 Nf6 c4 g6 Nc3 d5 cxd5 Nxd5 e4 Nxc3 bxc3 Bg7 Bc4"),
+    Opening::variation("D87", "Grunfeld Defense", "Exchange Variation, Spassky Variation", "d4 Nf6 c4 g6 Nc3 d5 cxd5 Nxd5 e4 Nxc3 bxc3 Bg7 Bc4 O-O Ne2 c5"),
+    Opening::variation("D88", "Grunfeld Defense", "Exchange Variation, Spassky Variation, Main Line", "d4 Nf6 c4 g6 Nc3 d5 cxd5 Nxd5 e4 Nxc3 bxc3 Bg7 Bc4 O-O Ne2 c5 O-O Nc6 Be3 cxd4 cxd4"),
+    Opening::variation("D89", "Grunfeld Defense", "Exchange Variation, Spassky Variation, Main Line, 13.Bd3", "d4 Nf6 c4 g6 Nc3 d5 cxd5 Nxd5 e4 Nxc3 bxc3 Bg7 Bc4 O-O Ne2 c5 O-O Nc6 Be3 cxd4 cxd4 Bg4 f3 Na5 Bd3 Be6"),
+    Opening::variation("D90", "Grunfeld Defense", "Three Knights Variation", "d4 Nf6 c4 g6 Nc3 d5 Nf3"),
+    Opening::variation("D91", "Grunfeld Defense", "Three Knights Variation, Petrosian System", "d4 Nf6 c4 g6 Nc3 d5 Nf3 Bg7 Bg5"),
+    Opening::variation("D92", "Grunfeld Defense", "Three Knights Variation, Hungarian Attack", "d4 Nf6 c4 g6 Nc3 d5 Nf3 Bg7 Bf4"),
+    Opening::variation("D93", "Grunfeld Defense", "Three Knights Variation, Hungarian Variation, 6.e3", "d4 Nf6 c4 g6 Nc3 d5 Nf3 Bg7 Bf4 O-O e3"),
+    Opening::variation("D94", "Grunfeld Defense", "Three Knights Variation, Burille Variation", "d4 Nf6 c4 g6 Nc3 d5 Nf3 Bg7 e3"),
+    Opening::variation("D95", "Grunfeld Defense", "Three Knights Variation, Vienna Variation", "d4 Nf6 c4 g6 Nc3 d5 Nf3 Bg7 e3 O-O Qb3"),
+    Opening::variation("D96", "Grunfeld Defense", "Russian Variation", "d4 Nf6 c4 g6 Nc3 d5 Nf3 Bg7 Qb3"),
+    Opening::variation("D97", "Grunfeld Defense", "Russian Variation, 7.e4", "d4 Nf6 c4 g6 Nc3 d5 Nf3 Bg7 Qb3 dxc4 Qxc4 O-O e4"),
+    Opening::variation("D97", "Grunfeld Defense", "Russian Variation, Hungarian Variation", "d4 Nf6 c4 g6 Nc3 d5 Nf3 Bg7 Qb3 dxc4 Qxc4 O-O e4 a6"),
+    Opening::variation("D98", "Grunfeld Defense", "Russian Variation, Smyslov Variation", "d4 Nf6 c4 g6 Nc3 d5 Nf3 Bg7 Qb3 dxc4 Qxc4 O-O e4 Bg4"),
+    Opening::variation("D99", "Grunfeld Defense", "Russian Variation, Smyslov Variation, Main Line", "d4 Nf6 c4 g6 Nc3 d5 Nf3 Bg7 Qb3 dxc4 Qxc4 O-O e4 Bg4 Be3 Nfd7"),
+    Opening::variation("E00", "Indian Defense", "East Indian Defense", "d4 Nf6 c4 e6"),
+    Opening::new("E00", "Catalan Opening", "d4 Nf6 c4 e6 g3"),
+    Opening::variation("E01", "Catalan Opening", "Closed", "d4 Nf6 c4 e6 g3 d5 Bg2"),
+    Opening::variation("E02", "Catalan Opening", "Open Defense, 5.Qa4", "d4 Nf6 c4 e6 g3 d5 Bg2 dxc4 Qa4+"),
+    Opening::variation("E03", "Catalan Opening", "Open Defense, Alekhine Variation", "d4 Nf6 c4 e6 g3 d5 Bg2 dxc4 Qa4+ Nbd7 Qxc4"),
+    Opening::variation("E04", "Catalan Opening", "Open Defense", "d4 Nf6 c4 e6 g3 d5 Bg2 dxc4 Nf3"),
+    Opening::variation("E05", "Catalan Opening", "Open Defense, Classical Line", "d4 Nf6 c4 e6 g3 d5 Bg2 dxc4 Nf3 Be7"),
+    Opening::variation("E06", "Catalan Opening", "Closed Variation", "d4 Nf6 c4 e6 g3 d5 Bg2 Be7 Nf3"),
+    Opening::variation("E07", "Catalan Opening", "Closed Variation, 6...Nbd7", "d4 Nf6 c4 e6 g3 d5 Bg2 Be7 Nf3 O-O O-O Nbd7"),
+    Opening::variation("E08", "Catalan Opening", "Closed Variation, 7.Qc2", "d4 Nf6 c4 e6 g3 d5 Bg2 Be7 Nf3 O-O O-O Nbd7 Qc2"),
+    Opening::variation("E09", "Catalan Opening", "Closed Variation, Main Line", "d4 Nf6 c4 e6 g3 d5 Bg2 Be7 Nf3 O-O O-O Nbd7 Qc2 c6 Nbd2"),
+    Opening::variation("E10", "Indian Defense", "Anti-Nimzo-Indian", "d4 Nf6 c4 e6 Nf3"),
+    Opening::new("E10", "Blumenfeld Countergambit", "d4 Nf6 c4 e6 Nf3 c5 d5 b5"),
+    Opening::new("E11", "Bogo-Indian Defense", "d4 Nf6 c4 e6 Nf3 Bb4+"),
+    Opening::new("E12", "Queen's Indian Defense", "d4 Nf6 c4 e6 Nf3 b6"),
+    Opening::variation("E12", "Queen's Indian Defense", "Petrosian Variation", "d4 Nf6 c4 e6 Nf3 b6 a3"),
+    Opening::variation("E13", "Queen's Indian Defense", "Kasparov Variation, 5.Bg5 h6 6.Bh4 Bb4", "d4 Nf6 c4 e6 Nf3 b6 Nc3 Bb7 Bg5 h6 Bh4 Bb4"),
+    Opening::variation("E14", "Queen's Indian Defense", "Spassky System", "d4 Nf6 c4 e6 Nf3 b6 e3"),
+    Opening::variation("E15", "Queen's Indian Defense", "Fianchetto Variation", "d4 Nf6 c4 e6 Nf3 b6 g3"),
+    Opening::variation("E15", "Queen's Indian Defense", "Fianchetto Variation, Nimzowitsch Variation", "d4 Nf6 c4 e6 Nf3 b6 g3 Ba6"),
+    Opening::variation("E16", "Queen's Indian Defense", "Capablanca Variation", "d4 Nf6 c4 e6 Nf3 b6 g3 Bb7 Bg2 Bb4+"),
+    Opening::variation("E17", "Queen's Indian Defense", "Fianchetto Variation, 5...Be7", "d4 Nf6 c4 e6 Nf3 b6 g3 Bb7 Bg2 Be7"),
+    Opening::variation("E18", "Queen's Indian Defense", "Fianchetto Variation, Old Main Line, 7.Nc3", "d4 Nf6 c4 e6 Nf3 b6 g3 Bb7 Bg2 Be7 O-O O-O Nc3"),
+    Opening::variation("E19", "Queen's Indian Defense", "Fianchetto Variation, Old Main Line, 9.Qxc3", "d4 Nf6 c4 e6 Nf3 b6 g3 Bb7 Bg2 Be7 O-O O-O Nc3 Ne4 Qc2 Nxc3 Qxc3"),
+    Opening::new("E20", "Nimzo-Indian Defense", "d4 Nf6 c4 e6 Nc3 Bb4"),
+    Opening::variation("E20", "Nimzo-Indian Defense", "Kmoch Variation", "d4 Nf6 c4 e6 Nc3 Bb4 f3"),
+    Opening::variation("E21", "Nimzo-Indian Defense", "Three Knights Variation", "d4 Nf6 c4 e6 Nc3 Bb4 Nf3"),
+    Opening::variation("E22", "Nimzo-Indian Defense", "Spielmann Variation", "d4 Nf6 c4 e6 Nc3 Bb4 Qb3"),
+    Opening::variation("E23", "Nimzo-Indian Defense", "Spielmann Variation, Karlsbad Variation", "d4 Nf6 c4 e6 Nc3 Bb4 Qb3 c5 dxc5 Nc6"),
+    Opening::variation("E24", "Nimzo-Indian Defense", "Samisch Variation", "d4 Nf6 c4 e6 Nc3 Bb4 a3 Bxc3+ bxc3"),
+    Opening::variation("E25", "Nimzo-Indian Defense", "Samisch Variation, Keres Variation", "d4 Nf6 c4 e6 Nc3 Bb4 a3 Bxc3+ bxc3 c5 f3 d5 cxd5"),
+    Opening::variation("E26", "Nimzo-Indian Defense", "Samisch Variation, 5...c5 6.e3", "d4 Nf6 c4 e6 Nc3 Bb4 a3 Bxc3+ bxc3 c5 e3"),
+    Opening::variation("E27", "Nimzo-Indian Defense", "Samisch Variation, 5...O-O", "d4 Nf6 c4 e6 Nc3 Bb4 a3 Bxc3+ bxc3 O-O"),
+    Opening::variation("E28", "Nimzo-Indian Defense", "Samisch Variation, 6.e3", "d4 Nf6 c4 e6 Nc3 Bb4 a3 Bxc3+ bxc3 O-O e3"),
+    Opening::variation("E29", "Nimzo-Indian Defense", "Samisch Variation, Main Line", "d4 Nf6 c4 e6 Nc3 Bb4 a3 Bxc3+ bxc3 O-O e3 c5 Bd3 Nc6"),
+    Opening::variation("E30", "Nimzo-Indian Defense", "Leningrad Variation", "d4 Nf6 c4 e6 Nc3 Bb4 Bg5"),
+    Opening::variation("E31", "Nimzo-Indian Defense", "Leningrad Variation, Main Line", "d4 Nf6 c4 e6 Nc3 Bb4 Bg5 h6 Bh4 c5 d5 d6"),
+    Opening::variation("E32", "Nimzo-Indian Defense", "Classical Variation", "d4 Nf6 c4 e6 Nc3 Bb4 Qc2"),
+    Opening::variation("E33", "Nimzo-Indian Defense", "Classical Variation, Milner-Barry Variation", "d4 Nf6 c4 e6 Nc3 Bb4 Qc2 Nc6"),
+    Opening::variation("E34", "Nimzo-Indian Defense", "Classical Variation, Noa Variation", "d4 Nf6 c4 e6 Nc3 Bb4 Qc2 d5"),
+    Opening::variation("E35", "Nimzo-Indian Defense", "Classical Variation, Noa Variation, 5.cxd5 exd5", "d4 Nf6 c4 e6 Nc3 Bb4 Qc2 d5 cxd5 exd5"),
+    Opening::variation("E36", "Nimzo-Indian Defense", "Classical Variation, Noa Variation, 5.a3", "d4 Nf6 c4 e6 Nc3 Bb4 Qc2 d5 a3"),
+    Opening::variation("E37", "Nimzo-Indian Defense", "Classical Variation, Noa Variation, Main Line", "d4 Nf6 c4 e6 Nc3 Bb4 Qc2 d5 a3 Bxc3+ Qxc3 Ne4 Qc2"),
+    Opening::variation("E38", "Nimzo-Indian Defense", "Classical Variation, 4...c5", "d4 Nf6 c4 e6 Nc3 Bb4 Qc2 c5"),
+    Opening::variation("E39", "Nimzo-Indian Defense", "Classical Variation, Pirc Variation", "d4 Nf6 c4 e6 Nc3 Bb4 Qc2 c5 dxc5 O-O"),
+    Opening::variation("E40", "Nimzo-Indian Defense", "Normal Variation", "d4 Nf6 c4 e6 Nc3 Bb4 e3"),
+    Opening::variation("E41", "Nimzo-Indian Defense", "Hubner Variation", "d4 Nf6 c4 e6 Nc3 Bb4 e3 c5"),
+    Opening::variation("E42", "Nimzo-Indian Defense", "Hubner Variation, Rubinstein Variation", "d4 Nf6 c4 e6 Nc3 Bb4 e3 c5 Ne2"),
+    Opening::variation("E43", "Nimzo-Indian Defense", "St. Petersburg Variation", "d4 Nf6 c4 e6 Nc3 Bb4 e3 b6"),
+    Opening::variation("E44", "Nimzo-Indian Defense", "Fischer Variation, 5.Ne2", "d4 Nf6 c4 e6 Nc3 Bb4 e3 b6 Ne2"),
+    Opening::variation("E45", "Nimzo-Indian Defense", "Normal Variation, Bronstein (Byrne) Variation", "d4 Nf6 c4 e6 Nc3 Bb4 e3 b6 Ne2 Ba6"),
+    Opening::variation("E46", "Nimzo-Indian Defense", "Normal Variation, 4...O-O", "d4 Nf6 c4 e6 Nc3 Bb4 e3 O-O"),
+    Opening::variation("E47", "Nimzo-Indian Defense", "Normal Variation, Bishop Attack", "d4 Nf6 c4 e6 Nc3 Bb4 e3 O-O Bd3"),
+    Opening::variation("E48", "Nimzo-Indian Defense", "Normal Variation, Bishop Attack, Classical Defense", "d4 Nf6 c4 e6 Nc3 Bb4 e3 O-O Bd3 d5"),
+    Opening::variation("E49", "Nimzo-Indian Defense", "Normal Variation, Botvinnik System", "d4 Nf6 c4 e6 Nc3 Bb4 e3 O-O Bd3 d5 a3 Bxc3+ bxc3"),
+    Opening::variation("E50", "Nimzo-Indian Defense", "Normal Variation, 5.Nf3", "d4 Nf6 c4 e6 Nc3 Bb4 e3 O-O Nf3"),
+    Opening::variation("E51", "Nimzo-Indian Defense", "Normal Variation, Ragozin Variation", "d4 Nf6 c4 e6 Nc3 Bb4 e3 O-O Nf3 d5"),
+    Opening::variation("E52", "Nimzo-Indian Defense", "Normal Variation, Schlechter Defense", "d4 Nf6 c4 e6 Nc3 Bb4 e3 O-O Nf3 d5 Bd3 b6"),
+    Opening::variation("E53", "Nimzo-Indian Defense", "Normal Variation, Gligoric System", "d4 Nf6 c4 e6 Nc3 Bb4 e3 O-O Nf3 d5 Bd3 c5"),
+    Opening::variation("E54", "Nimzo-Indian Defense", "Normal Variation, Gligoric System, Exchange at c4", "d4 Nf6 c4 e6 Nc3 Bb4 e3 O-O Nf3 d5 Bd3 c5 O-O dxc4 Bxc4"),
+    Opening::variation("E55", "Nimzo-Indian Defense", "Normal Variation, Gligoric System, Bronstein Variation", "d4 Nf6 c4 e6 Nc3 Bb4 e3 O-O Nf3 d5 Bd3 c5 O-O dxc4 Bxc4 Nbd7"),
+    Opening::variation("E56", "Nimzo-Indian Defense", "Normal Variation, Gligoric System, 7...Nc6", "d4 Nf6 c4 e6 Nc3 Bb4 e3 O-O Nf3 d5 Bd3 c5 O-O Nc6"),
+    Opening::variation("E57", "Nimzo-Indian Defense", "Normal Variation, Gligoric System, 8...dxc4 and 9...cxd4", "d4 Nf6 c4 e6 Nc3 Bb4 e3 O-O Nf3 d5 Bd3 c5 O-O Nc6 a3 dxc4 Bxc4 cxd4"),
+    Opening::variation("E58", "Nimzo-Indian Defense", "Normal Variation, Gligoric System, 8...Bxc3", "d4 Nf6 c4 e6 Nc3 Bb4 e3 O-O Nf3 d5 Bd3 c5 O-O Nc6 a3 Bxc3 bxc3"),
+    Opening::variation("E59", "Nimzo-Indian Defense", "Normal Variation, Gligoric System, Main Line", "d4 Nf6 c4 e6 Nc3 Bb4 e3 O-O Nf3 d5 Bd3 c5 O-O Nc6 a3 Bxc3 bxc3 dxc4 Bxc4 Qc7 Bd3"),
+    Opening::new("E60", "King's Indian Defense", "d4 Nf6 c4 g6"),
+    Opening::variation("E61", "King's Indian Defense", "3.Nc3", "d4 Nf6 c4 g6 Nc3"),
+    Opening::variation("E61", "King's Indian Defense", "Smyslov Variation", "d4 Nf6 c4 g6 Nc3 Bg7 Nf3 d6 Bg5"),
+    Opening::variation("E62", "King's Indian Defense", "Fianchetto Variation", "d4 Nf6 c4 g6 Nc3 Bg7 Nf3 d6 g3"),
+    Opening::variation("E63", "King's Indian Defense", "Fianchetto Variation, Panno Variation", "d4 Nf6 c4 g6 Nc3 Bg7 Nf3 d6 g3 O-O Bg2 Nc6 O-O a6"),
+    Opening::variation("E64", "King's Indian Defense", "Fianchetto Variation, Yugoslav System", "d4 Nf6 c4 g6 Nc3 Bg7 Nf3 d6 g3 O-O Bg2 c5"),
+    Opening::variation("E65", "King's Indian Defense", "Fianchetto Variation, Yugoslav Variation, 7.O-O", "d4 Nf6 c4 g6 Nc3 Bg7 Nf3 d6 g3 O-O Bg2 c5 O-O"),
+    Opening::variation("E66", "King's Indian Defense", "Fianchetto Variation, Yugoslav Variation, Advance Line", "d4 Nf6 c4 g6 Nc3 Bg7 Nf3 d6 g3 O-O Bg2 c5 O-O Nc6 d5"),
+    Opening::variation("E67", "King's Indian Defense", "Fianchetto Variation, Classical Fianchetto", "d4 Nf6 c4 g6 Nc3 Bg7 Nf3 d6 g3 O-O Bg2 Nbd7"),
+    Opening::variation("E68", "King's Indian Defense", "Fianchetto Variation, Classical Variation, 8.e4", "d4 Nf6 c4 g6 Nc3 Bg7 Nf3 d6 g3 O-O Bg2 Nbd7 O-O e5 e4"),
+    Opening::variation("E69", "King's Indian Defense", "Fianchetto Variation, Classical Main Line", "d4 Nf6 c4 g6 Nc3 Bg7 Nf3 d6 g3 O-O Bg2 Nbd7 O-O e5 e4 c6 h3"),
+    Opening::variation("E70", "King's Indian Defense", "Normal Variation", "d4 Nf6 c4 g6 Nc3 Bg7 e4"),
+    Opening::variation("E71", "King's Indian Defense", "Makogonov Variation", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 h3"),
+    Opening::variation("E72", "King's Indian Defense", "Normal Variation, Deferred Fianchetto", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 g3"),
+    Opening::variation("E73", "King's Indian Defense", "Averbakh Variation", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Be2 O-O Bg5"),
+    Opening::variation("E74", "King's Indian Defense", "Averbakh Variation, 6...c5", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Be2 O-O Bg5 c5"),
+    Opening::variation("E75", "King's Indian Defense", "Averbakh Variation, Main Line", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Be2 O-O Bg5 c5 d5 e6"),
+    Opening::variation("E76", "King's Indian Defense", "Four Pawns Attack", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 f4"),
+    Opening::variation("E77", "King's Indian Defense", "Four Pawns Attack, 6.Be2", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 f4 O-O Be2"),
+    Opening::variation("E78", "King's Indian Defense", "Four Pawns Attack, with Be2 and Nf3", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 f4 O-O Be2 c5 Nf3"),
+    Opening::variation("E79", "King's Indian Defense", "Four Pawns Attack, Main Line", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 f4 O-O Be2 c5 Nf3 cxd4 Nxd4 Nc6 Be3"),
+    Opening::variation("E80", "King's Indian Defense", "Samisch Variation", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 f3"),
+    Opening::variation("E81", "King's Indian Defense", "Samisch Variation, Normal Defense", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 f3 O-O"),
+    Opening::variation("E82", "King's Indian Defense", "Samisch Variation, Double Fianchetto", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 f3 O-O Be3 b6"),
+    Opening::variation("E83", "King's Indian Defense", "Samisch Variation, Panno Formation", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 f3 O-O Be3 Nc6"),
+    Opening::variation("E84", "King's Indian Defense", "Samisch Variation, Panno Main Line", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 f3 O-O Be3 Nc6 Nge2 a6 Qd2 Rb8"),
+    Opening::variation("E85", "King's Indian Defense", "Samisch Variation, Orthodox Variation", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 f3 O-O Be3 e5"),
+    Opening::variation("E86", "King's Indian Defense", "Samisch Variation, Orthodox Variation, 7.Nge2 c6", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 f3 O-O Be3 e5 Nge2 c6"),
+    Opening::variation("E87", "King's Indian Defense", "Samisch Variation, Closed Variation", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 f3 O-O Be3 e5 d5"),
+    Opening::variation("E88", "King's Indian Defense", "Samisch Variation, Closed Variation, 7...c6", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 f3 O-O Be3 e5 d5 c6"),
+    Opening::variation("E89", "King's Indian Defense", "Samisch Variation, Closed Variation, Main Line", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 f3 O-O Be3 e5 d5 c6 Nge2 cxd5"),
+    Opening::variation("E90", "King's Indian Defense", "Normal Variation, King's Knight Variation", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Nf3"),
+    Opening::variation("E91", "King's Indian Defense", "Kazakh Variation Line", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Nf3 O-O Be2"),
+    Opening::variation("E92", "King's Indian Defense", "Orthodox Variation", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Nf3 O-O Be2 e5"),
+    Opening::variation("E92", "King's Indian Defense", "Exchange Variation", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Nf3 O-O Be2 e5 dxe5 dxe5 Qxd8 Rxd8"),
+    Opening::variation("E93", "King's Indian Defense", "Petrosian Variation, Keres Defense Line", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Nf3 O-O Be2 e5 d5 Nbd7"),
+    Opening::variation("E94", "King's Indian Defense", "Orthodox Variation, 7.O-O", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Nf3 O-O Be2 e5 O-O"),
+    Opening::variation("E95", "King's Indian Defense", "Orthodox Variation, 7...Nbd7 8.Re1", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Nf3 O-O Be2 e5 O-O Nbd7 Re1"),
+    Opening::variation("E96", "King's Indian Defense", "Orthodox Variation, 7...Nbd7, Main Line", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Nf3 O-O Be2 e5 O-O Nbd7 Re1 c6 Bf1 a5"),
+    Opening::variation("E97", "King's Indian Defense", "Orthodox Variation, Aronin-Taimanov Defense", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Nf3 O-O Be2 e5 O-O Nc6"),
+    Opening::variation("E97", "King's Indian Defense", "Orthodox Variation, Bayonet Attack", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Nf3 O-O Be2 e5 O-O Nc6 d5 Ne7 b4"),
+    Opening::variation("E98", "King's Indian Defense", "Orthodox Variation, Classical System, 9.Ne1", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Nf3 O-O Be2 e5 O-O Nc6 d5 Ne7 Ne1"),
+    Opening::variation("E99", "King's Indian Defense", "Orthodox Variation, Classical System, Main Line", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Nf3 O-O Be2 e5 O-O Nc6 d5 Ne7 Ne1 Nd7 f3 f5"),
+];
+
+// deepest opening whose position the game went through, so transpositions count too
+pub fn classify(game: &Game) -> Option<&'static Opening> {
+    let keys = game.position_keys();
+    OPENINGS
+        .iter()
+        .zip(opening_keys())
+        .filter_map(|(opening, key)| Some((opening, (*key)?)))
+        .filter(|(_, (key, _))| keys.contains(key))
+        .max_by_key(|(_, (_, plies))| *plies)
+        .map(|(opening, _)| opening)
+}
+
+// keys and lengths of `OPENINGS` in order, replayed once on first use
+fn opening_keys() -> &'static [Option<(u64, usize)>] {
+    static KEYS: OnceLock<Vec<Option<(u64, usize)>>> = OnceLock::new();
+    KEYS.get_or_init(|| OPENINGS.iter().map(Opening::key).collect())
+}
+
+#[test]
+fn eco_test() {
+    for (opening, key) in OPENINGS.iter().zip(opening_keys()) {
+        assert!(key.is_some(), "{} is illegal", opening.moves);
+    }
+    // every code has a line
+    for volume in 'A'..='E' {
+        for number in 0..100 {
+            let code = format!("{volume}{number:02}");
+            assert!(
+                OPENINGS.iter().any(|opening| opening.code == code),
+                "{code}"
+            );
+        }
+    }
+
+    let play = |moves: &'static str| {
+        let mut game = Game::new();
+        game.start();
+        for mv in Opening::new("", "", moves).reference_moves() {
+            game.make_move(mv).unwrap();
+        }
+        game
+    };
+    assert_eq!(classify(&play("")), None);
+    assert_eq!(classify(&play("h3")).unwrap().code, "A00");
+    assert_eq!(classify(&play("e4 e5 Ke2")).unwrap().code, "C20");
+
+    let game = play("e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6 Be3 e5");
+    let opening = classify(&game).unwrap();
+    assert_eq!(opening.code, "B90");
+    assert_eq!(
+        opening.pgn_tags(),
+        "[ECO \"B90\"]\n[Opening \"Sicilian Defense\"]\n[Variation \"Najdorf Variation, English Attack\"]\n"
+    );
+    let game = play("e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6 Bg5 e6 f4 Be7 Qf3 Qc7 O-O-O Nbd7 g4");
+    assert_eq!(classify(&game).unwrap().code, "B99");
+
+    // English move order reaching the Nimzo-Indian
+    let game = play("c4 e6 d4 Nf6 Nc3 Bb4");
+    assert_eq!(classify(&game).unwrap().code, "E20");
+    assert_eq!(
+        classify(&play("d4 d5 c4")).unwrap().pgn_tags(),
+        "[ECO \"D06\"]\n[Opening \"Queen's Gambit\"]\n"
+    );
+    // the double pushes leave different en passant squares, which aren't capturable
+    assert_eq!(classify(&play("c4 d5 d4")).unwrap().code, "D06");
+    // a line that isn't legal has no key
+    assert_eq!(Opening::new("", "", "e4 e5 Ke3").key(), None);
+    assert_eq!(Opening::new("", "", "e4 e5 Ke3").reference_moves().len(), 2);
+}
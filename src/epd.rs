@@ -0,0 +1,274 @@
+use crate::{errors::GameError, game::Game, moves::Move};
+
+// a position of a test suite, ie. `r1b1k2r/... w kq - bm Nxe5; id "WAC.005";`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Epd {
+    // placement, side to move, castling and en passant, as FEN has them
+    pub fen: String,
+    // opcodes in order with their operands, quotes removed
+    pub operations: Vec<(String, Vec<String>)>,
+}
+
+impl Epd {
+    pub fn parse(line: &str) -> Result<Epd, GameError> {
+        let invalid = || GameError::InvalidNotation(line.to_owned());
+        let mut rest = line.trim();
+        let mut fields = Vec::new();
+        for _ in 0..4 {
+            let (field, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            if field.is_empty() {
+                return Err(invalid());
+            }
+            fields.push(field);
+            rest = tail.trim_start();
+        }
+
+        let mut operations = Vec::new();
+        let mut operands = Vec::new();
+        let mut token = String::new();
+        let mut quoted = false;
+        for c in rest.chars() {
+            match c {
+                '"' => {
+                    if quoted {
+                        operands.push(std::mem::take(&mut token));
+                    }
+                    quoted = !quoted;
+                }
+                _ if quoted => token.push(c),
+                ';' | ' ' | '\t' => {
+                    if !token.is_empty() {
+                        operands.push(std::mem::take(&mut token));
+                    }
+                    if c == ';' {
+                        let mut operands = std::mem::take(&mut operands).into_iter();
+                        let opcode = operands.next().ok_or_else(invalid)?;
+                        operations.push((opcode, operands.collect()));
+                    }
+                }
+                _ => token.push(c),
+            }
+        }
+        if quoted || !token.is_empty() || !operands.is_empty() {
+            return Err(invalid());
+        }
+        let epd = Epd {
+            fen: fields.join(" "),
+            operations,
+        };
+        epd.best_moves()?;
+        epd.avoid_moves()?;
+        Ok(epd)
+    }
+
+    pub fn operation(&self, opcode: &str) -> Option<&[String]> {
+        self.operations
+            .iter()
+            .find(|(code, _)| code == opcode)
+            .map(|(_, operands)| operands.as_slice())
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.operation("id")?.first().map(String::as_str)
+    }
+
+    // `c0` to `c9`
+    pub fn comment(&self, index: u8) -> Option<&str> {
+        self.operation(&format!("c{index}"))?
+            .first()
+            .map(String::as_str)
+    }
+
+    // the position, with `hmvc` and `fmvn` as clocks when given
+    pub fn game(&self) -> Result<Game, GameError> {
+        let clock = |opcode| {
+            self.operation(opcode)
+                .and_then(|operands| operands.first())
+                .map_or("", String::as_str)
+        };
+        let halfmove = match clock("hmvc") {
+            "" => "0",
+            clock => clock,
+        };
+        let fullmove = match clock("fmvn") {
+            "" => "1",
+            number => number,
+        };
+        Game::from_fen(&format!("{} {halfmove} {fullmove}", self.fen))
+    }
+
+    pub fn best_moves(&self) -> Result<Vec<Move>, GameError> {
+        self.moves("bm")
+    }
+
+    pub fn avoid_moves(&self) -> Result<Vec<Move>, GameError> {
+        self.moves("am")
+    }
+
+    fn moves(&self, opcode: &str) -> Result<Vec<Move>, GameError> {
+        let game = self.game()?;
+        self.operation(opcode)
+            .unwrap_or_default()
+            .iter()
+            .map(|san| game.parse_san(san))
+            .collect()
+    }
+
+    // a best move when `bm` is given, otherwise anything but the moves to avoid
+    pub fn is_solved(&self, mv: Move) -> bool {
+        let best = self.best_moves().unwrap_or_default();
+        let avoid = self.avoid_moves().unwrap_or_default();
+        (best.is_empty() || best.contains(&mv)) && !avoid.contains(&mv)
+    }
+
+    // STS scoring, `c0 "Nf3=10, e4=5"` lists the points per move, otherwise solving it
+    // is worth 10
+    pub fn points(&self, mv: Move) -> u32 {
+        let Ok(game) = self.game() else {
+            return 0;
+        };
+        let Some(scores) = self.comment(0).filter(|c| c.contains('=')) else {
+            return if self.is_solved(mv) { 10 } else { 0 };
+        };
+        scores
+            .split(',')
+            .filter_map(|score| {
+                let (san, points) = score.trim().split_once('=')?;
+                let points = points.trim().parse().ok()?;
+                (game.parse_san(san.trim()).ok()? == mv).then_some(points)
+            })
+            .next()
+            .unwrap_or(0)
+    }
+
+    // most points a single move can get
+    pub fn max_points(&self) -> u32 {
+        let Some(scores) = self.comment(0).filter(|c| c.contains('=')) else {
+            return 10;
+        };
+        scores
+            .split(',')
+            .filter_map(|score| score.split_once('=')?.1.trim().parse().ok())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+// positions of an EPD file, blank lines and `#` comments are skipped
+pub fn parse_suite(text: &str) -> Result<Vec<Epd>, GameError> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(Epd::parse)
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpdResult {
+    pub id: Option<String>,
+    pub played: Option<Move>,
+    pub solved: bool,
+    pub points: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SuiteReport {
+    pub results: Vec<EpdResult>,
+    pub solved: usize,
+    pub points: u32,
+    pub max_points: u32,
+}
+
+// asks `choose` for a move in every position, ie. an engine searching with a time or
+// depth limit, and scores the answers
+pub fn run_suite(suite: &[Epd], mut choose: impl FnMut(&Game) -> Option<Move>) -> SuiteReport {
+    let mut report = SuiteReport::default();
+    for epd in suite {
+        let played = epd.game().ok().and_then(|game| choose(&game));
+        let solved = played.is_some_and(|mv| epd.is_solved(mv));
+        let points = played.map_or(0, |mv| epd.points(mv));
+        report.solved += solved as usize;
+        report.points += points;
+        report.max_points += epd.max_points();
+        report.results.push(EpdResult {
+            id: epd.id().map(str::to_owned),
+            played,
+            solved,
+            points,
+        });
+    }
+    report
+}
+
+#[test]
+fn epd_test() {
+    use crate::{
+        pieces::{Character, Side},
+        position::Pos,
+    };
+
+    let suite = parse_suite(
+        "# mate in one and a knight fork\n\
+         7k/8/6K1/8/8/8/Q7/8 w - - bm Qa8#; id \"mate.1\";\n\
+         \n\
+         4k3/8/8/8/8/8/3r4/4KN2 w - - bm Nxd2 Nxe4; am Ng3; id \"fork.2\"; c0 \"Nxd2=10, Kxd2=3\"; hmvc 4; fmvn 30;\n",
+    );
+    assert!(suite.is_err()); // Nxe4 isn't a legal move there
+    let suite = parse_suite(
+        "7k/8/6K1/8/8/8/Q7/8 w - - bm Qa8#; id \"mate.1\";\n\
+         4k3/8/8/8/8/8/3r4/4KN2 w - - bm Nxd2; am Ng3; id \"fork.2\"; c0 \"Nxd2=10, Kxd2=3\"; hmvc 4; fmvn 30;\n",
+    )
+    .unwrap();
+    let mate = &suite[0];
+    assert_eq!(mate.id(), Some("mate.1"));
+    let qa8 = Move::Normal {
+        from: Pos('a', 2),
+        to: Pos('a', 8),
+        promotion: None,
+    };
+    assert_eq!(mate.best_moves(), Ok(vec![qa8]));
+    assert!(mate.is_solved(qa8));
+
+    let fork = &suite[1];
+    assert_eq!(fork.comment(0), Some("Nxd2=10, Kxd2=3"));
+    assert_eq!(
+        fork.game().unwrap().to_fen(),
+        "4k3/8/8/8/8/8/3r4/4KN2 w - - 4 30"
+    );
+    let nxd2 = Move::Normal {
+        from: Pos('f', 1),
+        to: Pos('d', 2),
+        promotion: None,
+    };
+    let kxd2 = Move::Normal {
+        from: Pos('e', 1),
+        to: Pos('d', 2),
+        promotion: None,
+    };
+    assert!(fork.best_moves().unwrap().contains(&nxd2));
+    assert!(!fork.is_solved(kxd2));
+    assert_eq!(fork.points(kxd2), 3);
+    assert_eq!(fork.max_points(), 10);
+
+    // always plays the first best move, except in the fork
+    let report = run_suite(&suite, |game| {
+        if game.fullmove_number == 30 {
+            game.parse_san("Kxd2").ok()
+        } else {
+            game.parse_san("Qa8").ok()
+        }
+    });
+    assert_eq!(report.solved, 1);
+    assert_eq!((report.points, report.max_points), (13, 20));
+    assert_eq!(report.results[1].id.as_deref(), Some("fork.2"));
+
+    assert!(Epd::parse("7k/8/6K1/8/8/8/Q7/8 w - - bm Qa8").is_err());
+    assert!(Epd::parse("7k/8/6K1/8 w").is_err());
+    // underpromotions aren't taken for the queen
+    let knight = Epd::parse("8/4P3/8/8/8/k7/8/K7 w - - bm e8=N;").unwrap();
+    assert_eq!(
+        knight.best_moves().unwrap()[0].promotion(),
+        Some(Character::Knight(Side::White))
+    );
+    assert!(Epd::parse("8/4P3/8/8/8/k7/8/K7 w - - bm e8;").is_err());
+}
@@ -1,16 +1,214 @@
-#[derive(Debug, PartialEq, Eq)]
+use std::{convert::Infallible, error::Error, fmt::Display};
+
+use crate::{
+    pieces::{Character, Side},
+    position::Pos,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum GameError {
-    EmptyCell,
+    EmptyCell(Pos),
     SideNotChanged,
     SideAlreadyChanged,
-    OpponentPiece,
-    InvalidMove,
+    OpponentPiece {
+        character: Character,
+        pos: Pos,
+        expected: Side,
+    },
+    InvalidMove {
+        character: Character,
+        from: Pos,
+        to: Pos,
+    },
+    InvalidDrop {
+        character: Character,
+        to: Pos,
+    },
+    NotPromotable(Pos),
+    // a pawn reaching the last rank must say what it becomes
+    PromotionRequired(Pos),
+    // `character` isn't something this move can promote to
+    InvalidPromotion(Character),
+    InvalidNotation(String),
     GameOver,
-    InvalidPosition,
-    AlonePiece,
+    // the text which couldn't be read as a position
+    InvalidPosition(String),
+    // the piece handle doesn't match what's picked in the game
+    NotPicked(Pos),
+    // the part of the FEN which couldn't be read
+    InvalidFen(String),
+    NotInPocket(Character),
+    // editing the position is only possible in setup mode
+    NotInSetup,
+    InvalidSetup(Vec<SetupError>),
 }
 
+impl Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyCell(pos) => write!(f, "there's no piece on {pos}"),
+            Self::SideNotChanged => write!(f, "side to move hasn't changed yet"),
+            Self::SideAlreadyChanged => write!(f, "side to move has already changed"),
+            Self::OpponentPiece {
+                character,
+                pos,
+                expected,
+            } => write!(
+                f,
+                "{} on {pos} belongs to {}, it's {expected}'s turn",
+                character.name(),
+                character.side()
+            ),
+            Self::InvalidMove {
+                character,
+                from,
+                to,
+            } => {
+                write!(f, "{} on {from} cannot move to {to}", character.name())
+            }
+            Self::InvalidDrop { character, to } => {
+                write!(f, "{} cannot be dropped on {to}", character.name())
+            }
+            Self::NotPromotable(pos) => write!(f, "there's no pawn on {pos} about to promote"),
+            Self::PromotionRequired(pos) => {
+                write!(f, "a pawn reaching {pos} has to choose a promotion")
+            }
+            Self::InvalidPromotion(character) => {
+                write!(f, "{} isn't a valid promotion here", character.name())
+            }
+            Self::InvalidNotation(notation) => write!(f, "invalid move notation `{notation}`"),
+            Self::GameOver => write!(f, "the game is over"),
+            Self::InvalidPosition(pos) => write!(f, "`{pos}` isn't a square on the board"),
+            Self::NotPicked(pos) => write!(f, "the piece on {pos} isn't picked"),
+            Self::InvalidFen(fen) => write!(f, "invalid FEN `{fen}`"),
+            Self::NotInPocket(character) => write!(
+                f,
+                "there's no {} in {}'s pocket",
+                character.name(),
+                character.side()
+            ),
+            Self::NotInSetup => write!(f, "the position can only be edited in setup mode"),
+            Self::InvalidSetup(problems) => {
+                write!(f, "invalid setup")?;
+                for (index, problem) in problems.iter().enumerate() {
+                    let separator = if index == 0 { ": " } else { ", " };
+                    write!(f, "{separator}{problem}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for GameError {}
+
+// lets `impl TryInto<Square>` arguments take a `Square` directly
+impl From<Infallible> for GameError {
+    fn from(never: Infallible) -> Self {
+        match never {}
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PosErr {
     InvalidPosition,
     ParseError,
 }
+
+impl Display for PosErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPosition => write!(f, "square is off the board"),
+            Self::ParseError => write!(f, "squares are written as a file and a rank, ie. `e4`"),
+        }
+    }
+}
+
+impl Error for PosErr {}
+
+// problems found by `Game::validate` in a custom position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupError {
+    MissingKing(Side),
+    TooManyKings(Side),
+    PawnOnBackRank(Pos),
+    TooManyPawns(Side),
+    TooManyPieces(Side),
+    // more queens, rooks, bishops and knights than missing pawns could have promoted to
+    TooManyPromotedPieces(Side),
+    // the side which isn't to move is in check
+    OpponentInCheck,
+    // side to move is checked by more pieces than a single move could uncover
+    ImpossibleCheck,
+    InvalidCastlingRights,
+    InvalidEnPassant(Pos),
+}
+
+impl Display for SetupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingKing(side) => write!(f, "{side} has no king"),
+            Self::TooManyKings(side) => write!(f, "{side} has too many kings"),
+            Self::PawnOnBackRank(pos) => write!(f, "pawn on {pos} stands on a back rank"),
+            Self::TooManyPawns(side) => write!(f, "{side} has more than 8 pawns"),
+            Self::TooManyPieces(side) => write!(f, "{side} has more than 16 pieces"),
+            Self::TooManyPromotedPieces(side) => {
+                write!(f, "{side} has more promoted pieces than missing pawns")
+            }
+            Self::OpponentInCheck => write!(f, "the side not to move is in check"),
+            Self::ImpossibleCheck => write!(f, "the side to move is in an impossible check"),
+            Self::InvalidCastlingRights => {
+                write!(f, "castling rights don't match the kings and rooks")
+            }
+            Self::InvalidEnPassant(pos) => {
+                write!(
+                    f,
+                    "en passant square {pos} isn't behind a double pushed pawn"
+                )
+            }
+        }
+    }
+}
+
+impl Error for SetupError {}
+
+#[test]
+fn errors_test() {
+    let error = GameError::InvalidMove {
+        character: Character::Knight(Side::White),
+        from: Pos('b', 1),
+        to: Pos('b', 3),
+    };
+    assert_eq!(error.to_string(), "Knight on b1 cannot move to b3");
+
+    let error = GameError::OpponentPiece {
+        character: Character::Pawn(Side::Black),
+        pos: Pos('e', 7),
+        expected: Side::White,
+    };
+    assert_eq!(
+        error.to_string(),
+        "Pawn on e7 belongs to Black, it's White's turn"
+    );
+
+    let error = GameError::InvalidSetup(vec![
+        SetupError::MissingKing(Side::Black),
+        SetupError::OpponentInCheck,
+    ]);
+    assert_eq!(
+        error.to_string(),
+        "invalid setup: Black has no king, the side not to move is in check"
+    );
+
+    // composes with `?` in applications
+    fn play() -> Result<(), Box<dyn Error>> {
+        let mut game = crate::game::Game::new();
+        game.start();
+        game.pick("e2")?.place_at(&mut game, "e5")?;
+        Ok(())
+    }
+    assert_eq!(
+        play().unwrap_err().to_string(),
+        "Pawn on e2 cannot move to e5"
+    );
+}
@@ -1,29 +1,160 @@
 #![allow(dead_code)]
 
-use std::mem;
-
 use crate::{
     chess_board::ChessBoard,
-    errors::GameError,
-    pieces::{self, Character, Piece, Side},
-    position::Pos,
+    errors::{GameError, SetupError},
+    moves::Move,
+    pieces::{Character, Piece, Side},
+    position::{Pos, Square},
+    variants::{Crazyhouse, Standard, ThreeCheck, Variant},
+    zobrist,
 };
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum GameState {
     #[default]
     Idle,
-    PiecePicked,
+    // square of the picked character, which stays on the board until placed
+    PiecePicked(Pos),
     PiecePlaced,
-    Ended,
+    Ended(Outcome),
+    // position is being edited, see `Game::setup_mode`
+    Setup,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    // `None` for a draw
+    pub winner: Option<Side>,
+    pub reason: EndReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndReason {
+    Checkmate,
+    Stalemate,
+    KingExploded,
+    KingOfTheHill,
+    ThreeChecks,
+    PiecesLost,
+    // racing kings, a king reached the eighth rank
+    GoalReached,
+}
+
+// castling is a king moving two squares towards the rook, see `Game::castle`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CastlingRights {
+    pub white_king_side: bool,
+    pub white_queen_side: bool,
+    pub black_king_side: bool,
+    pub black_queen_side: bool,
+}
+
+impl CastlingRights {
+    pub const ALL: CastlingRights = CastlingRights {
+        white_king_side: true,
+        white_queen_side: true,
+        black_king_side: true,
+        black_queen_side: true,
+    };
+
+    // every right whose king and rook stand on their initial squares
+    pub fn from_board(board: &ChessBoard) -> CastlingRights {
+        let mut rights = CastlingRights::ALL;
+        rights.retain_consistent(board);
+        rights
+    }
+
+    pub fn retain_consistent(&mut self, board: &ChessBoard) {
+        let at = |pos, character| board.character_at(pos) == Some(character);
+        let white_king = at(Pos('e', 1), Character::King(Side::White));
+        let black_king = at(Pos('e', 8), Character::King(Side::Black));
+        self.white_king_side &= white_king && at(Pos('h', 1), Character::Rook(Side::White));
+        self.white_queen_side &= white_king && at(Pos('a', 1), Character::Rook(Side::White));
+        self.black_king_side &= black_king && at(Pos('h', 8), Character::Rook(Side::Black));
+        self.black_queen_side &= black_king && at(Pos('a', 8), Character::Rook(Side::Black));
+    }
+
+    // drops rights once a king or rook leaves or gets captured on `pos`
+    fn touch(&mut self, pos: Pos) {
+        match (pos.file(), pos.rank()) {
+            ('e', 1) => {
+                self.white_king_side = false;
+                self.white_queen_side = false;
+            }
+            ('e', 8) => {
+                self.black_king_side = false;
+                self.black_queen_side = false;
+            }
+            ('h', 1) => self.white_king_side = false,
+            ('a', 1) => self.white_queen_side = false,
+            ('h', 8) => self.black_king_side = false,
+            ('a', 8) => self.black_queen_side = false,
+            _ => {}
+        }
+    }
+
+    pub fn fen(&self) -> String {
+        let rights = [
+            (self.white_king_side, 'K'),
+            (self.white_queen_side, 'Q'),
+            (self.black_king_side, 'k'),
+            (self.black_queen_side, 'q'),
+        ];
+        let fen: String = rights
+            .iter()
+            .filter(|(right, _)| *right)
+            .map(|(_, letter)| letter)
+            .collect();
+        if fen.is_empty() {
+            "-".to_owned()
+        } else {
+            fen
+        }
+    }
+
+    pub fn from_fen(fen: &str) -> Result<CastlingRights, GameError> {
+        let mut rights = CastlingRights::default();
+        if fen == "-" {
+            return Ok(rights);
+        }
+        for letter in fen.chars() {
+            match letter {
+                'K' => rights.white_king_side = true,
+                'Q' => rights.white_queen_side = true,
+                'k' => rights.black_king_side = true,
+                'q' => rights.black_queen_side = true,
+                _ => return Err(GameError::InvalidFen(fen.to_owned())),
+            }
+        }
+        Ok(rights)
+    }
 }
 
+// cheap enough to copy for a search, the variant's counters are cloned along
+#[derive(Clone)]
 pub struct Game {
     pub board: ChessBoard,
     pub state: GameState,
     side: Side,
     pub captured_white: Vec<Character>,
     pub captured_black: Vec<Character>,
+    // crazyhouse only, captured pieces which can be dropped back by their capturer
+    pub pocket_white: Vec<Character>,
+    pub pocket_black: Vec<Character>,
+    variant: Box<dyn Variant>,
+    pub castling: CastlingRights,
+    // square behind a pawn which just made a double push
+    pub en_passant: Option<Pos>,
+    pub halfmove_clock: u32,
+    pub fullmove_number: u32,
+    // moves played, each with the Zobrist key of the position it was played from
+    history: Vec<(u64, Move)>,
+}
+
+// a square argument as a `Pos`, checked so that no `Pos` off the board gets through
+fn square(square: impl TryInto<Square, Error: Into<GameError>>) -> Result<Pos, GameError> {
+    square.try_into().map(Pos::from).map_err(Into::into)
 }
 
 impl Default for Game {
@@ -34,6 +165,14 @@ impl Default for Game {
             state: GameState::Idle,
             captured_white: Vec::new(),
             captured_black: Vec::new(),
+            pocket_white: Vec::new(),
+            pocket_black: Vec::new(),
+            variant: Box::new(Standard),
+            castling: CastlingRights::default(),
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            history: Vec::new(),
         }
     }
 }
@@ -43,8 +182,130 @@ impl Game {
         Game::default()
     }
 
+    pub fn new_crazyhouse() -> Game {
+        Game::with_variant(Crazyhouse)
+    }
+
+    pub fn with_variant(variant: impl Variant + 'static) -> Game {
+        Game {
+            variant: Box::new(variant),
+            ..Game::default()
+        }
+    }
+
+    pub fn variant(&self) -> &dyn Variant {
+        self.variant.as_ref()
+    }
+
+    // FEN with crazyhouse pocket in brackets, ie. `.../RNBQKBNR[Qp] w - - 0 1`
+    // and three-check remaining checks after en passant, ie. `w KQkq - 3+3 0 1`
+    pub fn to_fen(&self) -> String {
+        let mut fen = self.board.fen_placement();
+        if self.variant.drops() {
+            fen.push('[');
+            fen.extend(self.pocket_white.iter().map(|c| c.letter()));
+            fen.extend(self.pocket_black.iter().map(|c| c.letter()));
+            fen.push(']');
+        }
+        let side = match self.side {
+            Side::White => 'w',
+            Side::Black => 'b',
+        };
+        let extra = self
+            .variant
+            .fen_field()
+            .map_or(String::new(), |field| format!("{field} "));
+        let en_passant = self
+            .en_passant
+            .map_or("-".to_owned(), |pos| format!("{pos:?}"));
+        format!(
+            "{fen} {side} {} {en_passant} {extra}{} {}",
+            self.castling.fen(),
+            self.halfmove_clock,
+            self.fullmove_number
+        )
+    }
+
+    // picks crazyhouse or three-check when the FEN has a pocket or check counter
+    pub fn from_fen(fen: &str) -> Result<Game, GameError> {
+        let placement = fen.split_whitespace().next().unwrap_or_default();
+        if placement.contains('[') || placement.matches('/').count() == 8 {
+            Game::from_fen_with(fen, Crazyhouse)
+        } else if fen
+            .split_whitespace()
+            .skip(4)
+            .any(|field| field.contains('+'))
+        {
+            Game::from_fen_with(fen, ThreeCheck::default())
+        } else {
+            Game::from_fen_with(fen, Standard)
+        }
+    }
+
+    pub fn from_fen_with(fen: &str, variant: impl Variant + 'static) -> Result<Game, GameError> {
+        let mut fields = fen.split_whitespace().peekable();
+        let placement = fields
+            .next()
+            .ok_or_else(|| GameError::InvalidFen(fen.to_owned()))?;
+
+        let mut game = Game::with_variant(variant);
+        // pocket either in brackets or as a ninth rank
+        let (placement, pocket) = if let Some((placement, pocket)) = placement.split_once('[') {
+            let pocket = pocket
+                .strip_suffix(']')
+                .ok_or_else(|| GameError::InvalidFen(pocket.to_owned()))?;
+            (placement, Some(pocket))
+        } else if placement.matches('/').count() == 8 {
+            let (placement, pocket) = placement.rsplit_once('/').unwrap();
+            (placement, Some(pocket))
+        } else {
+            (placement, None)
+        };
+        game.board = ChessBoard::from_fen_placement(placement)?;
+        if let Some(pocket) = pocket {
+            if !game.variant.drops() {
+                return Err(GameError::InvalidFen(pocket.to_owned()));
+            }
+            for letter in pocket.chars() {
+                let character = Character::from_letter(letter)
+                    .ok_or_else(|| GameError::InvalidFen(pocket.to_owned()))?;
+                game.pocket_mut(character.side()).push(character);
+            }
+        }
+
+        game.side = match fields.next() {
+            Some("w") | None => Side::White,
+            Some("b") => Side::Black,
+            Some(side) => return Err(GameError::InvalidFen(side.to_owned())),
+        };
+        if let Some(castling) = fields.next() {
+            game.castling = CastlingRights::from_fen(castling)?;
+        }
+        game.en_passant = match fields.next() {
+            Some("-") | None => None,
+            Some(pos) => {
+                Some(Pos::try_from(pos).map_err(|_| GameError::InvalidFen(pos.to_owned()))?)
+            }
+        };
+        if let Some(field) = fields.next_if(|field| field.contains('+')) {
+            game.variant.set_fen_field(field)?;
+        }
+        if let Some(clock) = fields.next() {
+            game.halfmove_clock = clock
+                .parse()
+                .map_err(|_| GameError::InvalidFen(clock.to_owned()))?;
+        }
+        if let Some(number) = fields.next() {
+            game.fullmove_number = number
+                .parse()
+                .map_err(|_| GameError::InvalidFen(number.to_owned()))?;
+        }
+        Ok(game)
+    }
+
     fn place_pieces(&mut self) {
-        self.board.place_character_init();
+        self.board = self.variant.setup();
+        self.castling = CastlingRights::from_board(&self.board);
     }
 
     pub fn start(&mut self) {
@@ -67,40 +328,803 @@ impl Game {
         }
     }
 
-    pub fn show_board(&self) {
-        self.board.show();
+    pub fn pocket(&self, side: Side) -> &Vec<Character> {
+        match side {
+            Side::White => self.pocket_white.as_ref(),
+            Side::Black => self.pocket_black.as_ref(),
+        }
+    }
+
+    fn pocket_mut(&mut self, side: Side) -> &mut Vec<Character> {
+        match side {
+            Side::White => self.pocket_white.as_mut(),
+            Side::Black => self.pocket_black.as_mut(),
+        }
     }
 
-    pub fn pick(&mut self, pos: impl TryInto<Pos>) -> Result<Piece, GameError> {
-        // select a character / return an error
+    // called by side to move when `character` got captured,
+    // in crazyhouse it also switches colour into the capturer's pocket
+    fn record_capture(&mut self, character: Character, promoted: bool) {
+        let side = self.side;
+        match side {
+            Side::White => self.captured_white.push(character),
+            Side::Black => self.captured_black.push(character),
+        }
+        if self.variant.drops() {
+            let pocketed = if promoted {
+                Character::Pawn(side)
+            } else {
+                character.with_side(side)
+            };
+            self.pocket_mut(side).push(pocketed);
+        }
+    }
 
-        match self.state {
-            GameState::Idle => match pos.try_into() {
-                Ok(pos) => match self.board.pick_character(pos) {
-                    Ok(character) => {
-                        let piece = Piece::new(character, pos, Some(mem::take(&mut self.board)));
-                        if character.side() == self.side {
-                            Ok(piece)
-                        } else {
-                            piece.place_back(self);
-                            Err(GameError::OpponentPiece)
-                        }
+    pub fn drop_piece(
+        &mut self,
+        character: Character,
+        pos: impl TryInto<Square, Error: Into<GameError>>,
+    ) -> Result<(), GameError> {
+        let pos = square(pos)?;
+        self.make_move(Move::Drop { character, to: pos })
+            .map(|_| ())
+    }
+
+    // whether side to move is in check, by the rules of the variant
+    pub fn is_in_check(&self) -> bool {
+        self.variant.is_in_check(&self.board, self.side)
+    }
+
+    // the board once `mv` and its variant side effects are applied, `mv` isn't validated
+    fn board_after(&self, mv: Move) -> ChessBoard {
+        let mut board = self.board.clone();
+        match mv {
+            Move::Normal {
+                from,
+                to,
+                promotion,
+            } => {
+                let _ = Game::move_on_board(&mut board, self.variant.as_ref(), from, to, promotion);
+            }
+            Move::Drop { character, to } => {
+                board.place_character(character, to);
+                board.set_promoted(to, false);
+            }
+        }
+        board
+    }
+
+    // moves the character on `from` to `to` on `board`, a castling king brings its rook
+    // along, a pawn taking en passant removes the pawn behind and promotions and capture
+    // side effects apply, returns the captured character and whether it was promoted
+    fn move_on_board(
+        board: &mut ChessBoard,
+        variant: &dyn Variant,
+        from: Pos,
+        to: Pos,
+        promotion: Option<Character>,
+    ) -> Result<Option<(Character, bool)>, GameError> {
+        let character = board.pick_character(from)?;
+        let d_file = to.file() as i32 - from.file() as i32;
+        let mut captured_at = to;
+        match character {
+            Character::King(_) if d_file.abs() == 2 => {
+                let (rook_from, rook_to) = if d_file > 0 { ('h', 'f') } else { ('a', 'd') };
+                let (rook_from, rook_to) = (Pos(rook_from, from.rank()), Pos(rook_to, from.rank()));
+                if let Ok(rook) = board.pick_character(rook_from) {
+                    board.place_character(rook, rook_to);
+                    board.move_promoted(rook_from, rook_to);
+                }
+            }
+            Character::Pawn(_) if d_file != 0 && board.character_at(to).is_none() => {
+                captured_at = Pos(to.file(), from.rank());
+            }
+            _ => {}
+        }
+        let promoted = board.is_promoted(captured_at);
+        let captured = board.pick_character(captured_at).ok();
+        board.set_promoted(captured_at, false);
+        board.place_character(character, to);
+        board.move_promoted(from, to);
+        if let Some(promotion) = promotion {
+            board.place_character(promotion, to);
+            board.set_promoted(to, true);
+        }
+        if captured.is_some() {
+            variant.after_capture(board, to);
+        }
+        Ok(captured.map(|captured| (captured, promoted)))
+    }
+
+    // destinations of the character on `from` ignoring checks, castling and en passant
+    // included for side to move
+    pub(crate) fn pseudo_moves(&self, from: Pos) -> Vec<Pos> {
+        let mut moves = self.board.moves_from(from);
+        match self.board.character_at(from) {
+            Some(Character::King(side)) if side == self.side => {
+                moves.extend(self.castling_targets(from));
+            }
+            Some(Character::Pawn(side)) if side == self.side => {
+                let (pushed_rank, direction) = match side {
+                    Side::White => (5, 1),
+                    Side::Black => (4, -1),
+                };
+                if let Some(to) = self.en_passant {
+                    let beside = (to.file() as i32 - from.file() as i32).abs() == 1;
+                    let behind = from.d_pos(to.file() as i32 - from.file() as i32, direction);
+                    let pawn = Pos(to.file(), from.rank());
+                    if from.rank() == pushed_rank
+                        && beside
+                        && behind == Some(to)
+                        && self.board.character_at(to).is_none()
+                        && self.board.character_at(pawn) == Some(Character::Pawn(side.opponent()))
+                    {
+                        moves.push(to);
+                    }
+                }
+            }
+            _ => {}
+        }
+        moves
+    }
+
+    // squares a king on `from` castles to, it may not leave, cross or land on an attacked
+    // square and the squares up to the rook must be empty
+    fn castling_targets(&self, from: Pos) -> Vec<Pos> {
+        let side = self.side;
+        let rank = match side {
+            Side::White => 1,
+            Side::Black => 8,
+        };
+        if from != Pos('e', rank) || !self.variant.castling() || self.is_in_check() {
+            return Vec::new();
+        }
+        let (king_side, queen_side) = match side {
+            Side::White => (
+                self.castling.white_king_side,
+                self.castling.white_queen_side,
+            ),
+            Side::Black => (
+                self.castling.black_king_side,
+                self.castling.black_queen_side,
+            ),
+        };
+        let wings = [
+            (king_side, 'h', "fg", 'f', 'g'),
+            (queen_side, 'a', "bcd", 'd', 'c'),
+        ];
+        let mut targets = Vec::new();
+        for (right, rook, between, crossed, to) in wings {
+            let empty = between
+                .chars()
+                .all(|file| self.board.character_at(Pos(file, rank)).is_none());
+            if !right
+                || !empty
+                || self.board.character_at(Pos(rook, rank)) != Some(Character::Rook(side))
+            {
+                continue;
+            }
+            // the landing square is checked with the rest of the move
+            let mut board = self.board.clone();
+            let _ = board.pick_character(from);
+            board.place_character(Character::King(side), Pos(crossed, rank));
+            if !self.variant.is_in_check(&board, side) {
+                targets.push(Pos(to, rank));
+            }
+        }
+        targets
+    }
+
+    // whether `mv` takes a piece, en passant included
+    pub fn is_capture(&self, mv: Move) -> bool {
+        let Move::Normal { from, to, .. } = mv else {
+            return false;
+        };
+        match self.board.character_at(to) {
+            Some(character) => self
+                .board
+                .character_at(from)
+                .is_some_and(|moved| moved.side() != character.side()),
+            None => {
+                matches!(self.board.character_at(from), Some(Character::Pawn(_)))
+                    && from.file() != to.file()
+            }
+        }
+    }
+
+    fn validate_move(&self, mv: Move) -> Result<(), GameError> {
+        let error = match mv {
+            Move::Normal {
+                from,
+                to,
+                promotion,
+            } => {
+                let character = self
+                    .board
+                    .character_at(from)
+                    .ok_or(GameError::EmptyCell(from))?;
+                if character.side() != self.side {
+                    return Err(GameError::OpponentPiece {
+                        character,
+                        pos: from,
+                        expected: self.side,
+                    });
+                }
+                let error = GameError::InvalidMove {
+                    character,
+                    from,
+                    to,
+                };
+                if !self.pseudo_moves(from).contains(&to) {
+                    return Err(error);
+                }
+                match (self.is_promotion(from, to), promotion) {
+                    (true, None) => return Err(GameError::PromotionRequired(to)),
+                    (true, Some(piece)) if !self.variant.promotions(self.side).contains(&piece) => {
+                        return Err(GameError::InvalidPromotion(piece))
                     }
-                    Err(_) => Err(GameError::EmptyCell),
-                },
-                Err(_) => Err(GameError::InvalidPosition),
+                    (false, Some(piece)) => return Err(GameError::InvalidPromotion(piece)),
+                    _ => {}
+                }
+                error
+            }
+            Move::Drop { character, to } => {
+                if character.side() != self.side {
+                    return Err(GameError::OpponentPiece {
+                        character,
+                        pos: to,
+                        expected: self.side,
+                    });
+                }
+                let error = GameError::InvalidDrop { character, to };
+                let pawn_on_back_rank =
+                    matches!(character, Character::Pawn(_)) && (to.rank() == 1 || to.rank() == 8);
+                if !self.variant.drops()
+                    || self.board.character_at(to).is_some()
+                    || pawn_on_back_rank
+                {
+                    return Err(error);
+                }
+                if !self.pocket(self.side).contains(&character) {
+                    return Err(GameError::NotInPocket(character));
+                }
+                error
+            }
+        };
+        if self.variant.is_legal(self, mv, &self.board_after(mv)) {
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+
+    // whether the character on `from` is a pawn which promotes by moving to `to`
+    pub fn is_promotion(&self, from: Pos, to: Pos) -> bool {
+        match self.board.character_at(from) {
+            Some(Character::Pawn(Side::White)) => to.rank() == 8,
+            Some(Character::Pawn(Side::Black)) => to.rank() == 1,
+            _ => false,
+        }
+    }
+
+    // `from` to `to` with the variant's default promotion when a pawn reaches the last rank
+    fn move_to(&self, from: Pos, to: Pos) -> Move {
+        let promotion = if self.is_promotion(from, to) {
+            self.variant.promotions(self.side).first().copied()
+        } else {
+            None
+        };
+        Move::Normal {
+            from,
+            to,
+            promotion,
+        }
+    }
+
+    // legal destinations for the character at `pos`, empty if it isn't side to move's
+    pub fn legal_moves(&self, pos: Pos) -> Vec<Pos> {
+        self.pseudo_moves(pos)
+            .into_iter()
+            .filter(|&to| self.validate_move(self.move_to(pos, to)).is_ok())
+            .collect()
+    }
+
+    pub fn has_legal_move(&self) -> bool {
+        let moves = self
+            .board
+            .positions(self.side)
+            .into_iter()
+            .any(|pos| !self.legal_moves(pos).is_empty());
+        if moves || !self.variant.drops() {
+            return moves;
+        }
+        let empty: Vec<Pos> = (0..64)
+            .map(Pos::from_matrix_index)
+            .filter(|&pos| self.board.character_at(pos).is_none())
+            .collect();
+        self.pocket(self.side).iter().any(|&character| {
+            empty
+                .iter()
+                .any(|&to| self.validate_move(Move::Drop { character, to }).is_ok())
+        })
+    }
+
+    // every legal move for side to move, one per promotion piece and drops included in
+    // crazyhouse
+    pub fn all_legal_moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+        for from in self.board.positions(self.side) {
+            for to in self.legal_moves(from) {
+                if self.is_promotion(from, to) {
+                    moves.extend(
+                        self.variant
+                            .promotions(self.side)
+                            .into_iter()
+                            .map(|promotion| Move::Normal {
+                                from,
+                                to,
+                                promotion: Some(promotion),
+                            }),
+                    );
+                } else {
+                    moves.push(Move::Normal {
+                        from,
+                        to,
+                        promotion: None,
+                    });
+                }
+            }
+        }
+        if self.variant.drops() {
+            let mut characters: Vec<Character> = Vec::new();
+            for &character in self.pocket(self.side) {
+                if !characters.contains(&character) {
+                    characters.push(character);
+                }
+            }
+            for character in characters {
+                for to in (0..64).map(Pos::from_matrix_index) {
+                    let mv = Move::Drop { character, to };
+                    if self.validate_move(mv).is_ok() {
+                        moves.push(mv);
+                    }
+                }
+            }
+        }
+        moves
+    }
+
+    // moves played since the game started or left setup mode
+    pub fn history(&self) -> Vec<Move> {
+        self.history.iter().map(|&(_, mv)| mv).collect()
+    }
+
+    // Zobrist keys of every position the game went through, the current one last
+    pub fn position_keys(&self) -> Vec<u64> {
+        let mut keys: Vec<u64> = self.history.iter().map(|&(key, _)| key).collect();
+        keys.push(self.zobrist_key());
+        keys
+    }
+
+    // Zobrist hash of the position, see `zobrist::key`
+    pub fn zobrist_key(&self) -> u64 {
+        zobrist::key(self)
+    }
+
+    // static exchange evaluation of `mv`, negative when it loses material
+    pub fn see(&self, mv: Move) -> i32 {
+        match mv {
+            Move::Normal { from, to, .. } => match self.board.character_at(from) {
+                Some(character) => self.board.see(character, Some(from), to),
+                None => 0,
             },
+            Move::Drop { character, to } => self.board.see(character, None, to),
+        }
+    }
+
+    // plays a move for side to move, returning the captured character if any
+    pub fn make_move(&mut self, mv: Move) -> Result<Option<Character>, GameError> {
+        if self.is_game_over() {
+            return Err(GameError::GameOver);
+        }
+        if !matches!(self.state, GameState::Idle | GameState::PiecePicked(_)) {
+            return Err(GameError::SideNotChanged);
+        }
+        self.validate_move(mv)?;
+        self.history.push((self.zobrist_key(), mv));
+
+        let side = self.side;
+        self.en_passant = None;
+        let (captured, resets_clock) = match mv {
+            Move::Normal {
+                from,
+                to,
+                promotion,
+            } => {
+                self.castling.touch(from);
+                self.castling.touch(to);
+                let character = self
+                    .board
+                    .character_at(from)
+                    .ok_or(GameError::EmptyCell(from))?;
+                let captured = Game::move_on_board(
+                    &mut self.board,
+                    self.variant.as_ref(),
+                    from,
+                    to,
+                    promotion,
+                )?;
+                if let Some((captured, promoted)) = captured {
+                    self.record_capture(captured, promoted);
+                }
+                let captured = captured.map(|(captured, _)| captured);
+                if matches!(character, Character::Pawn(_))
+                    && (from.rank() as i32 - to.rank() as i32).abs() == 2
+                {
+                    self.en_passant = Some(Pos(from.file(), (from.rank() + to.rank()) / 2));
+                }
+                (
+                    captured,
+                    captured.is_some() || matches!(character, Character::Pawn(_)),
+                )
+            }
+            Move::Drop { character, to } => {
+                let pocket = self.pocket_mut(side);
+                if let Some(index) = pocket.iter().position(|c| *c == character) {
+                    pocket.remove(index);
+                }
+                self.board.place_character(character, to);
+                self.board.set_promoted(to, false);
+                (None, matches!(character, Character::Pawn(_)))
+            }
+        };
+        if resets_clock {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+        self.variant.record_move(&self.board, side);
+
+        self.state = GameState::PiecePlaced;
+        self.change_side()?;
+        if let Some(outcome) = self.variant.outcome(self) {
+            self.state = GameState::Ended(outcome);
+        }
+        Ok(captured)
+    }
+
+    // drop in `P@e4` notation, piece letter case is ignored as side to move drops
+    pub fn play_drop(&mut self, notation: &str) -> Result<(), GameError> {
+        let invalid = || GameError::InvalidNotation(notation.to_owned());
+        let (letter, square) = notation.split_once('@').ok_or_else(invalid)?;
+        let mut letters = letter.chars();
+        let character = match (letters.next(), letters.next()) {
+            (Some(letter), None) => Character::from_letter(letter).ok_or_else(invalid)?,
+            _ => return Err(invalid()),
+        };
+        self.drop_piece(character.with_side(self.side), square)
+    }
+
+    // legal move for side to move written in SAN, ie. `Nbd7`, `exd5` or `e8=N+`
+    pub fn parse_san(&self, san: &str) -> Result<Move, GameError> {
+        let invalid = || GameError::InvalidNotation(san.to_owned());
+        let notation = san.trim_end_matches(['+', '#', '!', '?']);
+        let castling = match notation {
+            "O-O" | "0-0" => Some('g'),
+            "O-O-O" | "0-0-0" => Some('c'),
+            _ => None,
+        };
+        if let Some(file) = castling {
+            let from = self.board.king_position(self.side).ok_or_else(invalid)?;
+            let mv = Move::Normal {
+                from,
+                to: Pos(file, from.rank()),
+                promotion: None,
+            };
+            return match self.board.character_at(from) {
+                Some(_) if from.file() == 'e' && self.validate_move(mv).is_ok() => Ok(mv),
+                _ => Err(invalid()),
+            };
+        }
+        if let Some((letter, square)) = notation.split_once('@') {
+            let letter = letter.chars().next().ok_or_else(invalid)?;
+            let character = Character::from_letter(letter).ok_or_else(invalid)?;
+            let to = Pos::try_from(square).map_err(|_| invalid())?;
+            return Ok(Move::Drop {
+                character: character.with_side(self.side),
+                to,
+            });
+        }
+        let (notation, promotion) = match notation.split_once('=') {
+            Some((notation, letter)) => {
+                let mut letters = letter.chars();
+                let character = match (letters.next(), letters.next()) {
+                    (Some(letter), None) if letter.is_ascii_uppercase() => {
+                        Character::from_letter(letter).ok_or_else(invalid)?
+                    }
+                    _ => return Err(invalid()),
+                };
+                (notation, Some(character.with_side(self.side)))
+            }
+            None => (notation, None),
+        };
+        let (piece, rest) = match notation.chars().next() {
+            Some(letter @ ('K' | 'Q' | 'R' | 'B' | 'N')) => (letter, &notation[1..]),
+            Some(_) => ('P', notation),
+            None => return Err(invalid()),
+        };
+        let character = Character::from_letter(piece)
+            .ok_or_else(invalid)?
+            .with_side(self.side);
+        let split = rest.len().checked_sub(2).ok_or_else(invalid)?;
+        let to = rest
+            .get(split..)
+            .and_then(|square| Pos::try_from(square).ok())
+            .ok_or_else(invalid)?;
+        let hint: Vec<char> = rest[..split].chars().filter(|&c| c != 'x').collect();
+        let mut candidates = self.board.positions(self.side).into_iter().filter(|&from| {
+            self.board.character_at(from) == Some(character)
+                && hint.iter().all(|&c| {
+                    c == from.file() || Some(c) == char::from_digit(from.rank() as u32, 10)
+                })
+                && self.legal_moves(from).contains(&to)
+        });
+        let from = match (candidates.next(), candidates.next()) {
+            (Some(from), None) => from,
+            _ => return Err(invalid()),
+        };
+        let mv = Move::Normal {
+            from,
+            to,
+            promotion,
+        };
+        self.validate_move(mv).map_err(|_| invalid())?;
+        Ok(mv)
+    }
+
+    // `mv` in SAN as side to move would play it, the `+`/`#` suffix is left to the caller
+    // as it needs the move played
+    pub fn to_san(&self, mv: Move) -> String {
+        let (from, to, promotion) = match mv {
+            Move::Drop { character, to } => {
+                let letter = character.letter().to_ascii_uppercase();
+                return format!("{letter}@{to}");
+            }
+            Move::Normal {
+                from,
+                to,
+                promotion,
+            } => (from, to, promotion),
+        };
+        let Some(character) = self.board.character_at(from) else {
+            return format!("{from}{to}");
+        };
+        if let Character::King(_) = character {
+            match (from.file(), to.file()) {
+                ('e', 'g') => return "O-O".to_owned(),
+                ('e', 'c') => return "O-O-O".to_owned(),
+                _ => {}
+            }
+        }
+        let capture = if self.is_capture(mv) { "x" } else { "" };
+        if let Character::Pawn(_) = character {
+            let file = if capture.is_empty() {
+                String::new()
+            } else {
+                from.file().to_string()
+            };
+            let promotion = promotion.map_or(String::new(), |c| {
+                format!("={}", c.letter().to_ascii_uppercase())
+            });
+            return format!("{file}{capture}{to}{promotion}");
+        }
+        let rivals: Vec<Pos> = self
+            .board
+            .positions(self.side)
+            .into_iter()
+            .filter(|&other| {
+                other != from
+                    && self.board.character_at(other) == Some(character)
+                    && self.legal_moves(other).contains(&to)
+            })
+            .collect();
+        let hint = if rivals.is_empty() {
+            String::new()
+        } else if rivals.iter().all(|other| other.file() != from.file()) {
+            from.file().to_string()
+        } else if rivals.iter().all(|other| other.rank() != from.rank()) {
+            from.rank().to_string()
+        } else {
+            from.to_string()
+        };
+        let letter = character.letter().to_ascii_uppercase();
+        format!("{letter}{hint}{capture}{to}")
+    }
+
+    pub fn show_board(&self) {
+        self.board.show();
+    }
+
+    pub fn pick(
+        &mut self,
+        pos: impl TryInto<Square, Error: Into<GameError>>,
+    ) -> Result<Piece, GameError> {
+        // select a character / return an error, picking again replaces the selection
+        match self.state {
+            GameState::Idle | GameState::PiecePicked(_) => {
+                let pos = square(pos)?;
+                let character = self
+                    .board
+                    .character_at(pos)
+                    .ok_or(GameError::EmptyCell(pos))?;
+                if character.side() != self.side {
+                    return Err(GameError::OpponentPiece {
+                        character,
+                        pos,
+                        expected: self.side,
+                    });
+                }
+                self.state = GameState::PiecePicked(pos);
+                Ok(Piece::new(character, pos, Some(self.board.clone())))
+            }
+            GameState::Ended(_) => Err(GameError::GameOver),
             _ => Err(GameError::SideNotChanged),
         }
     }
 
+    // square of the picked character, if any
+    pub fn picked(&self) -> Option<Pos> {
+        match self.state {
+            GameState::PiecePicked(pos) => Some(pos),
+            _ => None,
+        }
+    }
+
+    // moves the picked character, placing it on its own square unpicks it, a pawn reaching
+    // the last rank becomes a queen, see `promote_pawn` for other pieces
+    pub fn place_picked(
+        &mut self,
+        pos: impl TryInto<Square, Error: Into<GameError>>,
+    ) -> Result<Option<Character>, GameError> {
+        let from = match self.state {
+            GameState::PiecePicked(from) => from,
+            GameState::Ended(_) => return Err(GameError::GameOver),
+            _ => return Err(GameError::SideNotChanged),
+        };
+        let to = square(pos)?;
+        if to == from {
+            self.cancel_pick();
+            return Ok(None);
+        }
+        self.make_move(self.move_to(from, to))
+    }
+
+    // drops the selection, ie. when a drag gets cancelled
+    pub fn cancel_pick(&mut self) {
+        if let GameState::PiecePicked(_) = self.state {
+            self.state = GameState::Idle;
+        }
+    }
+
+    // setup mode, pieces can be edited freely until `start_from_here`
+    pub fn setup_mode(&mut self) {
+        self.state = GameState::Setup;
+        self.history.clear();
+    }
+
+    fn editing(&mut self) -> Result<&mut Game, GameError> {
+        if self.state == GameState::Setup {
+            Ok(self)
+        } else {
+            Err(GameError::NotInSetup)
+        }
+    }
+
+    pub fn clear_board(&mut self) -> Result<(), GameError> {
+        let game = self.editing()?;
+        game.board.clear();
+        game.castling = CastlingRights::default();
+        game.en_passant = None;
+        Ok(())
+    }
+
+    pub fn put_piece(
+        &mut self,
+        character: Character,
+        pos: impl TryInto<Square, Error: Into<GameError>>,
+    ) -> Result<Option<Character>, GameError> {
+        let pos = square(pos)?;
+        let game = self.editing()?;
+        game.board.set_promoted(pos, false);
+        Ok(game.board.place_character(character, pos))
+    }
+
+    pub fn remove_piece(
+        &mut self,
+        pos: impl TryInto<Square, Error: Into<GameError>>,
+    ) -> Result<Character, GameError> {
+        let pos = square(pos)?;
+        let game = self.editing()?;
+        game.board.set_promoted(pos, false);
+        game.board.pick_character(pos)
+    }
+
+    // moves a piece regardless of rules, replacing whatever stood on `to`
+    pub fn relocate_piece(
+        &mut self,
+        from: impl TryInto<Square, Error: Into<GameError>>,
+        to: impl TryInto<Square, Error: Into<GameError>>,
+    ) -> Result<Option<Character>, GameError> {
+        let from = square(from)?;
+        let to = square(to)?;
+        let game = self.editing()?;
+        let character = game.board.pick_character(from)?;
+        let replaced = game.board.place_character(character, to);
+        game.board.move_promoted(from, to);
+        Ok(replaced)
+    }
+
+    pub fn set_turn(&mut self, side: Side) -> Result<(), GameError> {
+        self.editing()?.side = side;
+        Ok(())
+    }
+
+    pub fn set_castling(&mut self, castling: CastlingRights) -> Result<(), GameError> {
+        self.editing()?.castling = castling;
+        Ok(())
+    }
+
+    pub fn set_en_passant(&mut self, pos: Option<Pos>) -> Result<(), GameError> {
+        self.editing()?.en_passant = pos;
+        Ok(())
+    }
+
+    // board transformations drop castling rights and en passant which no longer fit
+    pub fn flip_board(&mut self) -> Result<(), GameError> {
+        let game = self.editing()?;
+        game.board.flip();
+        game.castling.retain_consistent(&game.board);
+        game.en_passant = None;
+        Ok(())
+    }
+
+    pub fn mirror_board(&mut self) -> Result<(), GameError> {
+        let game = self.editing()?;
+        game.board.mirror();
+        game.castling.retain_consistent(&game.board);
+        game.en_passant = None;
+        Ok(())
+    }
+
+    pub fn swap_colours(&mut self) -> Result<(), GameError> {
+        let game = self.editing()?;
+        game.board.swap_colours();
+        game.castling.retain_consistent(&game.board);
+        game.en_passant = None;
+        Ok(())
+    }
+
+    // leaves setup mode once the position passes `validate`
+    pub fn start_from_here(&mut self) -> Result<(), GameError> {
+        self.editing()?;
+        let problems = self.validate();
+        if !problems.is_empty() {
+            return Err(GameError::InvalidSetup(problems));
+        }
+        self.state = GameState::Idle;
+        if let Some(outcome) = self.variant.outcome(self) {
+            self.state = GameState::Ended(outcome);
+        }
+        Ok(())
+    }
+
     pub fn change_side(&mut self) -> Result<(), GameError> {
         match self.state {
             GameState::PiecePlaced => {
-                self.side = match self.side {
-                    Side::White => Side::Black,
-                    Side::Black => Side::White,
-                };
+                if self.side == Side::Black {
+                    self.fullmove_number += 1;
+                }
+                self.side = self.side.opponent();
                 self.state = GameState::Idle;
                 Ok(())
             }
@@ -108,39 +1132,52 @@ impl Game {
         }
     }
 
-    pub fn castle(&mut self) {
-        todo!("first learn rules of castling")
+    // castles side to move's king towards the h file, or the a file for the queen side
+    pub fn castle(&mut self, king_side: bool) -> Result<(), GameError> {
+        let rank = match self.side {
+            Side::White => 1,
+            Side::Black => 8,
+        };
+        let file = if king_side { 'g' } else { 'c' };
+        self.make_move(Move::Normal {
+            from: Pos('e', rank),
+            to: Pos(file, rank),
+            promotion: None,
+        })
+        .map(|_| ())
     }
 
-    pub fn promote_pawn(&mut self, pos: impl TryInto<Pos>) -> Result<(), GameError> {
-        if let Ok(pos) = pos.try_into() {
-            let mut piece = self.pick(pos)?;
-            match pos.rank() {
-                2 => {
-                    if piece.side == Side::White {
-                        piece.character = Character::Queen(piece.side);
-                        piece.place_at(self, Pos(pos.file(), 8)).map(|_a| ())
-                    } else {
-                        Err(GameError::InvalidMove)
-                    }
-                }
-                7 => {
-                    if piece.side == Side::Black {
-                        piece.character = Character::Queen(piece.side);
-                        piece.place_at(self, Pos(pos.file(), 8)).map(|_a| ())
-                    } else {
-                        Err(GameError::InvalidMove)
-                    }
-                }
-                _ => Err(GameError::InvalidMove),
-            }
-        } else {
-            Err(GameError::InvalidPosition)
+    // moves the pawn on `pos` to `target` on the last rank, straight ahead or capturing,
+    // where it becomes `piece`, which takes side to move's colour
+    pub fn promote_pawn(
+        &mut self,
+        pos: impl TryInto<Square, Error: Into<GameError>>,
+        target: impl TryInto<Square, Error: Into<GameError>>,
+        piece: Character,
+    ) -> Result<(), GameError> {
+        let pos = square(pos)?;
+        let target = square(target)?;
+        if !self.is_promotion(pos, target) {
+            return Err(GameError::NotPromotable(pos));
         }
+        self.make_move(Move::Normal {
+            from: pos,
+            to: target,
+            promotion: Some(piece.with_side(self.side)),
+        })
+        .map(|_| ())
     }
 
-    pub fn en_passant_capture(&mut self, piece: Piece) {
-        todo!("learn how to perform")
+    // takes the pawn which just double pushed past the picked pawn `piece`
+    pub fn en_passant_capture(&mut self, piece: Piece) -> Result<Option<Character>, GameError> {
+        match self.en_passant {
+            Some(to) => piece.place_at(self, to),
+            None => Err(GameError::InvalidMove {
+                character: piece.character,
+                from: piece.position,
+                to: piece.position,
+            }),
+        }
     }
 
     pub fn request_draw(&mut self) {
@@ -152,7 +1189,19 @@ impl Game {
     }
 
     pub fn is_game_over(&self) -> bool {
-        todo!("provide way to tell this")
+        matches!(self.state, GameState::Ended(_))
+    }
+
+    // problems which make the position unplayable, empty when it's fine to start from
+    pub fn validate(&self) -> Vec<SetupError> {
+        self.variant.validate(self)
+    }
+
+    pub fn outcome(&self) -> Option<Outcome> {
+        match self.state {
+            GameState::Ended(outcome) => Some(outcome),
+            _ => None,
+        }
     }
 }
 
@@ -168,8 +1217,316 @@ fn game_test() {
 
     let maybe_piece = game.pick("a1");
     if let Err(error) = maybe_piece {
-        assert_eq!(error, GameError::EmptyCell);
+        assert_eq!(error, GameError::EmptyCell(Pos('a', 1)));
     }
 }
 
+#[test]
+fn pick_test() {
+    let mut game = Game::new();
+    game.start();
+    let fen = game.to_fen();
+
+    // a dropped handle leaves the position untouched
+    let _ = game.pick("e2").unwrap();
+    assert_eq!(game.picked(), Some(Pos('e', 2)));
+    assert_eq!(game.to_fen(), fen);
+
+    // picking again replaces the selection, old handles go stale
+    let stale = game.pick("e2").unwrap();
+    let pawn = game.pick("d2").unwrap();
+    assert_eq!(
+        game.pick("d7").unwrap_err(),
+        GameError::OpponentPiece {
+            character: Character::Pawn(Side::Black),
+            pos: Pos('d', 7),
+            expected: Side::White
+        }
+    );
+    assert_eq!(
+        stale.place_at(&mut game, "e4"),
+        Err(GameError::NotPicked(Pos('e', 2)))
+    );
+    pawn.place_back(&mut game);
+    assert_eq!(game.picked(), None);
+    assert_eq!(game.place_picked("d4"), Err(GameError::SideNotChanged));
+
+    game.pick("d2").unwrap();
+    assert!(game.place_picked("d5").is_err());
+    assert_eq!(game.picked(), Some(Pos('d', 2)));
+    game.cancel_pick();
+    assert_eq!(game.state, GameState::Idle);
+    assert_eq!(game.to_fen(), fen);
+
+    game.pick("d2").unwrap();
+    game.place_picked("d4").unwrap();
+    assert_eq!(game.whose_turn(), Side::Black);
+    assert_eq!(game.picked(), None);
+    // squares off the board are errors, not panics
+    assert_eq!(
+        game.pick(Pos('z', 100)).unwrap_err(),
+        GameError::InvalidPosition("z100".into())
+    );
+    assert!(game.pick("").is_err());
+    assert!(game.legal_moves(Pos('e', 0)).is_empty());
+}
+
 // somehow moves most piece related stuff to piece module,
+
+#[test]
+fn crazyhouse_test() {
+    let mut game = Game::new_crazyhouse();
+    game.start();
+
+    game.pick("e2").unwrap().place_at(&mut game, "e4").unwrap();
+    game.pick("d7").unwrap().place_at(&mut game, "d5").unwrap();
+    game.pick("e4").unwrap().place_at(&mut game, "d5").unwrap();
+
+    // captured pawn switched colour into white's pocket
+    assert_eq!(game.captured_white, vec![Character::Pawn(Side::Black)]);
+    assert_eq!(
+        game.pocket(Side::White),
+        &vec![Character::Pawn(Side::White)]
+    );
+    assert_eq!(
+        game.to_fen(),
+        "rnbqkbnr/ppp1pppp/8/3P4/8/8/PPPP1PPP/RNBQKBNR[P] b KQkq - 0 2"
+    );
+
+    // black has nothing to drop
+    assert_eq!(
+        game.play_drop("P@e5"),
+        Err(GameError::NotInPocket(Character::Pawn(Side::Black)))
+    );
+    game.pick("g8").unwrap().place_at(&mut game, "f6").unwrap();
+
+    let pawn = Character::Pawn(Side::White);
+    assert_eq!(
+        game.play_drop("P@e8"),
+        Err(GameError::InvalidDrop {
+            character: pawn,
+            to: Pos('e', 8)
+        })
+    );
+    assert_eq!(
+        game.play_drop("P@"),
+        Err(GameError::InvalidPosition("".into()))
+    );
+    assert_eq!(
+        game.play_drop("Pe4"),
+        Err(GameError::InvalidNotation("Pe4".into()))
+    );
+    assert_eq!(
+        game.play_drop("P@e1"),
+        Err(GameError::InvalidDrop {
+            character: pawn,
+            to: Pos('e', 1)
+        })
+    );
+    assert_eq!(game.play_drop("P@e4"), Ok(()));
+    assert_eq!(
+        game.board.character_at(Pos('e', 4)),
+        Some(Character::Pawn(Side::White))
+    );
+    assert!(game.pocket(Side::White).is_empty());
+    assert_eq!(game.whose_turn(), Side::Black);
+
+    // standard games don't have drops
+    let mut game = Game::new();
+    game.start();
+    assert_eq!(
+        game.play_drop("P@e4"),
+        Err(GameError::InvalidDrop {
+            character: Character::Pawn(Side::White),
+            to: Pos('e', 4)
+        })
+    );
+}
+
+#[test]
+fn castling_test() {
+    let mut game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    assert!(game.legal_moves(Pos('e', 1)).contains(&Pos('g', 1)));
+    assert!(game.legal_moves(Pos('e', 1)).contains(&Pos('c', 1)));
+    let short = game.parse_san("O-O").unwrap();
+    assert_eq!(game.to_san(short), "O-O");
+    game.castle(true).unwrap();
+    assert_eq!(game.to_fen(), "r3k2r/8/8/8/8/8/8/R4RK1 b kq - 1 1");
+    let long = game.parse_san("O-O-O").unwrap();
+    game.make_move(long).unwrap();
+    assert_eq!(game.to_fen(), "2kr3r/8/8/8/8/8/8/R4RK1 w - - 2 2");
+
+    // not out of, through or into check, and not without the right
+    let game = Game::from_fen("r3k2r/8/8/8/8/8/5r2/R3K2R w KQkq - 0 1").unwrap();
+    assert!(!game.legal_moves(Pos('e', 1)).contains(&Pos('g', 1)));
+    assert!(game.legal_moves(Pos('e', 1)).contains(&Pos('c', 1)));
+    let game = Game::from_fen("r3k2r/8/8/8/8/8/6r1/R3K2R w KQkq - 0 1").unwrap();
+    assert!(!game.legal_moves(Pos('e', 1)).contains(&Pos('g', 1)));
+    let game = Game::from_fen("r3k2r/8/8/8/8/8/4r3/R3K2R w KQkq - 0 1").unwrap();
+    assert!(game.parse_san("O-O").is_err());
+    let mut game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w Qkq - 0 1").unwrap();
+    assert!(game.castle(true).is_err());
+    let game = Game::from_fen("r3k2r/8/8/8/8/8/8/RN2K2R w KQkq - 0 1").unwrap();
+    assert!(game.parse_san("O-O-O").is_err());
+}
+
+#[test]
+fn en_passant_test() {
+    let mut game = Game::from_fen("4k3/8/8/8/3p4/8/4P3/4K3 w - - 0 1").unwrap();
+    game.make_move(game.parse_san("e4").unwrap()).unwrap();
+    assert_eq!(game.en_passant, Some(Pos('e', 3)));
+    let capture = game.parse_san("dxe3").unwrap();
+    assert_eq!(game.to_san(capture), "dxe3");
+    assert_eq!(
+        game.make_move(capture),
+        Ok(Some(Character::Pawn(Side::White)))
+    );
+    assert_eq!(game.to_fen(), "4k3/8/8/8/8/4p3/8/4K3 w - - 0 2");
+
+    // only on the move right after the double push
+    let mut game = Game::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+    let pawn = game.pick("e5").unwrap();
+    game.en_passant_capture(pawn).unwrap();
+    assert_eq!(game.board.character_at(Pos('d', 5)), None);
+    let game = Game::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(game.legal_moves(Pos('e', 5)), vec![Pos('e', 6)]);
+    // nor when it exposes the king along the rank
+    let game = Game::from_fen("8/8/8/K2pP2r/8/8/8/7k w - d6 0 1").unwrap();
+    assert_eq!(game.legal_moves(Pos('e', 5)), vec![Pos('e', 6)]);
+}
+
+#[test]
+fn promotion_test() {
+    let mut game = Game::from_fen("n3k3/1P6/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let push = |promotion| Move::Normal {
+        from: Pos('b', 7),
+        to: Pos('b', 8),
+        promotion,
+    };
+    assert_eq!(
+        game.make_move(push(None)),
+        Err(GameError::PromotionRequired(Pos('b', 8)))
+    );
+    let king = Some(Character::King(Side::White));
+    assert_eq!(
+        game.make_move(push(king)),
+        Err(GameError::InvalidPromotion(Character::King(Side::White)))
+    );
+    // one move per piece, both pushing and capturing
+    let promotions = game
+        .all_legal_moves()
+        .into_iter()
+        .filter(|mv| mv.promotion().is_some())
+        .count();
+    assert_eq!(promotions, 8);
+    let knight = push(Some(Character::Knight(Side::White)));
+    assert_eq!(game.to_san(knight), "b8=N");
+    assert_eq!(game.parse_san("b8=N+"), Ok(knight));
+    assert!(game.parse_san("b8").is_err());
+    game.make_move(knight).unwrap();
+    assert_eq!(
+        game.board.character_at(Pos('b', 8)),
+        Some(Character::Knight(Side::White))
+    );
+    assert_eq!(game.history(), vec![knight]);
+
+    // the king is just another piece in antichess
+    let mut game =
+        Game::from_fen_with("8/1P6/8/8/8/8/8/7n w - - 0 1", crate::variants::Antichess).unwrap();
+    game.make_move(push(king)).unwrap();
+    assert_eq!(
+        game.board.character_at(Pos('b', 8)),
+        Some(Character::King(Side::White))
+    );
+}
+
+#[test]
+fn crazyhouse_promoted_test() {
+    let mut game = Game::from_fen("4k3/1P6/8/8/8/8/8/4K3[] w - - 0 1").unwrap();
+    assert_eq!(game.variant().name(), "crazyhouse");
+    game.promote_pawn("b7", "b8", Character::Queen(Side::White))
+        .unwrap();
+    assert_eq!(
+        game.board.character_at(Pos('b', 8)),
+        Some(Character::Queen(Side::White))
+    );
+    assert_eq!(game.to_fen(), "1Q~2k3/8/8/8/8/8/8/4K3[] b - - 0 1");
+
+    // a promoted queen goes back to the pocket as a pawn
+    let mut game = Game::from_fen("1Q~2k3/8/8/8/8/8/8/4K3[] b - - 0 1").unwrap();
+    game.board
+        .place_character(Character::Rook(Side::Black), Pos('a', 8));
+    game.pick("a8").unwrap().place_at(&mut game, "b8").unwrap();
+    assert_eq!(game.captured_black, vec![Character::Queen(Side::White)]);
+    assert_eq!(
+        game.pocket(Side::Black),
+        &vec![Character::Pawn(Side::Black)]
+    );
+    assert!(!game.board.is_promoted(Pos('b', 8)));
+
+    let game = Game::from_fen("4k3/8/8/8/8/8/8/4K3/Qn b - - 3 20").unwrap();
+    assert_eq!(
+        game.pocket(Side::White),
+        &vec![Character::Queen(Side::White)]
+    );
+    assert_eq!(
+        game.pocket(Side::Black),
+        &vec![Character::Knight(Side::Black)]
+    );
+    assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/8/4K3[Qn] b - - 3 20");
+    assert!(Game::from_fen("4k3/8/8/8/8/8/8/4K3[Qx] w - - 0 1").is_err());
+}
+
+#[test]
+fn setup_mode_test() {
+    let mut game = Game::new();
+    game.start();
+    assert_eq!(game.clear_board(), Err(GameError::NotInSetup));
+
+    game.setup_mode();
+    game.clear_board().unwrap();
+    game.put_piece(Character::King(Side::White), "e1").unwrap();
+    game.put_piece(Character::King(Side::Black), "e8").unwrap();
+    game.put_piece(Character::Rook(Side::White), "a1").unwrap();
+    game.put_piece(Character::Rook(Side::White), "a2").unwrap();
+    assert_eq!(game.remove_piece("a2"), Ok(Character::Rook(Side::White)));
+    assert_eq!(
+        game.remove_piece("a2"),
+        Err(GameError::EmptyCell(Pos('a', 2)))
+    );
+    game.relocate_piece("e8", "h8").unwrap();
+    game.set_turn(Side::Black).unwrap();
+    game.set_castling(CastlingRights {
+        white_queen_side: true,
+        ..CastlingRights::default()
+    })
+    .unwrap();
+    assert_eq!(game.to_fen(), "7k/8/8/8/8/8/8/R3K3 b Q - 0 1");
+
+    // castling rights no longer fit once the board is flipped
+    game.flip_board().unwrap();
+    assert_eq!(game.to_fen(), "R3K3/8/8/8/8/8/8/7k b - - 0 1");
+    game.swap_colours().unwrap();
+    game.mirror_board().unwrap();
+    assert_eq!(game.to_fen(), "3k3r/8/8/8/8/8/8/K7 b - - 0 1");
+
+    game.relocate_piece("h8", "h1").unwrap();
+    game.put_piece(Character::Pawn(Side::White), "a8").unwrap();
+    assert_eq!(
+        game.start_from_here(),
+        Err(GameError::InvalidSetup(vec![
+            SetupError::PawnOnBackRank(Pos('a', 8)),
+            SetupError::OpponentInCheck
+        ]))
+    );
+    game.remove_piece("a8").unwrap();
+    game.set_turn(Side::White).unwrap();
+    game.start_from_here().unwrap();
+    assert_eq!(game.state, GameState::Idle);
+    assert!(game.is_in_check());
+    game.pick("a1").unwrap().place_at(&mut game, "a2").unwrap();
+    assert_eq!(
+        game.put_piece(Character::Pawn(Side::White), "a2"),
+        Err(GameError::NotInSetup)
+    );
+}
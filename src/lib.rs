@@ -1,9 +1,24 @@
 pub mod characters;
 pub mod chess_board;
+pub mod eco;
+pub mod epd;
 pub mod errors;
 pub mod game;
 pub mod moves;
+pub mod nnue;
+pub mod pgn;
 pub mod pieces;
+pub mod polyglot;
 pub mod position;
+pub mod search;
+pub mod selfplay;
+pub mod stats;
+pub mod tablebase;
+pub mod tournament;
+pub mod transposition;
+pub mod tuning;
+pub mod validation;
+pub mod variants;
+pub mod zobrist;
 
 pub mod prelude {}
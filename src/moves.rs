@@ -36,6 +36,133 @@ pub mod dirs {
     pub const ONE_TOP_RIGHT: Dir = (1, 1);
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    // `promotion` is the piece a pawn reaching the last rank becomes, `None` otherwise
+    Normal {
+        from: Pos,
+        to: Pos,
+        promotion: Option<Character>,
+    },
+    // crazyhouse drop from the pocket
+    Drop {
+        character: Character,
+        to: Pos,
+    },
+}
+
+impl Move {
+    pub fn to(&self) -> Pos {
+        match self {
+            Self::Normal { to, .. } => *to,
+            Self::Drop { to, .. } => *to,
+        }
+    }
+
+    pub fn promotion(&self) -> Option<Character> {
+        match self {
+            Self::Normal { promotion, .. } => *promotion,
+            Self::Drop { .. } => None,
+        }
+    }
+
+    // long algebraic notation as UCI writes it, `e7e8q` for a promotion, `N@f3` for a drop
+    pub fn to_uci(&self) -> String {
+        match *self {
+            Self::Normal {
+                from,
+                to,
+                promotion,
+            } => {
+                let promotion = promotion.map_or(String::new(), |c| {
+                    c.with_side(Side::Black).letter().to_string()
+                });
+                format!("{from}{to}{promotion}")
+            }
+            Self::Drop { character, to } => {
+                format!("{}@{to}", character.with_side(Side::White).letter())
+            }
+        }
+    }
+
+    // `e2e4` or `e7e8n`, only white promotes on the eighth rank
+    pub fn from_uci(text: &str) -> Option<Move> {
+        let from = Pos::try_from(text.get(0..2)?).ok()?;
+        let to = Pos::try_from(text.get(2..4)?).ok()?;
+        let side = if to.rank() == 8 {
+            Side::White
+        } else {
+            Side::Black
+        };
+        let mut letters = text.get(4..)?.chars();
+        let promotion = match (letters.next(), letters.next()) {
+            (None, _) => None,
+            (Some(letter @ ('q' | 'r' | 'b' | 'n' | 'k')), None) => {
+                Some(Character::from_letter(letter)?.with_side(side))
+            }
+            _ => return None,
+        };
+        Some(Self::Normal {
+            from,
+            to,
+            promotion,
+        })
+    }
+
+    // 16 bits as stored in the transposition table: destination in the low 6 bits, origin
+    // or dropped character above it, a drop flag and the promotion piece on top
+    pub fn encode(&self) -> u16 {
+        match *self {
+            Self::Normal {
+                from,
+                to,
+                promotion,
+            } => {
+                let promotion = promotion
+                    .and_then(|c| "QRBNK".find(c.letter().to_ascii_uppercase()))
+                    .map_or(0, |index| index as u16 + 1);
+                promotion << 13 | (from.matrix_index() as u16) << 6 | to.matrix_index() as u16
+            }
+            Self::Drop { character, to } => {
+                let letter = "KQRBNPkqrbnp".find(character.letter()).unwrap_or_default();
+                1 << 12 | (letter as u16) << 6 | to.matrix_index() as u16
+            }
+        }
+    }
+
+    pub fn decode(encoded: u16) -> Option<Move> {
+        let to = Pos::from_matrix_index((encoded & 63) as usize);
+        let from = ((encoded >> 6) & 63) as usize;
+        match (encoded >> 12 & 1, encoded >> 13) {
+            (0, 0) => Some(Self::Normal {
+                from: Pos::from_matrix_index(from),
+                to,
+                promotion: None,
+            }),
+            // only white promotes on the eighth rank
+            (0, promotion) => {
+                let letter = "QRBNK".chars().nth(promotion as usize - 1)?;
+                let side = if to.rank() == 8 {
+                    Side::White
+                } else {
+                    Side::Black
+                };
+                Some(Self::Normal {
+                    from: Pos::from_matrix_index(from),
+                    to,
+                    promotion: Character::from_letter(letter).map(|c| c.with_side(side)),
+                })
+            }
+            (1, 0) => {
+                let letter = "KQRBNPkqrbnp".chars().nth(from)?;
+                let character = Character::from_letter(letter)?;
+                Some(Self::Drop { character, to })
+            }
+            _ => None,
+        }
+    }
+}
+
 // TODOs
 // move manager can perform moves
 // based on pattern specified in chess,
@@ -72,9 +199,13 @@ pub trait Moving {
                 let dirs = moves::King.to_vec();
                 self.move_maker(dirs, false)
             }
-            Character::Pawn(_) => {
+            Character::Pawn(side) => {
                 let Pos(_file, rank) = self.current_position();
-                let first_move = rank == 2 || rank == 7;
+                // pawns on the first rank only happen in horde, they may double push too
+                let first_move = match side {
+                    Side::White => rank <= 2,
+                    Side::Black => rank >= 7,
+                };
                 let dirs = moves::Pawn.to_vec();
                 self.dirs_traverser(dirs, true, |cp, mc, (d_file, d_rank)| {
                     if d_file != 0 {
@@ -94,6 +225,21 @@ pub trait Moving {
         }
     }
 
+    // squares this character attacks, including ones held by its own side
+    fn attacks(&self) -> Vec<Pos> {
+        let (dirs, infinite) = match self.character() {
+            Character::Bishop(_) => (moves::Bishop.to_vec(), true),
+            Character::Queen(_) => (moves::Queen.to_vec(), true),
+            Character::Rook(_) => (moves::Rook.to_vec(), true),
+            Character::Knight(_) => (moves::Knight.to_vec(), false),
+            Character::King(_) => (moves::King.to_vec(), false),
+            Character::Pawn(_) => (vec![dirs::ONE_TOP_LEFT, dirs::ONE_TOP_RIGHT], false),
+        };
+        let mut attacks = self.dirs_traverser(dirs, infinite, |_, mc, _| (true, mc.is_some()));
+        attacks.retain(|&pos| pos != self.current_position());
+        attacks
+    }
+
     fn can_move(&self, new_pos: Pos) -> bool {
         self.possible_moves().contains(&new_pos)
     }
@@ -138,7 +284,7 @@ pub trait Moving {
             let i = i * di;
             let mut to_remove = Vec::new();
             for (index, (d_file, d_rank)) in dirs.iter().enumerate() {
-                if let Ok(pos) = pos.d_pos(d_file * i, d_rank * i) {
+                if let Some(pos) = pos.d_pos(d_file * i, d_rank * i) {
                     let maybe_character = surounding.character_at(pos);
                     let (is_valid_pos, stop_here) = adding_condition(
                         self.character(),
@@ -170,3 +316,50 @@ pub trait Moving {
 
 #[test]
 fn test_moves() {}
+
+#[test]
+fn encode_test() {
+    let moves = [
+        Move::Normal {
+            from: Pos('e', 2),
+            to: Pos('e', 4),
+            promotion: None,
+        },
+        Move::Normal {
+            from: Pos('a', 8),
+            to: Pos('h', 1),
+            promotion: None,
+        },
+        Move::Normal {
+            from: Pos('b', 7),
+            to: Pos('a', 8),
+            promotion: Some(Character::Knight(Side::White)),
+        },
+        Move::Normal {
+            from: Pos('g', 2),
+            to: Pos('g', 1),
+            promotion: Some(Character::King(Side::Black)),
+        },
+        Move::Drop {
+            character: Character::Knight(Side::Black),
+            to: Pos('f', 3),
+        },
+    ];
+    for mv in moves {
+        assert_eq!(Move::decode(mv.encode()), Some(mv));
+    }
+    assert_eq!(Move::decode(0xffff), None);
+
+    // UCI text, back and forth
+    for mv in &moves[..4] {
+        assert_eq!(Move::from_uci(&mv.to_uci()), Some(*mv));
+    }
+    assert_eq!(moves[2].to_uci(), "b7a8n");
+    assert_eq!(moves[4].to_uci(), "N@f3");
+    assert_eq!(
+        Move::from_uci("b2b1n").and_then(|mv| mv.promotion()),
+        Some(Character::Knight(Side::Black))
+    );
+    assert_eq!(Move::from_uci("a7a8x"), None);
+    assert_eq!(Move::from_uci("e2"), None);
+}
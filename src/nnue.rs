@@ -0,0 +1,593 @@
+use std::{fs, io, path::Path};
+
+use crate::{
+    chess_board::ChessBoard,
+    errors::GameError,
+    game::Game,
+    moves::Move,
+    pieces::{Character, Side},
+    position::Pos,
+    tuning::Weights,
+};
+
+// HalfKP, every non-king character on every square, for every square of the own king
+pub const INPUTS: usize = 64 * 640;
+
+// quantization of the feature transformer and output layer, and centipawns per unit
+const QA: i32 = 255;
+const QB: i32 = 64;
+const SCALE: i32 = 400;
+
+// inputs active at once at most, a piece on every square but the two kings'
+const MAX_FEATURES: usize = 62;
+
+const MAGIC: &[u8; 4] = b"CNUE";
+const VERSION: u8 = 1;
+
+// feature transformer to `hidden` neurons per perspective, then both perspectives, side
+// to move first, through a clipped ReLU to a single output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    hidden: usize,
+    // `hidden` weights per input
+    feature_weights: Vec<i16>,
+    feature_biases: Vec<i16>,
+    output_weights: Vec<i8>,
+    output_bias: i32,
+}
+
+// feature transformer output of both perspectives, kept up to date move by move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accumulator {
+    white: Vec<i16>,
+    black: Vec<i16>,
+}
+
+impl Accumulator {
+    fn side(&mut self, perspective: Side) -> &mut Vec<i16> {
+        match perspective {
+            Side::White => &mut self.white,
+            Side::Black => &mut self.black,
+        }
+    }
+}
+
+// accumulators of the positions along a line of play, from the root to the current
+// one, a move pushes the next and taking it back pops it, so undoing needs no board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccumulatorStack {
+    accumulators: Vec<Accumulator>,
+}
+
+impl AccumulatorStack {
+    pub fn new(network: &Network, board: &ChessBoard) -> AccumulatorStack {
+        AccumulatorStack {
+            accumulators: vec![network.refresh(board)],
+        }
+    }
+
+    pub fn current(&self) -> &Accumulator {
+        self.accumulators.last().unwrap()
+    }
+
+    // takes back the last move, `None` at the root
+    pub fn pop(&mut self) -> Option<Accumulator> {
+        if self.accumulators.len() > 1 {
+            self.accumulators.pop()
+        } else {
+            None
+        }
+    }
+
+    // moves played since the root
+    pub fn len(&self) -> usize {
+        self.accumulators.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// a character a move takes off `from` and puts on `to`, `None` for a capture or the pawn
+// of a promotion leaving the board and for a promoted piece or a drop arriving
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DirtyPiece {
+    pub character: Character,
+    pub from: Option<Pos>,
+    pub to: Option<Pos>,
+}
+
+impl Network {
+    pub fn hidden(&self) -> usize {
+        self.hidden
+    }
+
+    // `CNUE`, version, hidden size as u32, then feature weights, feature biases, output
+    // weights and output bias, all little endian
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        bytes.extend((self.hidden as u32).to_le_bytes());
+        for value in self.feature_weights.iter().chain(&self.feature_biases) {
+            bytes.extend(value.to_le_bytes());
+        }
+        bytes.extend(self.output_weights.iter().map(|&weight| weight as u8));
+        bytes.extend(self.output_bias.to_le_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Network> {
+        let invalid = |reason: &str| io::Error::new(io::ErrorKind::InvalidData, reason.to_owned());
+        if bytes.len() < 9 || &bytes[..4] != MAGIC || bytes[4] != VERSION {
+            return Err(invalid("not a network file"));
+        }
+        let hidden = u32::from_le_bytes([bytes[5], bytes[6], bytes[7], bytes[8]]) as usize;
+        let weights = INPUTS * hidden;
+        let expected = 9 + 2 * (weights + hidden) + 2 * hidden + 4;
+        if hidden == 0 || bytes.len() != expected {
+            return Err(invalid("network size doesn't match its hidden layer"));
+        }
+        let mut i16s = bytes[9..9 + 2 * (weights + hidden)]
+            .chunks_exact(2)
+            .map(|pair| i16::from_le_bytes([pair[0], pair[1]]));
+        let feature_weights = i16s.by_ref().take(weights).collect();
+        let feature_biases = i16s.collect();
+        let rest = &bytes[9 + 2 * (weights + hidden)..];
+        let output_weights = rest[..2 * hidden].iter().map(|&byte| byte as i8).collect();
+        let bias = &rest[2 * hidden..];
+        let network = Network {
+            hidden,
+            feature_weights,
+            feature_biases,
+            output_weights,
+            output_bias: i32::from_le_bytes([bias[0], bias[1], bias[2], bias[3]]),
+        };
+        if !network.fits_accumulator() {
+            return Err(invalid("feature weights can overflow the accumulator"));
+        }
+        Ok(network)
+    }
+
+    // whether every neuron's bias and its `MAX_FEATURES` largest weights stay within
+    // i16 whatever their signs, then no board overflows an accumulator
+    fn fits_accumulator(&self) -> bool {
+        let mut column = vec![0; INPUTS];
+        (0..self.hidden).all(|neuron| {
+            for (input, magnitude) in column.iter_mut().enumerate() {
+                *magnitude = (self.feature_weights[input * self.hidden + neuron] as i32).abs();
+            }
+            column.select_nth_unstable_by(MAX_FEATURES - 1, |a, b| b.cmp(a));
+            let largest: i32 = column[..MAX_FEATURES].iter().sum();
+            (self.feature_biases[neuron] as i32).abs() + largest <= i16::MAX as i32
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Network> {
+        Network::from_bytes(&fs::read(path)?)
+    }
+
+    // accumulator computed from scratch
+    pub fn refresh(&self, board: &ChessBoard) -> Accumulator {
+        Accumulator {
+            white: self.refresh_side(board, Side::White),
+            black: self.refresh_side(board, Side::Black),
+        }
+    }
+
+    fn refresh_side(&self, board: &ChessBoard, perspective: Side) -> Vec<i16> {
+        let mut values = self.feature_biases.clone();
+        if let Some(king) = board.king_position(perspective) {
+            for pos in (0..64).map(Pos::from_matrix_index) {
+                if let Some(index) = board
+                    .character_at(pos)
+                    .and_then(|character| feature(perspective, king, character, pos))
+                {
+                    self.add_feature(&mut values, index);
+                }
+            }
+        }
+        values
+    }
+
+    // plays `mv` and pushes the accumulator of the position it leads to, `pop` takes it
+    // back, the dirty pieces are what changed on the board
+    pub fn make_move(
+        &self,
+        stack: &mut AccumulatorStack,
+        game: &mut Game,
+        mv: Move,
+    ) -> Result<Vec<DirtyPiece>, GameError> {
+        // the squares a move can change, those around the target for atomic explosions
+        let mut squares: Vec<Pos> = match mv {
+            Move::Normal { to, .. } => (-1..=1)
+                .flat_map(|d_file| (-1..=1).filter_map(move |d_rank| to.d_pos(d_file, d_rank)))
+                .collect(),
+            Move::Drop { to, .. } => vec![to],
+        };
+        if let Move::Normal { from, .. } = mv {
+            // a castling king brings its rook along from the corner
+            let mut extra = vec![from];
+            if let Some(Character::King(_)) = game.board.character_at(from) {
+                extra.extend([Pos('a', from.rank()), Pos('h', from.rank())]);
+            }
+            for pos in extra {
+                if !squares.contains(&pos) {
+                    squares.push(pos);
+                }
+            }
+        }
+        let before: Vec<_> = squares
+            .iter()
+            .map(|&pos| game.board.character_at(pos))
+            .collect();
+        game.make_move(mv)?;
+
+        let mut removed = Vec::new();
+        let mut added = Vec::new();
+        for (&pos, old) in squares.iter().zip(before) {
+            let new = game.board.character_at(pos);
+            if old != new {
+                removed.extend(old.map(|character| (character, pos)));
+                added.extend(new.map(|character| (character, pos)));
+            }
+        }
+        let mut dirty: Vec<DirtyPiece> = removed
+            .into_iter()
+            .map(|(character, from)| {
+                let to = added
+                    .iter()
+                    .position(|&(arrived, _)| arrived == character)
+                    .map(|index| added.swap_remove(index).1);
+                DirtyPiece {
+                    character,
+                    from: Some(from),
+                    to,
+                }
+            })
+            .collect();
+        dirty.extend(added.into_iter().map(|(character, to)| DirtyPiece {
+            character,
+            from: None,
+            to: Some(to),
+        }));
+        let mut accumulator = stack.current().clone();
+        self.update(&mut accumulator, &dirty, &game.board);
+        stack.accumulators.push(accumulator);
+        Ok(dirty)
+    }
+
+    // applies the dirty pieces of a move, only their features change unless a king
+    // moved, whose perspective is refreshed from `board`, the position after them
+    pub fn update(&self, accumulator: &mut Accumulator, dirty: &[DirtyPiece], board: &ChessBoard) {
+        for perspective in [Side::White, Side::Black] {
+            if dirty
+                .iter()
+                .any(|piece| piece.character == Character::King(perspective))
+            {
+                *accumulator.side(perspective) = self.refresh_side(board, perspective);
+                continue;
+            }
+            let Some(king) = board.king_position(perspective) else {
+                continue;
+            };
+            let values = accumulator.side(perspective);
+            for piece in dirty {
+                let feature = |pos| feature(perspective, king, piece.character, pos);
+                if let Some(index) = piece.from.and_then(feature) {
+                    self.remove_feature(values, index);
+                }
+                if let Some(index) = piece.to.and_then(feature) {
+                    self.add_feature(values, index);
+                }
+            }
+        }
+    }
+
+    // `fits_accumulator` keeps the sums in range
+    fn add_feature(&self, values: &mut [i16], index: usize) {
+        add(
+            values,
+            &self.feature_weights[index * self.hidden..(index + 1) * self.hidden],
+        );
+    }
+
+    fn remove_feature(&self, values: &mut [i16], index: usize) {
+        sub(
+            values,
+            &self.feature_weights[index * self.hidden..(index + 1) * self.hidden],
+        );
+    }
+
+    // centipawns for `side`
+    pub fn evaluate(&self, accumulator: &Accumulator, side: Side) -> i32 {
+        let (own, other) = match side {
+            Side::White => (&accumulator.white, &accumulator.black),
+            Side::Black => (&accumulator.black, &accumulator.white),
+        };
+        let (own_weights, other_weights) = self.output_weights.split_at(self.hidden);
+        let output = layer(own, own_weights) + layer(other, other_weights) + self.output_bias;
+        output * SCALE / (QA * QB)
+    }
+}
+
+// plain loops over contiguous slices, which the compiler vectorizes for the target it
+// was built for, the `simd` feature picks AVX2 at run time when the CPU has it
+fn add(values: &mut [i16], weights: &[i16]) {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    if is_x86_feature_detected!("avx2") {
+        // AVX2 is there, checked above
+        return unsafe { simd::add(values, weights) };
+    }
+    for (value, weight) in values.iter_mut().zip(weights) {
+        *value += *weight;
+    }
+}
+
+fn sub(values: &mut [i16], weights: &[i16]) {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    if is_x86_feature_detected!("avx2") {
+        return unsafe { simd::sub(values, weights) };
+    }
+    for (value, weight) in values.iter_mut().zip(weights) {
+        *value -= *weight;
+    }
+}
+
+// output layer of one perspective, clipped ReLU then the weights
+fn layer(values: &[i16], weights: &[i8]) -> i32 {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    if is_x86_feature_detected!("avx2") {
+        return unsafe { simd::layer(values, weights) };
+    }
+    values
+        .iter()
+        .zip(weights)
+        .map(|(&value, &weight)| (value as i32).clamp(0, QA) * weight as i32)
+        .sum()
+}
+
+// sixteen i16 lanes at a time, the rest one by one, callers make sure of AVX2
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd {
+    use std::arch::x86_64::*;
+
+    const LANES: usize = 16;
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn add(values: &mut [i16], weights: &[i16]) {
+        let len = values.len().min(weights.len());
+        for i in (0..len / LANES * LANES).step_by(LANES) {
+            let value = _mm256_loadu_si256(values.as_ptr().add(i) as *const __m256i);
+            let weight = _mm256_loadu_si256(weights.as_ptr().add(i) as *const __m256i);
+            let sum = _mm256_add_epi16(value, weight);
+            _mm256_storeu_si256(values.as_mut_ptr().add(i) as *mut __m256i, sum);
+        }
+        for i in len / LANES * LANES..len {
+            values[i] += weights[i];
+        }
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn sub(values: &mut [i16], weights: &[i16]) {
+        let len = values.len().min(weights.len());
+        for i in (0..len / LANES * LANES).step_by(LANES) {
+            let value = _mm256_loadu_si256(values.as_ptr().add(i) as *const __m256i);
+            let weight = _mm256_loadu_si256(weights.as_ptr().add(i) as *const __m256i);
+            let difference = _mm256_sub_epi16(value, weight);
+            _mm256_storeu_si256(values.as_mut_ptr().add(i) as *mut __m256i, difference);
+        }
+        for i in len / LANES * LANES..len {
+            values[i] -= weights[i];
+        }
+    }
+
+    // the i8 weights widen to i16, pairs of products sum to i32 lanes
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn layer(values: &[i16], weights: &[i8]) -> i32 {
+        let len = values.len().min(weights.len());
+        let zero = _mm256_setzero_si256();
+        let ceiling = _mm256_set1_epi16(super::QA as i16);
+        let mut sums = zero;
+        for i in (0..len / LANES * LANES).step_by(LANES) {
+            let value = _mm256_loadu_si256(values.as_ptr().add(i) as *const __m256i);
+            let clipped = _mm256_min_epi16(_mm256_max_epi16(value, zero), ceiling);
+            let weight = _mm_loadu_si128(weights.as_ptr().add(i) as *const __m128i);
+            let product = _mm256_madd_epi16(clipped, _mm256_cvtepi8_epi16(weight));
+            sums = _mm256_add_epi32(sums, product);
+        }
+        let mut lanes = [0i32; 8];
+        _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, sums);
+        let tail: i32 = (len / LANES * LANES..len)
+            .map(|i| (values[i] as i32).clamp(0, super::QA) * weights[i] as i32)
+            .sum();
+        lanes.iter().sum::<i32>() + tail
+    }
+}
+
+// input of `character` on `pos` seen by `perspective` with its king on `king`, squares
+// are flipped for black so both sides share the weights, kings aren't inputs
+fn feature(perspective: Side, king: Pos, character: Character, pos: Pos) -> Option<usize> {
+    let kind = match character {
+        Character::Pawn(_) => 0,
+        Character::Knight(_) => 1,
+        Character::Bishop(_) => 2,
+        Character::Rook(_) => 3,
+        Character::Queen(_) => 4,
+        Character::King(_) => return None,
+    };
+    let square = |pos: Pos| {
+        let square = (pos.rank() as usize - 1) * 8 + (pos.file() as usize - 'a' as usize);
+        match perspective {
+            Side::White => square,
+            Side::Black => square ^ 56,
+        }
+    };
+    let colour = (character.side() != perspective) as usize;
+    Some(square(king) * 640 + (kind * 2 + colour) * 64 + square(pos))
+}
+
+// the network when one is loaded, otherwise the classical material and mobility terms
+pub enum Evaluator {
+    Nnue(Network),
+    Classical(Weights),
+}
+
+impl Evaluator {
+    // the classical terms only when there's no file, a corrupt one is an error
+    pub fn load_or_classical(path: impl AsRef<Path>) -> io::Result<Evaluator> {
+        match Network::load(path) {
+            Ok(network) => Ok(Evaluator::Nnue(network)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                Ok(Evaluator::Classical(Weights::default()))
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    // centipawns for side to move, from scratch, a search keeps an `AccumulatorStack`
+    // up to date with `Network::make_move` instead
+    pub fn evaluate(&self, game: &Game) -> i32 {
+        let side = game.whose_turn();
+        match self {
+            Evaluator::Nnue(network) => network.evaluate(&network.refresh(&game.board), side),
+            Evaluator::Classical(weights) => match side {
+                Side::White => weights.evaluate(game),
+                Side::Black => -weights.evaluate(game),
+            },
+        }
+    }
+}
+
+#[test]
+fn nnue_test() {
+    use crate::{moves::Move, polyglot::Rng};
+
+    // more than one AVX2 register and a rest
+    let hidden = 20;
+    let mut rng = Rng::new(3);
+    let mut small = |range: u64| (rng.below(2 * range + 1) as i64 - range as i64) as i16;
+    let network = Network {
+        hidden,
+        feature_weights: (0..INPUTS * hidden).map(|_| small(40)).collect(),
+        feature_biases: (0..hidden).map(|_| small(40)).collect(),
+        output_weights: (0..2 * hidden).map(|_| small(60) as i8).collect(),
+        output_bias: 1000,
+    };
+    let bytes = network.to_bytes();
+    assert_eq!(Network::from_bytes(&bytes).unwrap(), network);
+    assert!(Network::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+    // a bias the largest weights could push past i16 is rejected
+    let mut overflowing = network.clone();
+    overflowing.feature_biases[3] = 32000;
+    let error = Network::from_bytes(&overflowing.to_bytes()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "feature weights can overflow the accumulator"
+    );
+    overflowing.feature_biases[3] = -32000;
+    assert!(Network::from_bytes(&overflowing.to_bytes()).is_err());
+
+    // incremental updates match a refresh, taking moves back pops to the earlier ones
+    let mut game = Game::from_fen("r6k/8/3p4/8/4N3/8/8/4K3 w - - 0 1").unwrap();
+    let mut stack = AccumulatorStack::new(&network, &game.board);
+    let root = stack.current().clone();
+    let nxd6 = Move::Normal {
+        from: Pos('e', 4),
+        to: Pos('d', 6),
+        promotion: None,
+    };
+    let dirty = network.make_move(&mut stack, &mut game, nxd6).unwrap();
+    let captured = DirtyPiece {
+        character: Character::Pawn(Side::Black),
+        from: Some(Pos('d', 6)),
+        to: None,
+    };
+    assert_eq!(dirty.len(), 2);
+    assert!(dirty.contains(&captured));
+    assert_eq!(stack.current(), &network.refresh(&game.board));
+    let after_capture = stack.current().clone();
+
+    let ra6 = Move::Normal {
+        from: Pos('a', 8),
+        to: Pos('a', 6),
+        promotion: None,
+    };
+    network.make_move(&mut stack, &mut game, ra6).unwrap();
+    assert_eq!(stack.current(), &network.refresh(&game.board));
+
+    // a king move refreshes its perspective
+    let kf2 = Move::Normal {
+        from: Pos('e', 1),
+        to: Pos('f', 2),
+        promotion: None,
+    };
+    network.make_move(&mut stack, &mut game, kf2).unwrap();
+    assert_eq!(stack.current(), &network.refresh(&game.board));
+    // an illegal move pushes nothing
+    assert!(network.make_move(&mut stack, &mut game, kf2).is_err());
+    assert_eq!(stack.len(), 3);
+
+    assert!(stack.pop().is_some());
+    assert!(stack.pop().is_some());
+    assert_eq!(stack.current(), &after_capture);
+    assert!(stack.pop().is_some());
+    assert_eq!(stack.current(), &root);
+    assert_eq!(stack.pop(), None);
+    assert!(stack.is_empty());
+
+    // a capturing promotion
+    let mut game = Game::from_fen("1n5k/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let mut stack = AccumulatorStack::new(&network, &game.board);
+    let axb8 = Move::Normal {
+        from: Pos('a', 7),
+        to: Pos('b', 8),
+        promotion: Some(Character::Queen(Side::White)),
+    };
+    let dirty = network.make_move(&mut stack, &mut game, axb8).unwrap();
+    assert_eq!(dirty.len(), 3);
+    assert_eq!(stack.current(), &network.refresh(&game.board));
+
+    // the vector paths agree with plain loops at the edges of the ranges
+    let values: Vec<i16> = (0..37).map(|i| i * 900 - 16000).collect();
+    let weights: Vec<i16> = (0..37).map(|i| 300 - i * 17).collect();
+    let mut sum = values.clone();
+    add(&mut sum, &weights);
+    assert!(sum
+        .iter()
+        .zip(&values)
+        .zip(&weights)
+        .all(|((s, v), w)| *s == v + w));
+    sub(&mut sum, &weights);
+    assert_eq!(sum, values);
+    let output: Vec<i8> = (0..37).map(|i| (i * 7 - 128) as i8).collect();
+    let expected: i32 = values
+        .iter()
+        .zip(&output)
+        .map(|(&v, &w)| (v as i32).clamp(0, QA) * w as i32)
+        .sum();
+    assert_eq!(layer(&values, &output), expected);
+
+    // the same position mirrored with colours swapped evaluates the same for the mover
+    let white = Game::from_fen("4k3/8/8/8/4N3/8/3P4/4K3 w - - 0 1").unwrap();
+    let black = Game::from_fen("4k3/3p4/8/4n3/8/8/8/4K3 b - - 0 1").unwrap();
+    let evaluator = Evaluator::Nnue(network);
+    assert_eq!(evaluator.evaluate(&white), evaluator.evaluate(&black));
+
+    let classical = Evaluator::load_or_classical("/nonexistent/network.nnue").unwrap();
+    assert!(matches!(classical, Evaluator::Classical(_)));
+    let path = std::env::temp_dir().join("chess_game_engine_corrupt.nnue");
+    fs::write(&path, b"CNUE").unwrap();
+    let corrupt = Evaluator::load_or_classical(&path);
+    fs::remove_file(&path).unwrap();
+    assert!(corrupt.is_err());
+    assert_eq!(
+        classical.evaluate(&black),
+        -Weights::default().evaluate(&black)
+    );
+}
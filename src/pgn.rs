@@ -0,0 +1,205 @@
+use crate::{errors::GameError, game::Game, moves::Move, tournament::GameResult};
+
+// a game of a PGN file, moves replayed from the initial position or the `FEN` tag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnGame {
+    // in the order they're written, escapes removed
+    pub tags: Vec<(String, String)>,
+    pub moves: Vec<Move>,
+    // `None` for `*` or a game with no result
+    pub result: Option<GameResult>,
+}
+
+impl PgnGame {
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    // position the moves start from
+    pub fn start(&self) -> Result<Game, GameError> {
+        match self.tag("FEN") {
+            Some(fen) => Game::from_fen(fen),
+            None => {
+                let mut game = Game::new();
+                game.start();
+                Ok(game)
+            }
+        }
+    }
+}
+
+// every game of `text`, comments, variations, NAGs and move numbers are skipped and the
+// main line replayed with `parse_san`, the first move that doesn't parse is the error
+pub fn parse_pgn(text: &str) -> Result<Vec<PgnGame>, GameError> {
+    let mut games = Vec::new();
+    let mut tags = Vec::new();
+    let mut tokens: Vec<String> = Vec::new();
+    let mut token = String::new();
+    let mut depth = 0;
+    // the newline at the end closes the last token
+    let mut chars = text.chars().chain(Some('\n'));
+    while let Some(c) = chars.next() {
+        let ends_token = matches!(c, '[' | '{' | ';' | '(' | ')') || c.is_whitespace();
+        if ends_token && !token.is_empty() {
+            tokens.push(std::mem::take(&mut token));
+        }
+        match c {
+            '{' => {
+                chars.by_ref().find(|&c| c == '}');
+            }
+            ';' => {
+                chars.by_ref().find(|&c| c == '\n');
+            }
+            '(' => depth += 1,
+            ')' => depth = (depth - 1).max(0),
+            _ if depth > 0 || c.is_whitespace() => {}
+            '[' => {
+                // the tags of the next game, the last one ended without a result
+                if !tokens.is_empty() {
+                    games.push(replay(std::mem::take(&mut tags), &tokens, None)?);
+                    tokens.clear();
+                }
+                tags.push(read_tag(&mut chars)?);
+            }
+            _ => token.push(c),
+        }
+        let result = match tokens.last().map(String::as_str) {
+            Some("1-0") => Some(Some(GameResult::WhiteWins)),
+            Some("0-1") => Some(Some(GameResult::BlackWins)),
+            Some("1/2-1/2") => Some(Some(GameResult::Draw)),
+            Some("*") => Some(None),
+            _ => None,
+        };
+        if let Some(result) = result {
+            tokens.pop();
+            games.push(replay(std::mem::take(&mut tags), &tokens, result)?);
+            tokens.clear();
+        }
+    }
+    if !tags.is_empty() || !tokens.is_empty() {
+        games.push(replay(tags, &tokens, None)?);
+    }
+    Ok(games)
+}
+
+// `Name "value"]` after the opening bracket
+fn read_tag(chars: &mut impl Iterator<Item = char>) -> Result<(String, String), GameError> {
+    let mut name = String::new();
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut closed = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if quoted => value.extend(chars.next()),
+            '"' => quoted = !quoted,
+            ']' if !quoted => {
+                closed = true;
+                break;
+            }
+            _ if quoted => value.push(c),
+            _ if !c.is_whitespace() => name.push(c),
+            _ => {}
+        }
+    }
+    if !closed || name.is_empty() {
+        return Err(GameError::InvalidNotation(format!("[{name} \"{value}\"")));
+    }
+    Ok((name, value))
+}
+
+fn replay(
+    tags: Vec<(String, String)>,
+    tokens: &[String],
+    result: Option<GameResult>,
+) -> Result<PgnGame, GameError> {
+    let mut game = PgnGame {
+        tags,
+        moves: Vec::new(),
+        result,
+    };
+    let mut position = game.start()?;
+    for token in tokens {
+        // `12.`, `12...` and `12.e4` carry a move number, `0-0` doesn't
+        let san = match token.rsplit_once('.') {
+            Some((_, san)) => san,
+            None if token.chars().all(|c| c.is_ascii_digit()) => "",
+            None => token,
+        };
+        if san.is_empty() || san.starts_with('$') {
+            continue;
+        }
+        let mv = position.parse_san(san)?;
+        position.make_move(mv)?;
+        game.moves.push(mv);
+    }
+    Ok(game)
+}
+
+#[test]
+fn pgn_test() {
+    use crate::{
+        pieces::{Character, Side},
+        position::Pos,
+    };
+
+    let text = r#"
+[Event "Casual \"blitz\""]
+[White "A"]
+[Black "B"]
+[Result "1-0"]
+
+1. e4 {best by test} e5 2. Nf3 Nc6 (2... d6 3. d4) 3. Bc4 $1 Nf6 4. O-O Be7
+; a rest of line comment
+5.d4 exd4 6. e5 d5 7. exd6 Qxd6 1-0
+
+[Event "Underpromotion"]
+[SetUp "1"]
+[FEN "8/1P4k1/8/8/8/8/6K1/8 w - - 0 1"]
+
+1. b8=N Kf6 *
+
+1. d4 d5
+"#;
+    let games = parse_pgn(text).unwrap();
+    assert_eq!(games.len(), 3);
+
+    let game = &games[0];
+    assert_eq!(game.tag("Event"), Some("Casual \"blitz\""));
+    assert_eq!(game.tag("Round"), None);
+    assert_eq!(game.result, Some(GameResult::WhiteWins));
+    assert_eq!(game.moves.len(), 14);
+    // castling and en passant replay as the king's two squares and the pawn's diagonal
+    let mv = |from: &str, to: &str| Move::Normal {
+        from: Pos::try_from(from).unwrap(),
+        to: Pos::try_from(to).unwrap(),
+        promotion: None,
+    };
+    assert_eq!(game.moves[6], mv("e1", "g1"));
+    assert_eq!(game.moves[12], mv("e5", "d6"));
+    let mut position = game.start().unwrap();
+    for &mv in &game.moves {
+        position.make_move(mv).unwrap();
+    }
+    assert_eq!(
+        position.to_fen(),
+        "r1b1k2r/ppp1bppp/2nq1n2/8/2Bp4/5N2/PPP2PPP/RNBQ1RK1 w kq - 0 8"
+    );
+
+    let game = &games[1];
+    assert_eq!(game.result, None);
+    assert_eq!(
+        game.moves[0].promotion(),
+        Some(Character::Knight(Side::White))
+    );
+    assert_eq!(games[2].moves.len(), 2);
+
+    // the move that isn't legal is the error
+    assert_eq!(
+        parse_pgn("1. e4 e5 2. Ke3 1-0"),
+        Err(GameError::InvalidNotation("Ke3".to_owned()))
+    );
+    assert!(parse_pgn("[Event \"unclosed").is_err());
+}
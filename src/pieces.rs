@@ -1,14 +1,14 @@
 #![allow(dead_code)]
 
-use std::{cell::RefCell, fmt::format, mem, ops::DerefMut};
+use std::{cell::RefCell, fmt::Display};
 
 use crate::{
     characters,
     chess_board::ChessBoard,
     errors::GameError,
-    game::{Game, GameState},
+    game::Game,
     moves::Moving,
-    position::Pos,
+    position::{Pos, Square},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -16,13 +16,22 @@ pub enum Side {
     White,
     Black,
 }
-impl ToString for Side {
-    fn to_string(&self) -> String {
+impl Side {
+    pub fn opponent(self) -> Side {
         match self {
+            Self::White => Self::Black,
+            Self::Black => Self::White,
+        }
+    }
+}
+
+impl Display for Side {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let side = match self {
             Self::White => "White",
             Self::Black => "Black",
-        }
-        .to_owned()
+        };
+        write!(f, "{side}")
     }
 }
 
@@ -73,33 +82,96 @@ impl Character {
         }
     }
 
+    // material in centipawns, the king outweighs everything else together
+    pub fn value(&self) -> i32 {
+        match self {
+            Self::King(_) => 10000,
+            Self::Queen(_) => 900,
+            Self::Rook(_) => 500,
+            Self::Bishop(_) => 300,
+            Self::Knight(_) => 300,
+            Self::Pawn(_) => 100,
+        }
+    }
+
     pub fn same_side(character_a: Character, character_b: Character) -> bool {
         character_a.side() == character_b.side()
     }
-}
 
-impl ToString for Character {
-    fn to_string(&self) -> String {
-        let character = match self {
+    // name without side, ie. `Knight`
+    pub fn name(&self) -> &'static str {
+        match self {
             Self::King(_) => "King",
             Self::Queen(_) => "Queen",
             Self::Knight(_) => "Knight",
             Self::Bishop(_) => "Bishop",
             Self::Rook(_) => "Rook",
             Self::Pawn(_) => "Pawn",
+        }
+    }
+
+    pub fn with_side(self, side: Side) -> Character {
+        match self {
+            Self::King(_) => Self::King(side),
+            Self::Queen(_) => Self::Queen(side),
+            Self::Knight(_) => Self::Knight(side),
+            Self::Rook(_) => Self::Rook(side),
+            Self::Bishop(_) => Self::Bishop(side),
+            Self::Pawn(_) => Self::Pawn(side),
+        }
+    }
+
+    // letter used by FEN and move notation, uppercase for white
+    pub fn letter(&self) -> char {
+        let letter = match self {
+            Self::King(_) => 'k',
+            Self::Queen(_) => 'q',
+            Self::Knight(_) => 'n',
+            Self::Rook(_) => 'r',
+            Self::Bishop(_) => 'b',
+            Self::Pawn(_) => 'p',
+        };
+        if self.is_white() {
+            letter.to_ascii_uppercase()
+        } else {
+            letter
+        }
+    }
+
+    pub fn from_letter(letter: char) -> Option<Character> {
+        let side = if letter.is_ascii_uppercase() {
+            Side::White
+        } else {
+            Side::Black
         };
+        match letter.to_ascii_lowercase() {
+            'k' => Some(Self::King(side)),
+            'q' => Some(Self::Queen(side)),
+            'n' => Some(Self::Knight(side)),
+            'r' => Some(Self::Rook(side)),
+            'b' => Some(Self::Bishop(side)),
+            'p' => Some(Self::Pawn(side)),
+            _ => None,
+        }
+    }
+}
 
-        let side = self.side().to_string();
-        format!("{character}_{side}")
+impl Display for Character {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let character = self.name();
+        let side = self.side();
+        write!(f, "{character}_{side}")
     }
 }
 
+// handle to a picked character, the game keeps owning the board so dropping it is safe
 #[derive(Debug, Clone)]
 pub struct Piece {
     pub character: Character,
     pub position: Pos,
     pub side: Side,
-    pub surrounding: Option<RefCell<ChessBoard>>,
+    // snapshot of the board at picking time, empty for alone pieces
+    pub surrounding: RefCell<ChessBoard>,
 }
 
 impl Piece {
@@ -109,7 +181,7 @@ impl Piece {
             character,
             position,
             side,
-            surrounding: surrounding.map(RefCell::new),
+            surrounding: RefCell::new(surrounding.unwrap_or_default()),
         }
     }
 
@@ -124,44 +196,24 @@ impl Piece {
     pub fn place_at(
         self,
         game: &mut Game,
-        pos: impl TryInto<Pos>,
+        pos: impl TryInto<Square, Error: Into<GameError>>,
     ) -> Result<Option<Character>, GameError> {
-        match pos.try_into() {
-            Ok(pos) => {
-                let is_current_pos = self.position == pos; // want to place back
-                if is_current_pos || self.can_move(pos) {
-                    match self.surrounding {
-                        Some(ref surrounding_ref) => {
-                            let mut surrounding = surrounding_ref.borrow_mut();
-                            let res = surrounding.place_character(self.character, pos);
-                            if let Some(character) = res {
-                                match game.whose_turn() {
-                                    Side::White => game.captured_white.push(character),
-                                    Side::Black => game.captured_black.push(character),
-                                }
-                            }
-                            game.board = mem::take(surrounding.deref_mut());
-                            if !is_current_pos {
-                                game.state = GameState::PiecePlaced;
-                                game.change_side(); //implicitly changing side
-                                Ok(res)
-                            } else {
-                                Ok(None)
-                            }
-                        }
-                        None => Err(GameError::AlonePiece),
-                    }
-                } else {
-                    Err(GameError::InvalidMove)
-                }
-            }
-            Err(_) => Err(GameError::InvalidPosition),
+        if !self.is_picked_in(game) {
+            return Err(GameError::NotPicked(self.position));
         }
+        game.place_picked(pos)
     }
 
+    // unpicks, does nothing if the piece isn't the one picked in `game`
     pub fn place_back(self, game: &mut Game) {
-        let position = self.position;
-        self.place_at(game, position).unwrap();
+        if self.is_picked_in(game) {
+            game.cancel_pick();
+        }
+    }
+
+    fn is_picked_in(&self, game: &Game) -> bool {
+        game.picked() == Some(self.position)
+            && game.board.character_at(self.position) == Some(self.character)
     }
 }
 
@@ -175,7 +227,7 @@ impl Moving for Piece {
     }
 
     fn surrounding(&self) -> std::cell::RefMut<'_, ChessBoard> {
-        self.surrounding.as_ref().unwrap().borrow_mut()
+        self.surrounding.borrow_mut()
     }
 }
 
@@ -189,4 +241,15 @@ fn piece_test() {
     let character = Character::Knight(Side::Black);
 
     assert_eq!(character.to_string(), "Knight_Black".to_owned());
+
+    assert_eq!(character.letter(), 'n');
+    assert_eq!(
+        Character::from_letter('N'),
+        Some(Character::Knight(Side::White))
+    );
+    assert_eq!(Character::from_letter('x'), None);
+    assert_eq!(
+        character.with_side(Side::White),
+        Character::Knight(Side::White)
+    );
 }
@@ -1,10 +1,13 @@
-use crate::errors::GameError;
-use std::fmt::Debug;
+use crate::errors::{GameError, PosErr};
+use std::fmt::{Debug, Display};
 use std::ops::RangeInclusive;
+use std::str::FromStr;
 
-const FILE_RANGE: RangeInclusive<u8> = ('a' as u8)..=('h' as u8);
+const FILE_RANGE: RangeInclusive<u8> = b'a'..=b'h';
 const RANK_RANGE: RangeInclusive<u8> = 1..=8;
 
+// fields are public so `Pos` can hold squares off the board, `Game` takes a `Square`
+// wherever a position comes from outside, and a `Pos` converts to one only when valid
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Pos(pub char, pub u8);
 impl Pos {
@@ -12,7 +15,7 @@ impl Pos {
         if Pos::is_valid(file, rank) {
             Ok(Pos(file, rank))
         } else {
-            Err(GameError::InvalidPosition)
+            Err(GameError::InvalidPosition(format!("{file}{rank}")))
         }
     }
 
@@ -20,14 +23,14 @@ impl Pos {
         FILE_RANGE.contains(&(file as u8)) && RANK_RANGE.contains(&rank)
     }
 
-    pub fn d_pos(&self, d_file: i32, d_rank: i32) -> Result<Pos, ()> {
+    pub fn d_pos(&self, d_file: i32, d_rank: i32) -> Option<Pos> {
         // need to refactor almost all stuff about this function
         let new_file = (self.file() as i32 + d_file) as u8 as char;
         let new_rank = (self.rank() as i32 + d_rank) as u8;
         if Pos::is_valid(new_file, new_rank) {
-            Ok(Pos(new_file, new_rank))
+            Some(Pos(new_file, new_rank))
         } else {
-            Err(())
+            None
         }
     }
 
@@ -39,7 +42,20 @@ impl Pos {
         self.0
     }
 
-    pub fn index(&self) -> usize {
+    // matrix order of `ChessBoard`, a8 = 0 to h1 = 63, see `Square::index` for a1 = 0
+    pub fn from_matrix_index(index: usize) -> Pos {
+        let (row, col) = (index / 8 % 8, index % 8);
+        Pos((b'a' + col as u8) as char, 8 - row as u8)
+    }
+
+    pub fn is_adjacent(&self, other: Pos) -> bool {
+        let d_file = (self.file() as i32 - other.file() as i32).abs();
+        let d_rank = (self.rank() as i32 - other.rank() as i32).abs();
+        *self != other && d_file <= 1 && d_rank <= 1
+    }
+
+    // a8 = 0 to h1 = 63, the inverse of `Pos::from_matrix_index`
+    pub fn matrix_index(&self) -> usize {
         let (row, col) = self.at_matrix();
         row * 8 + col
     }
@@ -52,23 +68,26 @@ impl Pos {
     }
 }
 
-impl From<(i32, i32)> for Pos {
-    fn from((mut x, y): (i32, i32)) -> Self {
-        x -= 1;
-        let file = (x as u8 + 'a' as u8) as char;
-        let rank = y as u8;
-        Pos(file, rank)
+// file and rank counted from 1, ie. `(5, 4)` for e4
+impl TryFrom<(i32, i32)> for Pos {
+    type Error = GameError;
+    fn try_from((file, rank): (i32, i32)) -> Result<Self, Self::Error> {
+        let invalid = || GameError::InvalidPosition(format!("({file}, {rank})"));
+        let file = File::from_index(u8::try_from(file - 1).map_err(|_| invalid())?);
+        let rank = Rank::from_number(u8::try_from(rank).map_err(|_| invalid())?);
+        match (file, rank) {
+            (Some(file), Some(rank)) => Ok(Pos::from(Square::new(file, rank))),
+            _ => Err(invalid()),
+        }
     }
 }
 
 impl TryFrom<&str> for Pos {
     type Error = GameError;
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let s = s.to_lowercase();
-        Pos::new(
-            s.chars().next().unwrap(),
-            s.chars().nth(1).unwrap().to_digit(10).unwrap() as u8,
-        )
+        s.parse::<Square>()
+            .map(Pos::from)
+            .map_err(|_| GameError::InvalidPosition(s.to_owned()))
     }
 }
 
@@ -77,3 +96,255 @@ impl Debug for Pos {
         write!(f, "{}{}", self.file(), self.rank())
     }
 }
+
+impl Display for Pos {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum File {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+}
+
+impl File {
+    pub const ALL: [File; 8] = [
+        File::A,
+        File::B,
+        File::C,
+        File::D,
+        File::E,
+        File::F,
+        File::G,
+        File::H,
+    ];
+
+    pub fn from_index(index: u8) -> Option<File> {
+        File::ALL.get(index as usize).copied()
+    }
+
+    pub fn from_char(file: char) -> Option<File> {
+        let index = (file.to_ascii_lowercase() as u32).checked_sub('a' as u32)?;
+        File::from_index(u8::try_from(index).ok()?)
+    }
+
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+
+    pub fn char(self) -> char {
+        (b'a' + self.index()) as char
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rank {
+    First,
+    Second,
+    Third,
+    Fourth,
+    Fifth,
+    Sixth,
+    Seventh,
+    Eighth,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 8] = [
+        Rank::First,
+        Rank::Second,
+        Rank::Third,
+        Rank::Fourth,
+        Rank::Fifth,
+        Rank::Sixth,
+        Rank::Seventh,
+        Rank::Eighth,
+    ];
+
+    pub fn from_index(index: u8) -> Option<Rank> {
+        Rank::ALL.get(index as usize).copied()
+    }
+
+    // 1 to 8, as written in notation
+    pub fn from_number(rank: u8) -> Option<Rank> {
+        Rank::from_index(rank.checked_sub(1)?)
+    }
+
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+
+    pub fn number(self) -> u8 {
+        self.index() + 1
+    }
+}
+
+// a square on the board, indexed from a1 = 0 to h8 = 63 as Polyglot and the tablebase
+// count, unlike `Pos::matrix_index`. unlike `Pos` it can't be constructed off the board
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Square(u8);
+
+impl Square {
+    pub fn new(file: File, rank: Rank) -> Square {
+        Square(rank.index() * 8 + file.index())
+    }
+
+    pub fn from_index(index: u8) -> Option<Square> {
+        (index < 64).then_some(Square(index))
+    }
+
+    pub fn all() -> impl Iterator<Item = Square> {
+        (0..64).map(Square)
+    }
+
+    pub fn index(self) -> u8 {
+        self.0
+    }
+
+    pub fn file(self) -> File {
+        File::ALL[(self.0 % 8) as usize]
+    }
+
+    pub fn rank(self) -> Rank {
+        Rank::ALL[(self.0 / 8) as usize]
+    }
+
+    pub fn offset(self, d_file: i32, d_rank: i32) -> Option<Square> {
+        let file = u8::try_from(self.file().index() as i32 + d_file).ok()?;
+        let rank = u8::try_from(self.rank().index() as i32 + d_rank).ok()?;
+        Some(Square::new(
+            File::from_index(file)?,
+            Rank::from_index(rank)?,
+        ))
+    }
+}
+
+impl FromStr for Square {
+    type Err = PosErr;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let (file, rank) = match (chars.next(), chars.next(), chars.next()) {
+            (Some(file), Some(rank), None) => (file, rank),
+            _ => return Err(PosErr::ParseError),
+        };
+        if !file.is_ascii_alphabetic() || !rank.is_ascii_digit() {
+            return Err(PosErr::ParseError);
+        }
+        let file = File::from_char(file).ok_or(PosErr::InvalidPosition)?;
+        let rank = rank
+            .to_digit(10)
+            .and_then(|rank| Rank::from_number(rank as u8))
+            .ok_or(PosErr::InvalidPosition)?;
+        Ok(Square::new(file, rank))
+    }
+}
+
+impl Display for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.file().char(), self.rank().number())
+    }
+}
+
+impl Debug for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl From<Square> for Pos {
+    fn from(square: Square) -> Self {
+        Pos(square.file().char(), square.rank().number())
+    }
+}
+
+impl Square {
+    // `None` for a `Pos` off the board
+    pub fn from_pos(pos: Pos) -> Option<Square> {
+        Square::try_from(pos).ok()
+    }
+}
+
+impl TryFrom<Pos> for Square {
+    type Error = GameError;
+    fn try_from(pos: Pos) -> Result<Self, Self::Error> {
+        let invalid = || GameError::InvalidPosition(pos.to_string());
+        // `File::from_char` takes upper case too, a `Pos` doesn't
+        if !pos.file().is_ascii_lowercase() {
+            return Err(invalid());
+        }
+        let file = File::from_char(pos.file()).ok_or_else(invalid)?;
+        let rank = Rank::from_number(pos.rank()).ok_or_else(invalid)?;
+        Ok(Square::new(file, rank))
+    }
+}
+
+impl TryFrom<&str> for Square {
+    type Error = GameError;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+            .map_err(|_| GameError::InvalidPosition(s.to_owned()))
+    }
+}
+
+#[test]
+fn square_test() {
+    let square: Square = "e4".parse().unwrap();
+    assert_eq!(square, Square::new(File::E, Rank::Fourth));
+    assert_eq!(square.index(), 28);
+    assert_eq!(square.to_string(), "e4");
+    assert_eq!(Pos::from(square), Pos('e', 4));
+    // the two numberings, a1 = 0 for squares and a8 = 0 for the board matrix
+    assert_eq!(Square::from_pos(Pos('a', 8)).map(Square::index), Some(56));
+    assert_eq!(Pos('a', 8).matrix_index(), 0);
+    assert_eq!(Pos::from_matrix_index(56), Pos('a', 1));
+    assert_eq!(
+        Square::try_from(Pos('h', 8)),
+        Ok(Square::from_index(63).unwrap())
+    );
+    assert_eq!(
+        Square::try_from(Pos('z', 100)),
+        Err(GameError::InvalidPosition("z100".into()))
+    );
+    assert_eq!(Square::try_from("e4"), Ok(square));
+    assert_eq!(Pos::try_from((5, 4)), Ok(Pos('e', 4)));
+    assert_eq!(
+        Pos::try_from((0, 4)),
+        Err(GameError::InvalidPosition("(0, 4)".into()))
+    );
+    assert_eq!(Pos::from_matrix_index(63), Pos('h', 1));
+    assert_eq!("E4".parse::<Square>(), Ok(square));
+
+    assert_eq!("".parse::<Square>(), Err(PosErr::ParseError));
+    assert_eq!("a".parse::<Square>(), Err(PosErr::ParseError));
+    assert_eq!("e44".parse::<Square>(), Err(PosErr::ParseError));
+    assert_eq!("4e".parse::<Square>(), Err(PosErr::ParseError));
+    assert_eq!("z1".parse::<Square>(), Err(PosErr::InvalidPosition));
+    assert_eq!("a9".parse::<Square>(), Err(PosErr::InvalidPosition));
+    assert_eq!(Square::from_index(64), None);
+
+    assert_eq!(square.offset(1, 2), "f6".parse().ok());
+    assert_eq!(square.offset(4, 0), None);
+    assert_eq!(Square::all().count(), 64);
+
+    // parsing a `Pos` doesn't panic anymore
+    assert_eq!(
+        Pos::try_from(""),
+        Err(GameError::InvalidPosition("".into()))
+    );
+    assert_eq!(
+        Pos::try_from("a"),
+        Err(GameError::InvalidPosition("a".into()))
+    );
+    assert_eq!(
+        Pos::try_from("é4"),
+        Err(GameError::InvalidPosition("é4".into()))
+    );
+}
@@ -0,0 +1,773 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    game::Game,
+    moves::Move,
+    nnue::Evaluator,
+    transposition::{Bound, Entry, TranspositionTable, MATE_SCORE, MAX_PLY},
+};
+
+const INFINITY: i32 = MATE_SCORE + 1;
+
+// as the UCI `Threads` option allows
+pub const MAX_THREADS: usize = 256;
+
+// as the UCI `MultiPV` option allows
+pub const MAX_MULTI_PV: usize = 256;
+
+// the search stops at whichever limit comes first, without any it goes on to `MAX_PLY`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    pub depth: Option<u32>,
+    pub nodes: Option<u64>,
+    pub time: Option<Duration>,
+}
+
+impl Limits {
+    pub fn depth(depth: u32) -> Limits {
+        Limits {
+            depth: Some(depth),
+            ..Limits::default()
+        }
+    }
+
+    pub fn nodes(nodes: u64) -> Limits {
+        Limits {
+            nodes: Some(nodes),
+            ..Limits::default()
+        }
+    }
+
+    pub fn time(time: Duration) -> Limits {
+        Limits {
+            time: Some(time),
+            ..Limits::default()
+        }
+    }
+}
+
+// how a background search treats its limits, `Infinite` and `Ponder` search until told
+// to stop and hold the result till then, `ponderhit` turns a `Ponder` search into a
+// `Normal` one, its limits counting from then
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Normal,
+    Infinite,
+    Ponder,
+}
+
+// what the deepest finished iteration found, the score is for side to move
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchResult {
+    pub best_move: Option<Move>,
+    pub score: i32,
+    pub depth: u32,
+    pub nodes: u64,
+    // principal variation, starting with the best move
+    pub pv: Vec<Move>,
+    // best first, as many as `set_multi_pv` asks for or the position has moves
+    pub lines: Vec<Line>,
+}
+
+// a root move with its score and the principal variation starting with it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    pub mv: Move,
+    pub score: i32,
+    pub pv: Vec<Move>,
+}
+
+impl SearchResult {
+    // an `info` line per line of the result, as a UCI engine reports them
+    pub fn uci_info(&self, time: Duration) -> Vec<String> {
+        let millis = time.as_millis() as u64;
+        let nps = self.nodes * 1000 / millis.max(1);
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let pv: Vec<String> = line.pv.iter().map(Move::to_uci).collect();
+                format!(
+                    "info depth {} multipv {} score {} nodes {} nps {nps} time {millis} pv {}",
+                    self.depth,
+                    index + 1,
+                    uci_score(line.score),
+                    self.nodes,
+                    pv.join(" ")
+                )
+            })
+            .collect()
+    }
+}
+
+// `cp` for centipawns, `mate` in moves once the score is a mate, negative when mated
+pub fn uci_score(score: i32) -> String {
+    let plies = MATE_SCORE - score.abs();
+    if plies > MAX_PLY {
+        return format!("cp {score}");
+    }
+    match score {
+        0.. => format!("mate {}", (plies + 1) / 2),
+        _ => format!("mate -{}", plies / 2),
+    }
+}
+
+// alpha-beta with iterative deepening, a transposition table and a capture search at the
+// leaves, scores in centipawns or `MATE_SCORE` less the plies to mate
+pub struct Search {
+    evaluator: Evaluator,
+    table: TranspositionTable,
+    threads: usize,
+    multi_pv: usize,
+}
+
+// what the threads of one `go` share besides the table
+struct Shared {
+    start: Instant,
+    stop: AtomicBool,
+    nodes: AtomicU64,
+    // limits don't apply until `ponderhit`
+    waiting: AtomicBool,
+    // microseconds from `start` to `ponderhit`, when the limits start counting
+    ponderhit: AtomicU64,
+}
+
+impl Shared {
+    fn new(mode: Mode) -> Shared {
+        Shared {
+            start: Instant::now(),
+            stop: AtomicBool::new(false),
+            nodes: AtomicU64::new(0),
+            waiting: AtomicBool::new(mode != Mode::Normal),
+            ponderhit: AtomicU64::new(0),
+        }
+    }
+
+    fn waiting(&self) -> bool {
+        self.waiting.load(Ordering::Acquire)
+    }
+
+    fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    // time since the limits started counting
+    fn limited_for(&self) -> Duration {
+        let ponderhit = Duration::from_micros(self.ponderhit.load(Ordering::Relaxed));
+        self.start.elapsed().saturating_sub(ponderhit)
+    }
+}
+
+// a search on a thread of its own, stop it or let it finish and `wait` for its result
+pub struct SearchHandle {
+    shared: Arc<Shared>,
+    thread: JoinHandle<SearchResult>,
+}
+
+impl SearchHandle {
+    // as on `stop`, the deepest finished iteration stands
+    pub fn stop(&self) {
+        self.shared.stop.store(true, Ordering::Relaxed);
+    }
+
+    // as on `ponderhit`, the limits count from now and the search returns once they're
+    // reached
+    pub fn ponderhit(&self) {
+        let elapsed = self.shared.start.elapsed().as_micros() as u64;
+        self.shared.ponderhit.store(elapsed, Ordering::Relaxed);
+        self.shared.waiting.store(false, Ordering::Release);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    pub fn wait(self) -> SearchResult {
+        self.thread
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    }
+}
+
+impl Search {
+    pub fn new(evaluator: Evaluator) -> Search {
+        Search {
+            evaluator,
+            table: TranspositionTable::default(),
+            threads: 1,
+            multi_pv: 1,
+        }
+    }
+
+    // as the UCI `Threads` option sets it, clamped to 1..=MAX_THREADS, helper threads
+    // search the same position through the shared table, one thread alone gives the same
+    // result every time
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.clamp(1, MAX_THREADS);
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    // as the UCI `MultiPV` option sets it, clamped to 1..=MAX_MULTI_PV, each iteration
+    // searches the root again without the moves of the lines found before
+    pub fn set_multi_pv(&mut self, lines: usize) {
+        self.multi_pv = lines.clamp(1, MAX_MULTI_PV);
+    }
+
+    pub fn multi_pv(&self) -> usize {
+        self.multi_pv
+    }
+
+    // as the UCI `Hash` option sets it
+    pub fn set_hash(&mut self, size_mb: usize) {
+        self.table.resize(size_mb);
+    }
+
+    // forgets earlier searches, ie. on `ucinewgame`
+    pub fn clear(&mut self) {
+        self.table.clear();
+    }
+
+    // the main thread's result, helpers only fill the table for it and stop with it, the
+    // node limit counts the nodes of all threads
+    pub fn go(&self, game: &Game, limits: Limits) -> SearchResult {
+        self.run(game, limits, &Shared::new(Mode::Normal), &mut |_, _| {})
+    }
+
+    // `go` on a thread of its own, `progress` gets every finished iteration with the
+    // nodes of all threads and the time since the start, ie. for UCI `info` lines
+    pub fn spawn(
+        self: &Arc<Self>,
+        game: Game,
+        limits: Limits,
+        mode: Mode,
+        mut progress: impl FnMut(&SearchResult, Duration) + Send + 'static,
+    ) -> SearchHandle {
+        let shared = Arc::new(Shared::new(mode));
+        let search = Arc::clone(self);
+        let thread = {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || search.run(&game, limits, &shared, &mut progress))
+        };
+        SearchHandle { shared, thread }
+    }
+
+    fn run(
+        &self,
+        game: &Game,
+        limits: Limits,
+        shared: &Shared,
+        progress: &mut dyn FnMut(&SearchResult, Duration),
+    ) -> SearchResult {
+        self.table.new_search();
+        let mut result = thread::scope(|scope| {
+            for id in 1..self.threads {
+                scope.spawn(move || self.iterate(game, limits, id, shared, &mut |_, _| {}));
+            }
+            let result = self.iterate(game, limits, 0, shared, progress);
+            // an infinite or pondering search holds its result until it's told
+            while shared.waiting() && !shared.stopped() {
+                thread::sleep(Duration::from_millis(1));
+            }
+            shared.stop.store(true, Ordering::Relaxed);
+            result
+        });
+        if result.best_move.is_none() && terminal_score(game, 0).is_none() {
+            result.best_move = game.all_legal_moves().first().copied();
+        }
+        result.nodes = shared.nodes.load(Ordering::Relaxed);
+        result
+    }
+
+    pub fn best_move(&self, game: &Game, limits: Limits) -> Option<Move> {
+        self.go(game, limits).best_move
+    }
+
+    // iterative deepening of thread `id`, odd helpers start a ply deeper so the threads
+    // don't all search the same depth at once
+    fn iterate(
+        &self,
+        game: &Game,
+        limits: Limits,
+        id: usize,
+        shared: &Shared,
+        progress: &mut dyn FnMut(&SearchResult, Duration),
+    ) -> SearchResult {
+        let mut worker = Worker {
+            search: self,
+            limits,
+            shared,
+            nodes: 0,
+            stopped: false,
+            excluded: Vec::new(),
+        };
+        // helpers only fill the table
+        let multi_pv = if id == 0 { self.multi_pv } else { 1 };
+        let max_depth = limits
+            .depth
+            .unwrap_or(MAX_PLY as u32)
+            .min(MAX_PLY as u32 - 1);
+        let mut result = SearchResult::default();
+        for depth in (1 + id as u32 % 2).min(max_depth.max(1))..MAX_PLY as u32 {
+            // a waiting search goes on past the depth limit
+            if depth > max_depth && !shared.waiting() {
+                break;
+            }
+            let mut lines: Vec<Line> = Vec::new();
+            let mut score = 0;
+            for index in 0..multi_pv {
+                worker.excluded = lines.iter().map(|line| line.mv).collect();
+                let mut pv = Vec::new();
+                let line_score =
+                    worker.negamax(game, depth as i32, 0, -INFINITY, INFINITY, &mut pv);
+                if index == 0 {
+                    score = line_score;
+                }
+                if let Some(&mv) = pv.first() {
+                    lines.push(Line {
+                        mv,
+                        score: line_score,
+                        pv,
+                    });
+                }
+                if worker.stopped || lines.len() <= index {
+                    break;
+                }
+            }
+            // a cut off iteration doesn't count once an earlier one finished
+            if worker.stopped && result.best_move.is_some() {
+                break;
+            }
+            lines.sort_by_key(|line| -line.score);
+            let best = lines.first();
+            result = SearchResult {
+                best_move: best.map(|line| line.mv),
+                score: best.map_or(score, |line| line.score),
+                depth,
+                nodes: worker.nodes,
+                pv: best.map_or(Vec::new(), |line| line.pv.clone()),
+                lines,
+            };
+            if !worker.stopped {
+                let report = SearchResult {
+                    nodes: shared.nodes.load(Ordering::Relaxed),
+                    ..result.clone()
+                };
+                progress(&report, shared.start.elapsed());
+            }
+            if worker.stopped || result.best_move.is_none() {
+                break;
+            }
+        }
+        result
+    }
+}
+
+struct Worker<'a> {
+    search: &'a Search,
+    limits: Limits,
+    shared: &'a Shared,
+    // of this thread
+    nodes: u64,
+    stopped: bool,
+    // root moves of the lines already found this iteration
+    excluded: Vec<Move>,
+}
+
+impl Worker<'_> {
+    fn out_of_limits(&mut self) -> bool {
+        if !self.stopped && !self.shared.waiting() {
+            let searched = self.shared.nodes.load(Ordering::Relaxed);
+            let nodes = self.limits.nodes.is_some_and(|nodes| searched >= nodes);
+            let time = self
+                .limits
+                .time
+                .is_some_and(|time| self.shared.limited_for() >= time);
+            self.stopped = nodes || time;
+        }
+        self.stopped = self.stopped || self.shared.stopped();
+        self.stopped
+    }
+
+    fn count_node(&mut self) {
+        self.nodes += 1;
+        self.shared.nodes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // score of `game` for side to move, `pv` gets the line leading to it
+    fn negamax(
+        &mut self,
+        game: &Game,
+        depth: i32,
+        ply: i32,
+        mut alpha: i32,
+        beta: i32,
+        pv: &mut Vec<Move>,
+    ) -> i32 {
+        pv.clear();
+        if let Some(score) = terminal_score(game, ply) {
+            return score;
+        }
+        if depth <= 0 || ply >= MAX_PLY - 1 {
+            return self.quiescence(game, ply, alpha, beta);
+        }
+        if self.out_of_limits() {
+            return 0;
+        }
+        self.count_node();
+
+        let key = game.zobrist_key();
+        let entry = self.search.table.probe(key, ply);
+        if let Some(entry) = entry.filter(|entry| ply > 0 && entry.depth as i32 >= depth) {
+            let cut = match entry.bound {
+                Bound::Exact => true,
+                Bound::Lower => entry.score >= beta,
+                Bound::Upper => entry.score <= alpha,
+            };
+            if cut {
+                return entry.score;
+            }
+        }
+
+        let original_alpha = alpha;
+        let mut best = (-INFINITY, None);
+        let mut child_pv = Vec::new();
+        for mv in ordered_moves(game, entry.and_then(|entry| entry.best_move)) {
+            if ply == 0 && self.excluded.contains(&mv) {
+                continue;
+            }
+            let mut child = game.clone();
+            if child.make_move(mv).is_err() {
+                continue;
+            }
+            let score = -self.negamax(&child, depth - 1, ply + 1, -beta, -alpha, &mut child_pv);
+            if self.stopped {
+                return 0;
+            }
+            if score > best.0 {
+                best = (score, Some(mv));
+                if score > alpha {
+                    alpha = score;
+                    pv.clear();
+                    pv.push(mv);
+                    pv.extend(&child_pv);
+                }
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+        let bound = if best.0 >= beta {
+            Bound::Lower
+        } else if best.0 > original_alpha {
+            Bound::Exact
+        } else {
+            Bound::Upper
+        };
+        // a root without some of its moves isn't the position the table knows
+        if ply == 0 && !self.excluded.is_empty() {
+            return best.0;
+        }
+        self.search.table.store(
+            key,
+            ply,
+            Entry {
+                depth: depth as u8,
+                bound,
+                score: best.0,
+                best_move: best.1,
+            },
+        );
+        best.0
+    }
+
+    // captures only until the position is quiet, side to move may stand pat
+    fn quiescence(&mut self, game: &Game, ply: i32, mut alpha: i32, beta: i32) -> i32 {
+        if let Some(score) = terminal_score(game, ply) {
+            return score;
+        }
+        if self.out_of_limits() {
+            return 0;
+        }
+        self.count_node();
+        let stand_pat = self.search.evaluator.evaluate(game);
+        if stand_pat >= beta || ply >= MAX_PLY - 1 {
+            return stand_pat;
+        }
+        alpha = alpha.max(stand_pat);
+        let mut captures: Vec<(i32, Move)> = game
+            .all_legal_moves()
+            .into_iter()
+            .filter(|&mv| game.is_capture(mv))
+            .map(|mv| (game.see(mv), mv))
+            .filter(|&(see, _)| see >= 0)
+            .collect();
+        captures.sort_by_key(|&(see, _)| -see);
+        for (_, mv) in captures {
+            let mut child = game.clone();
+            if child.make_move(mv).is_err() {
+                continue;
+            }
+            let score = -self.quiescence(&child, ply + 1, -beta, -alpha);
+            if self.stopped {
+                return 0;
+            }
+            if score >= beta {
+                return score;
+            }
+            alpha = alpha.max(score);
+        }
+        alpha
+    }
+}
+
+// the score of a finished game or a draw by the fifty move rule or repetition, a game
+// set up from FEN only knows it's over once the variant is asked
+fn terminal_score(game: &Game, ply: i32) -> Option<i32> {
+    let outcome = match ply {
+        0 => game.variant().outcome(game),
+        _ => game.outcome(),
+    };
+    if let Some(outcome) = outcome {
+        // in antichess the side to move can be the winner
+        return Some(match outcome.winner {
+            Some(side) if side == game.whose_turn() => MATE_SCORE - ply,
+            Some(_) => -(MATE_SCORE - ply),
+            None => 0,
+        });
+    }
+    if ply == 0 {
+        return None;
+    }
+    if game.halfmove_clock >= 100 {
+        return Some(0);
+    }
+    let keys = game.position_keys();
+    let (current, earlier) = keys.split_last()?;
+    earlier
+        .iter()
+        .rev()
+        .take(game.halfmove_clock as usize)
+        .any(|key| key == current)
+        .then_some(0)
+}
+
+// the table's move first, then captures and promotions by the material they win, quiet
+// moves and at last the losing captures
+fn ordered_moves(game: &Game, hash_move: Option<Move>) -> Vec<Move> {
+    let mut moves = game.all_legal_moves();
+    moves.sort_by_cached_key(|&mv| {
+        if Some(mv) == hash_move {
+            return i32::MIN;
+        }
+        let promotion = mv.promotion().map_or(0, |c| c.value() - 100);
+        if !game.is_capture(mv) && promotion == 0 {
+            return 0;
+        }
+        match game.see(mv) + promotion {
+            gain if gain >= 0 => -1_000 - gain,
+            loss => -loss,
+        }
+    });
+    moves
+}
+
+// leaf nodes of the move tree `depth` plies deep, for checking move generation
+pub fn perft(game: &Game, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    let moves = game.all_legal_moves();
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+    moves
+        .into_iter()
+        .map(|mv| {
+            let mut child = game.clone();
+            child.make_move(mv).map_or(0, |_| perft(&child, depth - 1))
+        })
+        .sum()
+}
+
+#[test]
+fn search_test() {
+    use crate::{
+        pieces::{Character, Side},
+        position::Pos,
+        tuning::Weights,
+    };
+
+    // castling, en passant and promotions, counts as published for these positions
+    let start = Game::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    assert_eq!(perft(&start.unwrap(), 3), 8902);
+    let kiwipete =
+        Game::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+    assert_eq!(perft(&kiwipete.unwrap(), 2), 2039);
+    let endgame = Game::from_fen("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1");
+    assert_eq!(perft(&endgame.unwrap(), 3), 2812);
+    let promotions =
+        Game::from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1");
+    assert_eq!(perft(&promotions.unwrap(), 2), 264);
+
+    let search = Search::new(Evaluator::Classical(Weights::default()));
+    let game = Game::from_fen("7k/8/6K1/8/8/8/Q7/8 w - - 0 1").unwrap();
+    let result = search.go(&game, Limits::depth(2));
+    let qa8 = Move::Normal {
+        from: Pos('a', 2),
+        to: Pos('a', 8),
+        promotion: None,
+    };
+    assert_eq!(result.best_move, Some(qa8));
+    assert_eq!(result.pv, [qa8]);
+    assert_eq!(result.score, MATE_SCORE - 1);
+
+    // the hanging rook is taken, and the search keeps to its node budget
+    let game = Game::from_fen("4k3/8/8/3r4/8/8/3Q4/4K3 w - - 0 1").unwrap();
+    let result = search.go(&game, Limits::nodes(2000));
+    assert_eq!(
+        result.best_move,
+        Some(Move::Normal {
+            from: Pos('d', 2),
+            to: Pos('d', 5),
+            promotion: None,
+        })
+    );
+    assert!(result.nodes <= 2000);
+
+    // promoting to a knight mates at once
+    let game = Game::from_fen("6nb/5Ppk/6pp/8/8/8/8/K7 w - - 0 1").unwrap();
+    let result = search.go(&game, Limits::depth(1));
+    assert_eq!(
+        result.best_move.and_then(|mv| mv.promotion()),
+        Some(Character::Knight(Side::White))
+    );
+
+    // nothing to play once the game is over, set up from FEN or played
+    let mate = Game::from_fen("Q6k/8/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert_eq!(search.best_move(&mate, Limits::depth(3)), None);
+    let mut mated = Game::from_fen("7k/8/6K1/8/8/8/Q7/8 w - - 0 1").unwrap();
+    mated.make_move(qa8).unwrap();
+    assert_eq!(search.go(&mated, Limits::depth(3)).score, -MATE_SCORE);
+
+    // one thread searches the same tree every time
+    let mut search = Search::new(Evaluator::Classical(Weights::default()));
+    let italian =
+        Game::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3")
+            .unwrap();
+    let first = search.go(&italian, Limits::depth(3));
+    search.clear();
+    assert_eq!(search.go(&italian, Limits::depth(3)), first);
+
+    // helpers share the table, the main thread's answer stands and the node limit
+    // holds for all threads together, but for the nodes under way as it's reached
+    search.set_threads(4);
+    assert_eq!(search.threads(), 4);
+    let queen = Game::from_fen("7k/8/6K1/8/8/8/Q7/8 w - - 0 1").unwrap();
+    let result = search.go(&queen, Limits::depth(2));
+    assert_eq!(result.best_move, Some(qa8));
+    assert_eq!(result.score, MATE_SCORE - 1);
+    let rook = Game::from_fen("4k3/8/8/3r4/8/8/3Q4/4K3 w - - 0 1").unwrap();
+    let result = search.go(&rook, Limits::nodes(2000));
+    let rxd5 = Move::Normal {
+        from: Pos('d', 2),
+        to: Pos('d', 5),
+        promotion: None,
+    };
+    assert_eq!(result.best_move, Some(rxd5));
+    assert!(result.nodes <= 2000 + 4);
+    assert_eq!(search.go(&italian, Limits::depth(2)).depth, 2);
+    search.set_threads(0);
+    assert_eq!(search.threads(), 1);
+
+    // the lines come best first with distinct moves, no more than the position has
+    search.set_multi_pv(3);
+    let result = search.go(&queen, Limits::depth(2));
+    assert_eq!(result.lines.len(), 3);
+    assert_eq!(result.lines[0].mv, qa8);
+    assert_eq!(result.lines[0].pv, result.pv);
+    assert_eq!(result.score, MATE_SCORE - 1);
+    assert!(result.lines.windows(2).all(|w| w[0].score >= w[1].score));
+    assert!(result.lines.iter().all(|line| line.pv[0] == line.mv));
+    assert_ne!(result.lines[1].mv, result.lines[2].mv);
+    assert!(result.lines[1].score < MATE_SCORE - 1);
+    let info = result.uci_info(Duration::from_millis(10));
+    assert_eq!(info.len(), 3);
+    assert_eq!(
+        info[0],
+        format!(
+            "info depth 2 multipv 1 score mate 1 nodes {} nps {} time 10 pv a2a8",
+            result.nodes,
+            result.nodes * 100
+        )
+    );
+    assert!(info[2].starts_with("info depth 2 multipv 3 score "));
+    search.set_multi_pv(5);
+    let king = Game::from_fen("7k/8/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    let result = search.go(&king, Limits::depth(1));
+    assert_eq!(result.lines.len(), 3);
+    assert!(result.lines.iter().all(|line| line.score == 0));
+    assert_eq!(search.go(&mated, Limits::depth(2)).lines, []);
+    search.set_multi_pv(0);
+    assert_eq!(search.multi_pv(), 1);
+    assert_eq!(uci_score(-35), "cp -35");
+    assert_eq!(uci_score(MATE_SCORE - 3), "mate 2");
+    assert_eq!(uci_score(-(MATE_SCORE - 2)), "mate -1");
+
+    // on a thread of its own, reporting every iteration, the same as `go`
+    let mut search = Arc::new(Search::new(Evaluator::Classical(Weights::default())));
+    let (sender, reports) = std::sync::mpsc::channel();
+    let progress = move |result: &SearchResult, _| {
+        let _ = sender.send(result.clone());
+    };
+    let handle = search.spawn(italian.clone(), Limits::depth(3), Mode::Normal, progress);
+    let result = handle.wait();
+    let reports: Vec<SearchResult> = reports.iter().collect();
+    assert_eq!(
+        reports.iter().map(|r| r.depth).collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+    assert_eq!(reports[2], result);
+    // a finished search lets go of it
+    Arc::get_mut(&mut search).unwrap().clear();
+    assert_eq!(search.go(&italian, Limits::depth(3)), result);
+
+    // infinite analysis holds its result past the depth limit until it's stopped
+    let (sender, reports) = std::sync::mpsc::channel();
+    let progress = move |result: &SearchResult, _| {
+        let _ = sender.send(result.depth);
+    };
+    let handle = search.spawn(queen.clone(), Limits::depth(1), Mode::Infinite, progress);
+    assert_eq!(reports.recv(), Ok(1));
+    assert_eq!(reports.recv(), Ok(2));
+    assert!(!handle.is_finished());
+    handle.stop();
+    let result = handle.wait();
+    assert!(result.depth >= 2);
+    assert_eq!(result.best_move, Some(qa8));
+
+    // pondering doesn't stop at its limits until `ponderhit`
+    let handle = search.spawn(queen.clone(), Limits::depth(1), Mode::Ponder, |_, _| {});
+    thread::sleep(Duration::from_millis(20));
+    assert!(!handle.is_finished());
+    handle.ponderhit();
+    assert_eq!(handle.wait().best_move, Some(qa8));
+    let limits = Limits::time(Duration::from_millis(50));
+    let handle = search.spawn(italian.clone(), limits, Mode::Ponder, |_, _| {});
+    thread::sleep(Duration::from_millis(100));
+    assert!(!handle.is_finished());
+    let hit = Instant::now();
+    handle.ponderhit();
+    assert!(handle.wait().best_move.is_some());
+    assert!(hit.elapsed() >= Duration::from_millis(50));
+}
@@ -0,0 +1,439 @@
+use crate::{
+    epd::Epd, errors::GameError, game::Game, moves::Move, polyglot::Rng, position::Square,
+    tournament::Adjudication,
+};
+
+// bytes of a sample in the binary format of `Sample::to_bytes`
+pub const SAMPLE_SIZE: usize = 34;
+
+// nibble of a piece is its index here, plus 8 for black
+const PIECES: &str = "pnbrqk";
+const CASTLING: &str = "KQkq";
+
+// a position of a self-play game with what the engine made of it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sample {
+    pub fen: String,
+    // centipawns for side to move
+    pub score: i32,
+    // in UCI notation, ie. `e7e8q` or `N@f3`
+    pub best_move: String,
+    // for side to move, 1 won, 0 drawn and -1 lost
+    pub result: i8,
+}
+
+impl Sample {
+    // `fen | score | move | result`, one sample per line
+    pub fn to_line(&self) -> String {
+        format!(
+            "{} | {} | {} | {}",
+            self.fen, self.score, self.best_move, self.result
+        )
+    }
+
+    pub fn from_line(line: &str) -> Result<Sample, GameError> {
+        let invalid = || GameError::InvalidNotation(line.to_owned());
+        let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+        let [fen, score, best_move, result] = fields[..] else {
+            return Err(invalid());
+        };
+        Ok(Sample {
+            fen: fen.to_owned(),
+            score: score.parse().map_err(|_| invalid())?,
+            best_move: best_move.to_owned(),
+            result: result.parse().map_err(|_| invalid())?,
+        })
+    }
+
+    // little endian, the occupied squares as a u64 with a1 the lowest bit, their pieces a
+    // nibble each in the same order, castling rights with bit 4 set for black to move,
+    // the en passant square or 64, halfmove clock, u16 fullmove number, i16 score, the
+    // result plus one and the move as u16 `to | from << 6 | promotion << 12`, promotion 1
+    // to 4 for n, b, r and q, drops, promoted pieces and more than 32 pieces don't fit
+    pub fn to_bytes(&self) -> Result<[u8; SAMPLE_SIZE], GameError> {
+        let invalid = || GameError::InvalidNotation(self.fen.clone());
+        let fields: Vec<&str> = self.fen.split_whitespace().collect();
+        let [board, side, castling, en_passant, halfmove, fullmove] = fields[..] else {
+            return Err(invalid());
+        };
+        let mut squares = [None; 64];
+        let ranks: Vec<&str> = board.split('/').collect();
+        if ranks.len() != 8 {
+            return Err(invalid());
+        }
+        for (row, rank) in ranks.iter().enumerate() {
+            let mut file = 0;
+            for c in rank.chars() {
+                if let Some(empty) = c.to_digit(10) {
+                    file += empty as usize;
+                    continue;
+                }
+                let piece = PIECES.find(c.to_ascii_lowercase()).ok_or_else(invalid)?;
+                let black = if c.is_ascii_lowercase() { 8 } else { 0 };
+                *squares.get_mut((7 - row) * 8 + file).ok_or_else(invalid)? =
+                    Some(piece as u8 | black);
+                file += 1;
+            }
+            if file != 8 {
+                return Err(invalid());
+            }
+        }
+
+        let mut bytes = [0; SAMPLE_SIZE];
+        let mut occupancy = 0u64;
+        for (index, nibble) in squares
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| Some((i, (*s)?)))
+        {
+            let count = occupancy.count_ones() as usize;
+            if count == 32 {
+                return Err(invalid());
+            }
+            occupancy |= 1 << index;
+            bytes[8 + count / 2] |= nibble << (count % 2 * 4);
+        }
+        bytes[..8].copy_from_slice(&occupancy.to_le_bytes());
+        for c in castling.chars().filter(|&c| c != '-') {
+            bytes[24] |= 1 << CASTLING.find(c).ok_or_else(invalid)?;
+        }
+        bytes[24] |= match side {
+            "w" => 0,
+            "b" => 1 << 4,
+            _ => return Err(invalid()),
+        };
+        bytes[25] = match en_passant {
+            "-" => 64,
+            square => square.parse::<Square>().map_err(|_| invalid())?.index(),
+        };
+        bytes[26] = halfmove.parse().map_err(|_| invalid())?;
+        let fullmove: u16 = fullmove.parse().map_err(|_| invalid())?;
+        bytes[27..29].copy_from_slice(&fullmove.to_le_bytes());
+        // mate scores are within 30000
+        let score = self.score.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+        bytes[29..31].copy_from_slice(&score.to_le_bytes());
+        bytes[31] = u8::try_from(self.result + 1).map_err(|_| invalid())?;
+        bytes[32..].copy_from_slice(&encode_move(&self.best_move)?.to_le_bytes());
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8; SAMPLE_SIZE]) -> Result<Sample, GameError> {
+        let invalid = || GameError::InvalidNotation(format!("{bytes:02x?}"));
+        let occupancy = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+        let mut squares = [None; 64];
+        for (count, index) in (0..64).filter(|i| occupancy >> i & 1 == 1).enumerate() {
+            let nibble = bytes[8 + count / 2] >> (count % 2 * 4) & 0xf;
+            let piece = PIECES
+                .chars()
+                .nth(nibble as usize & 7)
+                .ok_or_else(invalid)?;
+            squares[index] = Some(if nibble & 8 == 8 {
+                piece
+            } else {
+                piece.to_ascii_uppercase()
+            });
+        }
+
+        let mut board = String::new();
+        for rank in (0..8).rev() {
+            let mut empty = 0;
+            for file in 0..8 {
+                match squares[rank * 8 + file] {
+                    Some(piece) => {
+                        if empty > 0 {
+                            board.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        board.push(piece);
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                board.push_str(&empty.to_string());
+            }
+            if rank > 0 {
+                board.push('/');
+            }
+        }
+        let side = if bytes[24] & 1 << 4 == 0 { "w" } else { "b" };
+        let castling: String = CASTLING
+            .chars()
+            .enumerate()
+            .filter(|(i, _)| bytes[24] >> i & 1 == 1)
+            .map(|(_, c)| c)
+            .collect();
+        let castling = if castling.is_empty() {
+            "-".to_owned()
+        } else {
+            castling
+        };
+        let en_passant = match bytes[25] {
+            64 => "-".to_owned(),
+            index => Square::from_index(index).ok_or_else(invalid)?.to_string(),
+        };
+        let fullmove = u16::from_le_bytes([bytes[27], bytes[28]]);
+        Ok(Sample {
+            fen: format!(
+                "{board} {side} {castling} {en_passant} {} {fullmove}",
+                bytes[26]
+            ),
+            score: i16::from_le_bytes([bytes[29], bytes[30]]) as i32,
+            best_move: decode_move(u16::from_le_bytes([bytes[32], bytes[33]]))
+                .ok_or_else(invalid)?,
+            result: bytes[31] as i8 - 1,
+        })
+    }
+}
+
+// the samples of a file written with `Sample::to_bytes`, one after another
+pub fn read_samples(bytes: &[u8]) -> Result<Vec<Sample>, GameError> {
+    let chunks = bytes.chunks_exact(SAMPLE_SIZE);
+    if !chunks.remainder().is_empty() {
+        return Err(GameError::InvalidNotation(format!(
+            "{} bytes left over",
+            chunks.remainder().len()
+        )));
+    }
+    chunks
+        .map(|chunk| Sample::from_bytes(chunk.try_into().unwrap()))
+        .collect()
+}
+
+fn encode_move(uci: &str) -> Result<u16, GameError> {
+    let invalid = || GameError::InvalidNotation(uci.to_owned());
+    let square = |range| {
+        uci.get(range)
+            .and_then(|name: &str| name.parse::<Square>().ok())
+            .ok_or_else(invalid)
+    };
+    let (from, to) = (square(0..2)?, square(2..4)?);
+    let promotion = match uci.get(4..) {
+        Some("") => 0,
+        Some(letter) if letter.len() == 1 => "nbrq".find(letter).ok_or_else(invalid)? + 1,
+        _ => return Err(invalid()),
+    };
+    Ok(to.index() as u16 | (from.index() as u16) << 6 | (promotion as u16) << 12)
+}
+
+fn decode_move(mv: u16) -> Option<String> {
+    let to = Square::from_index((mv & 63) as u8)?;
+    let from = Square::from_index((mv >> 6 & 63) as u8)?;
+    let promotion = match mv >> 12 {
+        0 => String::new(),
+        n => "nbrq".chars().nth(n as usize - 1)?.to_string(),
+    };
+    Some(format!("{from}{to}{promotion}"))
+}
+
+// games start from a random opening of the book, or the initial position without one,
+// followed by `random_plies` random moves so that no two games are alike
+pub struct SelfPlay {
+    pub openings: Vec<Epd>,
+    pub random_plies: usize,
+    pub adjudication: Adjudication,
+    rng: Rng,
+}
+
+impl SelfPlay {
+    pub fn new(openings: Vec<Epd>, random_plies: usize, seed: u64) -> SelfPlay {
+        SelfPlay {
+            openings,
+            random_plies,
+            adjudication: Adjudication::default(),
+            rng: Rng::new(seed),
+        }
+    }
+
+    // plays a game with `search`, ie. the engine at fixed nodes or depth, answering the
+    // best move and its score for side to move, empty when the random moves already
+    // ended the game
+    pub fn play_game(
+        &mut self,
+        mut search: impl FnMut(&Game) -> Option<(Move, i32)>,
+    ) -> Result<Vec<Sample>, GameError> {
+        let mut game = if self.openings.is_empty() {
+            let mut game = Game::new();
+            game.start();
+            game
+        } else {
+            let index = self.rng.below(self.openings.len() as u64) as usize;
+            self.openings[index].game()?
+        };
+        for _ in 0..self.random_plies {
+            let moves = game.all_legal_moves();
+            if moves.is_empty() || game.is_game_over() {
+                return Ok(Vec::new());
+            }
+            let mv = moves[self.rng.below(moves.len() as u64) as usize];
+            game.make_move(mv)?;
+        }
+        if game.is_game_over() {
+            return Ok(Vec::new());
+        }
+
+        let mut samples = Vec::new();
+        let mut sides = Vec::new();
+        let winner = loop {
+            if let Some(outcome) = game.outcome() {
+                break outcome.winner;
+            }
+            if self.adjudication.draw(&game, samples.len()).is_some() {
+                break None;
+            }
+            let side = game.whose_turn();
+            // a search without a move resigns
+            let Some((mv, score)) = search(&game) else {
+                break Some(side.opponent());
+            };
+            samples.push(Sample {
+                // `~` marks promoted pieces for crazyhouse, which trainers don't read
+                fen: game.to_fen().replace('~', ""),
+                score,
+                best_move: mv.to_uci(),
+                result: 0,
+            });
+            sides.push(side);
+            game.make_move(mv)?;
+        };
+        for (sample, side) in samples.iter_mut().zip(sides) {
+            sample.result = match winner {
+                Some(winner) if winner == side => 1,
+                Some(_) => -1,
+                None => 0,
+            };
+        }
+        Ok(samples)
+    }
+}
+
+#[test]
+fn selfplay_test() {
+    use crate::{
+        epd::parse_suite,
+        nnue::Evaluator,
+        search::{Limits, Search},
+        transposition::MATE_SCORE,
+        tuning::Weights,
+    };
+
+    let search = &Search::new(Evaluator::Classical(Weights::default()));
+    let engine = |limits| {
+        move |game: &Game| {
+            let result = search.go(game, limits);
+            Some((result.best_move?, result.score))
+        }
+    };
+    let sample = |fen: &str, score, best_move: &str, result| Sample {
+        fen: fen.to_owned(),
+        score,
+        best_move: best_move.to_owned(),
+        result,
+    };
+
+    // the engine at fixed depth mates at once
+    let openings = parse_suite("7k/8/6K1/8/8/8/Q7/8 w - -").unwrap();
+    let mut selfplay = SelfPlay::new(openings, 0, 7);
+    let samples = selfplay.play_game(engine(Limits::depth(2))).unwrap();
+    assert_eq!(
+        samples,
+        [sample(
+            "7k/8/6K1/8/8/8/Q7/8 w - - 0 1",
+            MATE_SCORE - 1,
+            "a2a8",
+            1
+        )]
+    );
+
+    // at fixed nodes it promotes, the new queen's FEN has no crazyhouse `~`
+    let openings = parse_suite("4k3/P7/8/8/8/8/8/4K3 w - -").unwrap();
+    let mut selfplay = SelfPlay::new(openings, 0, 7);
+    selfplay.adjudication.max_plies = Some(4);
+    let samples = selfplay.play_game(engine(Limits::nodes(500))).unwrap();
+    assert_eq!(
+        samples,
+        [
+            sample("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", 900, "a7a8q", 0),
+            sample("Q3k3/8/8/8/8/8/8/4K3 b - - 0 1", -900, "e8f7", 0),
+            sample("Q7/5k2/8/8/8/8/8/4K3 w - - 1 2", 900, "a8b8", 0),
+            sample("1Q6/5k2/8/8/8/8/8/4K3 b - - 2 2", -900, "f7g6", 0),
+        ]
+    );
+    assert_eq!(read_samples(&[]), Ok(Vec::new()));
+    let bytes: Vec<u8> = samples
+        .iter()
+        .flat_map(|sample| sample.to_bytes().unwrap())
+        .collect();
+    assert_eq!(read_samples(&bytes), Ok(samples.clone()));
+    assert!(read_samples(&bytes[1..]).is_err());
+
+    let line = samples[0].to_line();
+    assert_eq!(line, "4k3/P7/8/8/8/8/8/4K3 w - - 0 1 | 900 | a7a8q | 0");
+    assert_eq!(Sample::from_line(&line), Ok(samples[0].clone()));
+    assert!(Sample::from_line("4k3/8/8/8/8/8/8/4K3 w - - 0 1 | 12").is_err());
+
+    // black moves first after three random plies, white resigns at the fourth search
+    let mut selfplay = SelfPlay::new(Vec::new(), 3, 7);
+    let mut plies = 0;
+    let samples = selfplay
+        .play_game(|game| {
+            plies += 1;
+            let mv = *game.all_legal_moves().first()?;
+            (plies < 4).then_some((mv, 10))
+        })
+        .unwrap();
+    assert_eq!(
+        samples,
+        [
+            sample(
+                "r1bqkbnr/pppppppp/2n5/8/3P4/8/PPPKPPPP/RNBQ1BNR b kq - 2 2",
+                10,
+                "a8b8",
+                1
+            ),
+            sample(
+                "1rbqkbnr/pppppppp/2n5/8/3P4/8/PPPKPPPP/RNBQ1BNR w k - 3 3",
+                10,
+                "d4d5",
+                -1
+            ),
+            sample(
+                "1rbqkbnr/pppppppp/2n5/3P4/8/8/PPPKPPPP/RNBQ1BNR b k - 0 3",
+                10,
+                "b8a8",
+                1
+            ),
+        ]
+    );
+
+    // 34 bytes against some 60 for the line
+    let start = sample(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        -35,
+        "e2e4",
+        1,
+    );
+    let bytes = start.to_bytes().unwrap();
+    assert_eq!(
+        bytes,
+        [
+            0xff, 0xff, 0, 0, 0, 0, 0xff, 0xff, 0x13, 0x42, 0x25, 0x31, 0, 0, 0, 0, 0x88, 0x88,
+            0x88, 0x88, 0x9b, 0xca, 0xad, 0xb9, 0x0f, 64, 0, 1, 0, 0xdd, 0xff, 2, 0x1c, 0x03
+        ]
+    );
+    assert_eq!(Sample::from_bytes(&bytes), Ok(start.clone()));
+    let promotion = sample("8/1P4k1/8/8/8/8/6K1/8 b - e3 7 40", 250, "b7b8n", -1);
+    assert_eq!(
+        Sample::from_bytes(&promotion.to_bytes().unwrap()),
+        Ok(promotion)
+    );
+    let drop = sample("8/8/8/8/8/8/8/K6k w - - 0 1", 0, "N@f3", 0);
+    assert!(drop.to_bytes().is_err());
+
+    let knight = Move::Normal {
+        from: crate::position::Pos('a', 7),
+        to: crate::position::Pos('a', 8),
+        promotion: Some(crate::pieces::Character::Knight(crate::pieces::Side::White)),
+    };
+    assert_eq!(knight.to_uci(), "a7a8n");
+}
@@ -0,0 +1,315 @@
+use crate::tournament::{GameRecord, GameResult};
+
+// results of one player, from its point of view
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Score {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Elo {
+    pub difference: f64,
+    // bounds of the 95% confidence interval
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl Score {
+    pub fn of(records: &[GameRecord], player: &str) -> Score {
+        let mut score = Score::default();
+        for record in records {
+            score.add(points(record, player));
+        }
+        score
+    }
+
+    // adds a game worth 0, 0.5 or 1, given in half points
+    fn add(&mut self, half_points: Option<u32>) {
+        match half_points {
+            Some(2) => self.wins += 1,
+            Some(1) => self.draws += 1,
+            Some(_) => self.losses += 1,
+            None => {}
+        }
+    }
+
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    // mean points per game
+    pub fn ratio(&self) -> f64 {
+        (self.wins as f64 + self.draws as f64 / 2.) / self.games().max(1) as f64
+    }
+
+    pub fn draw_ratio(&self) -> f64 {
+        self.draws as f64 / self.games().max(1) as f64
+    }
+
+    // `None` until a game was both won or drawn and lost or drawn, the difference is
+    // unbounded before
+    pub fn elo(&self) -> Option<Elo> {
+        let games = self.games() as f64;
+        let ratio = self.ratio();
+        if !(ratio > 0. && ratio < 1.) {
+            return None;
+        }
+        let variance = (self.wins as f64 * (1. - ratio).powi(2)
+            + self.draws as f64 * (0.5 - ratio).powi(2)
+            + self.losses as f64 * ratio.powi(2))
+            / games;
+        let margin = 1.959964 * (variance / games).sqrt();
+        let bound = |ratio: f64| elo_from_ratio(ratio.clamp(1e-9, 1. - 1e-9));
+        Some(Elo {
+            difference: elo_from_ratio(ratio),
+            lower: bound(ratio - margin),
+            upper: bound(ratio + margin),
+        })
+    }
+
+    // likelihood of superiority, draws tell nothing about which player is stronger
+    pub fn los(&self) -> f64 {
+        let decisive = (self.wins + self.losses) as f64;
+        if decisive == 0. {
+            return 0.5;
+        }
+        0.5 * (1. + erf((self.wins as f64 - self.losses as f64) / (2. * decisive).sqrt()))
+    }
+}
+
+// pairs of games played from the same opening with colours swapped, counted by the points
+// the pair brought, 0, 0.5, 1, 1.5 or 2
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Pentanomial(pub [u32; 5]);
+
+impl Pentanomial {
+    // records in the order `tournament::round_robin` plays them, each pair consecutive
+    pub fn of(records: &[GameRecord], player: &str) -> Pentanomial {
+        let mut pentanomial = Pentanomial::default();
+        for pair in records.chunks_exact(2) {
+            if let (Some(first), Some(second)) =
+                (points(&pair[0], player), points(&pair[1], player))
+            {
+                pentanomial.0[(first + second) as usize] += 1;
+            }
+        }
+        pentanomial
+    }
+
+    pub fn pairs(&self) -> u32 {
+        self.0.iter().sum()
+    }
+
+    // mean points per game
+    pub fn ratio(&self) -> f64 {
+        self.mean_and_variance().0
+    }
+
+    // per game, of the pairs' mean scores
+    fn mean_and_variance(&self) -> (f64, f64) {
+        let pairs = self.pairs().max(1) as f64;
+        let value = |index: usize| index as f64 / 4.;
+        let mean = (0..5).map(|i| self.0[i] as f64 * value(i)).sum::<f64>() / pairs;
+        let variance = (0..5)
+            .map(|i| self.0[i] as f64 * (value(i) - mean).powi(2))
+            .sum::<f64>()
+            / pairs;
+        (mean, variance)
+    }
+}
+
+// half points `player` got, `None` when it didn't play
+fn points(record: &GameRecord, player: &str) -> Option<u32> {
+    let white = if record.white == player {
+        true
+    } else if record.black == player {
+        false
+    } else {
+        return None;
+    };
+    Some(match (record.result, white) {
+        (GameResult::Draw, _) => 1,
+        (GameResult::WhiteWins, true) | (GameResult::BlackWins, false) => 2,
+        _ => 0,
+    })
+}
+
+pub fn elo_from_ratio(ratio: f64) -> f64 {
+    -400. * (1. / ratio - 1.).log10()
+}
+
+pub fn ratio_from_elo(elo: f64) -> f64 {
+    1. / (1. + 10f64.powf(-elo / 400.))
+}
+
+// Abramowitz and Stegun 7.1.26, within 1.5e-7
+fn erf(x: f64) -> f64 {
+    let t = 1. / (1. + 0.3275911 * x.abs());
+    let polynomial = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    (1. - polynomial * (-x * x).exp()).copysign(x)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SprtStatus {
+    Continue,
+    // the change isn't `elo1` better, stop and reject it
+    AcceptH0,
+    AcceptH1,
+}
+
+// sequential probability ratio test of `elo0` against `elo1`, with false positive rate
+// `alpha` and false negative rate `beta`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprt {
+    pub elo0: f64,
+    pub elo1: f64,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+impl Default for Sprt {
+    fn default() -> Self {
+        Sprt {
+            elo0: 0.,
+            elo1: 5.,
+            alpha: 0.05,
+            beta: 0.05,
+        }
+    }
+}
+
+impl Sprt {
+    // log-likelihood ratio bounds, below the first H0 is accepted, above the second H1
+    pub fn bounds(&self) -> (f64, f64) {
+        (
+            (self.beta / (1. - self.alpha)).ln(),
+            ((1. - self.beta) / self.alpha).ln(),
+        )
+    }
+
+    // normal approximation of the generalized SPRT, per game
+    pub fn llr(&self, score: &Score) -> f64 {
+        let games = score.games() as f64;
+        let ratio = score.ratio();
+        let variance = (score.wins as f64 * (1. - ratio).powi(2)
+            + score.draws as f64 * (0.5 - ratio).powi(2)
+            + score.losses as f64 * ratio.powi(2))
+            / games.max(1.);
+        self.normal_llr(games, ratio, variance)
+    }
+
+    // the same per game pair, which accounts for the openings' bias
+    pub fn llr_pentanomial(&self, pentanomial: &Pentanomial) -> f64 {
+        let (mean, variance) = pentanomial.mean_and_variance();
+        self.normal_llr(pentanomial.pairs() as f64, mean, variance)
+    }
+
+    fn normal_llr(&self, samples: f64, mean: f64, variance: f64) -> f64 {
+        if variance <= 0. {
+            return 0.;
+        }
+        let score0 = ratio_from_elo(self.elo0);
+        let score1 = ratio_from_elo(self.elo1);
+        samples * (score1 - score0) * (2. * mean - score0 - score1) / (2. * variance)
+    }
+
+    pub fn status(&self, llr: f64) -> SprtStatus {
+        let (lower, upper) = self.bounds();
+        if llr <= lower {
+            SprtStatus::AcceptH0
+        } else if llr >= upper {
+            SprtStatus::AcceptH1
+        } else {
+            SprtStatus::Continue
+        }
+    }
+}
+
+#[test]
+fn stats_test() {
+    let score = Score {
+        wins: 30,
+        draws: 40,
+        losses: 30,
+    };
+    let elo = score.elo().unwrap();
+    assert_eq!(elo.difference, 0.);
+    assert!((elo.lower + 52.).abs() < 2. && (elo.upper - 52.).abs() < 2.);
+    assert!((score.los() - 0.5).abs() < 1e-6);
+    assert_eq!(score.draw_ratio(), 0.4);
+    assert_eq!(
+        Score {
+            wins: 3,
+            ..Score::default()
+        }
+        .elo(),
+        None
+    );
+
+    // 75% is about 191 elo
+    let score = Score {
+        wins: 60,
+        draws: 30,
+        losses: 10,
+    };
+    assert!((score.elo().unwrap().difference - 190.85).abs() < 0.01);
+    assert!(score.los() > 0.999);
+    assert!((erf(1.) - 0.842700793).abs() < 1e-6);
+
+    let sprt = Sprt::default();
+    let (lower, upper) = sprt.bounds();
+    assert!((lower + 2.944).abs() < 0.001 && (upper - 2.944).abs() < 0.001);
+    // 100 games aren't enough to tell 0 from 5 elo, even at 75%
+    assert_eq!(sprt.status(sprt.llr(&score)), SprtStatus::Continue);
+    let winning = Score {
+        wins: 600,
+        draws: 300,
+        losses: 100,
+    };
+    assert_eq!(sprt.status(sprt.llr(&winning)), SprtStatus::AcceptH1);
+    let losing = Score {
+        wins: 100,
+        draws: 300,
+        losses: 600,
+    };
+    assert_eq!(sprt.status(sprt.llr(&losing)), SprtStatus::AcceptH0);
+    let even = Score {
+        wins: 5,
+        draws: 10,
+        losses: 5,
+    };
+    assert_eq!(sprt.status(sprt.llr(&even)), SprtStatus::Continue);
+
+    let record = |white: &str, black: &str, result| GameRecord {
+        white: white.to_owned(),
+        black: black.to_owned(),
+        fen: None,
+        opening: None,
+        eco: None,
+        moves: Vec::new(),
+        result,
+        termination: String::new(),
+    };
+    let records = [
+        record("new", "base", GameResult::WhiteWins),
+        record("base", "new", GameResult::Draw),
+        record("new", "base", GameResult::Draw),
+        record("base", "new", GameResult::WhiteWins),
+    ];
+    assert_eq!(
+        Score::of(&records, "new"),
+        Score {
+            wins: 1,
+            draws: 2,
+            losses: 1
+        }
+    );
+    let pentanomial = Pentanomial::of(&records, "new");
+    assert_eq!(pentanomial, Pentanomial([0, 1, 0, 1, 0]));
+    assert_eq!(pentanomial.ratio(), 0.5);
+    assert!(sprt.llr_pentanomial(&pentanomial) < 0.);
+}
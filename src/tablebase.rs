@@ -0,0 +1,734 @@
+use std::{collections::HashMap, fs, io, path::Path};
+
+use crate::{
+    characters::moves,
+    errors::GameError,
+    game::Game,
+    moves::dirs::Dir,
+    moves::Move,
+    pieces::{Character, Side},
+    position::{Pos, Square},
+};
+
+// tables grow 64 times per piece, 4 pieces already take 33M positions
+pub const MAX_PIECES: usize = 4;
+
+const MAGIC: &[u8; 4] = b"CGTB";
+const VERSION: u8 = 1;
+
+// distance to mate in plies, counted for the side to move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dtm {
+    Win(u8),
+    // `Loss(0)` is checkmate
+    Loss(u8),
+    Draw,
+}
+
+impl Dtm {
+    // a table entry is plies + 1, wins take an odd number of plies and losses an even one,
+    // 0 is a draw or an impossible position
+    fn from_entry(entry: u8) -> Dtm {
+        match entry {
+            0 => Dtm::Draw,
+            entry if entry % 2 == 0 => Dtm::Win(entry - 1),
+            entry => Dtm::Loss(entry - 1),
+        }
+    }
+
+    // plies + 1, saturating at the largest entry of the same parity so a win never reads
+    // as a loss
+    fn entry(plies: usize) -> u8 {
+        let max = if plies.is_multiple_of(2) { 255 } else { 254 };
+        (plies + 1).min(max) as u8
+    }
+
+    // the same position seen by the other side, one ply earlier
+    fn before(self) -> Dtm {
+        match self {
+            Dtm::Win(plies) => Dtm::Loss(plies.saturating_add(1)),
+            Dtm::Loss(plies) => Dtm::Win(plies.saturating_add(1)),
+            Dtm::Draw => Dtm::Draw,
+        }
+    }
+
+    // orders results from the side to move's view, quick wins first and slow losses last
+    fn rank(self) -> i32 {
+        match self {
+            Dtm::Win(plies) => 1000 - plies as i32,
+            Dtm::Draw => 0,
+            Dtm::Loss(plies) => -1000 + plies as i32,
+        }
+    }
+}
+
+// distance to mate of every placement of one material set, either side to move
+#[derive(Debug, Clone)]
+pub struct Table {
+    material: Vec<Character>,
+    entries: Vec<u8>,
+}
+
+impl Table {
+    pub fn name(&self) -> String {
+        material_name(&self.material)
+    }
+
+    // `CGTB`, version, material name length and letters, then a byte per position
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let name = self.name();
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        bytes.push(name.len() as u8);
+        bytes.extend(name.bytes());
+        bytes.extend(&self.entries);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Table> {
+        let invalid = |reason: &str| io::Error::new(io::ErrorKind::InvalidData, reason.to_owned());
+        if bytes.len() < 6 || &bytes[..4] != MAGIC || bytes[4] != VERSION {
+            return Err(invalid("not a tablebase file"));
+        }
+        let len = bytes[5] as usize;
+        let name = bytes
+            .get(6..6 + len)
+            .and_then(|name| std::str::from_utf8(name).ok())
+            .ok_or_else(|| invalid("material name is cut off"))?;
+        let material = parse_material(name).map_err(|error| invalid(&error.to_string()))?;
+        let entries = bytes[6 + len..].to_vec();
+        if entries.len() != table_size(material.len()) {
+            return Err(invalid("table size doesn't match its material"));
+        }
+        Ok(Table { material, entries })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Table> {
+        Table::from_bytes(&fs::read(path)?)
+    }
+
+    fn dtm(&self, position: &Position) -> Dtm {
+        Dtm::from_entry(self.entries[position.index()])
+    }
+}
+
+// tables by material name, generated by retrograde analysis or loaded from disk,
+// castling and en passant are left out as they hardly happen with so few pieces
+#[derive(Debug, Clone, Default)]
+pub struct Tablebase {
+    tables: HashMap<String, Table>,
+}
+
+impl Tablebase {
+    pub fn new() -> Tablebase {
+        Tablebase::default()
+    }
+
+    pub fn table(&self, material: &str) -> Option<&Table> {
+        let name = material_name(&parse_material(material).ok()?);
+        self.tables.get(&name)
+    }
+
+    pub fn insert(&mut self, table: Table) {
+        self.tables.insert(table.name(), table);
+    }
+
+    // builds the table for `material`, ie. `KRKP`, and the smaller ones its captures and
+    // promotions lead to
+    pub fn generate(&mut self, material: &str) -> Result<&Table, GameError> {
+        let material = parse_material(material)?;
+        self.generate_material(&material);
+        Ok(&self.tables[&material_name(&material)])
+    }
+
+    // result for side to move, `None` without a table for the material on the board
+    pub fn probe(&self, game: &Game) -> Option<Dtm> {
+        let position = Position::from_game(game)?;
+        if only_kings(&position.material()) {
+            return Some(Dtm::Draw);
+        }
+        let table = self.tables.get(&material_name(&position.material()))?;
+        Some(table.dtm(&position))
+    }
+
+    // move keeping the best result, promotions included
+    pub fn best_move(&self, game: &Game) -> Option<Move> {
+        let position = Position::from_game(game)?;
+        self.tables.get(&material_name(&position.material()))?;
+        position
+            .legal_moves()
+            .into_iter()
+            .filter_map(|(from, to, child)| Some((from, to, child, self.child_dtm(&child)?)))
+            // the first of equally good moves, so a queen before an underpromotion
+            .min_by_key(|(_, _, _, dtm)| -dtm.before().rank())
+            .map(|(from, to, child, _)| {
+                let moved = position.at(from).map(|index| position.pieces()[index].0);
+                let arrived = child.at(to).map(|index| child.pieces()[index].0);
+                Move::Normal {
+                    from: square_pos(from),
+                    to: square_pos(to),
+                    promotion: arrived.filter(|&arrived| Some(arrived) != moved),
+                }
+            })
+    }
+
+    fn child_dtm(&self, child: &Position) -> Option<Dtm> {
+        if only_kings(&child.material()) {
+            return Some(Dtm::Draw);
+        }
+        let table = self.tables.get(&material_name(&child.material()))?;
+        Some(table.dtm(child))
+    }
+
+    fn generate_material(&mut self, material: &[Character]) {
+        let name = material_name(material);
+        if self.tables.contains_key(&name) || only_kings(material) {
+            return;
+        }
+        for child in child_materials(material) {
+            self.generate_material(&child);
+        }
+        let table = self.retrograde(material);
+        self.tables.insert(name, table);
+    }
+
+    fn retrograde(&self, material: &[Character]) -> Table {
+        let size = table_size(material.len());
+        let mut entries = vec![0u8; size];
+        let mut valid = vec![false; size];
+        let mut remaining = vec![0u8; size];
+        let mut can_lose = vec![false; size];
+        let mut exit_loss = vec![0u8; size];
+        // positions resolved at each ply, more may be added while earlier plies are processed
+        let mut buckets: Vec<Vec<u32>> = vec![Vec::new(); 256];
+
+        for index in 0..size {
+            let position = Position::from_index(material, index);
+            if !position.is_valid() {
+                continue;
+            }
+            valid[index] = true;
+            let moves = position.legal_moves();
+            if moves.is_empty() {
+                if position.is_in_check(position.side) {
+                    buckets[0].push(index as u32);
+                }
+                continue;
+            }
+            let mut in_table = 0;
+            let mut lose = true;
+            let mut win: Option<u8> = None;
+            let mut loss = 0;
+            for (_, _, child) in moves {
+                if child.has_material(material) {
+                    in_table += 1;
+                    continue;
+                }
+                match self.child_dtm(&child).map_or(Dtm::Draw, Dtm::before) {
+                    Dtm::Win(plies) => {
+                        win = Some(win.map_or(plies, |win| win.min(plies)));
+                        lose = false;
+                    }
+                    Dtm::Draw => lose = false,
+                    Dtm::Loss(plies) => loss = loss.max(plies),
+                }
+            }
+            remaining[index] = in_table;
+            can_lose[index] = lose;
+            exit_loss[index] = loss;
+            if let Some(win) = win {
+                buckets[win as usize].push(index as u32);
+            }
+            if lose && in_table == 0 {
+                buckets[loss as usize].push(index as u32);
+            }
+        }
+
+        for ply in 0..buckets.len() {
+            let mut resolved = Vec::new();
+            for index in std::mem::take(&mut buckets[ply]) {
+                if entries[index as usize] == 0 {
+                    entries[index as usize] = Dtm::entry(ply);
+                    resolved.push(index);
+                }
+            }
+            if ply + 1 >= buckets.len() {
+                break;
+            }
+            for index in resolved {
+                let position = Position::from_index(material, index as usize);
+                for parent in position.parents() {
+                    let parent = parent.index();
+                    if !valid[parent] || entries[parent] != 0 {
+                        continue;
+                    }
+                    if ply % 2 == 0 {
+                        // moving into a lost position wins
+                        buckets[ply + 1].push(parent as u32);
+                    } else {
+                        remaining[parent] -= 1;
+                        if remaining[parent] == 0 && can_lose[parent] {
+                            let loss = (ply + 1).max(exit_loss[parent] as usize);
+                            buckets[loss].push(parent as u32);
+                        }
+                    }
+                }
+            }
+        }
+        Table {
+            material: material.to_vec(),
+            entries,
+        }
+    }
+}
+
+// `KQK`, `KRKP` or `KRvKP`, white pieces up to the second king
+pub fn parse_material(name: &str) -> Result<Vec<Character>, GameError> {
+    let invalid = || GameError::InvalidNotation(name.to_owned());
+    let letters: String = name.chars().filter(|&c| c != 'v').collect();
+    let black = letters
+        .get(1..)
+        .and_then(|rest| rest.find('K'))
+        .ok_or_else(invalid)?
+        + 1;
+    let mut material = Vec::new();
+    for (index, letter) in letters.char_indices() {
+        let letter = if index < black {
+            letter.to_ascii_uppercase()
+        } else {
+            letter.to_ascii_lowercase()
+        };
+        material.push(Character::from_letter(letter).ok_or_else(invalid)?);
+    }
+    let kings = |side| {
+        material
+            .iter()
+            .filter(|&&c| c == Character::King(side))
+            .count()
+    };
+    if kings(Side::White) != 1 || kings(Side::Black) != 1 || material.len() > MAX_PIECES {
+        return Err(invalid());
+    }
+    sort_material(&mut material);
+    Ok(material)
+}
+
+fn material_name(material: &[Character]) -> String {
+    material
+        .iter()
+        .map(|c| c.letter().to_ascii_uppercase())
+        .collect()
+}
+
+fn sort_material(material: &mut [Character]) {
+    material.sort_by_key(|&c| order(c));
+}
+
+fn order(character: Character) -> usize {
+    let kind = "KQRBNP"
+        .find(character.letter().to_ascii_uppercase())
+        .unwrap_or_default();
+    match character.side() {
+        Side::White => kind,
+        Side::Black => kind + 6,
+    }
+}
+
+fn only_kings(material: &[Character]) -> bool {
+    material.iter().all(|c| matches!(c, Character::King(_)))
+}
+
+fn table_size(pieces: usize) -> usize {
+    2 * 64usize.pow(pieces as u32)
+}
+
+// materials one capture or promotion away
+fn child_materials(material: &[Character]) -> Vec<Vec<Character>> {
+    let mut children = Vec::new();
+    for (index, &character) in material.iter().enumerate() {
+        if !matches!(character, Character::King(_)) {
+            let mut child = material.to_vec();
+            child.remove(index);
+            children.push(child);
+        }
+        if let Character::Pawn(side) = character {
+            for promoted in promotions(side) {
+                let mut child = material.to_vec();
+                child[index] = promoted;
+                sort_material(&mut child);
+                children.push(child);
+            }
+        }
+    }
+    children
+}
+
+fn promotions(side: Side) -> [Character; 4] {
+    [
+        Character::Queen(side),
+        Character::Rook(side),
+        Character::Bishop(side),
+        Character::Knight(side),
+    ]
+}
+
+fn square_pos(square: u8) -> Pos {
+    Square::from_index(square).map(Pos::from).unwrap()
+}
+
+fn offset(square: u8, (d_file, d_rank): (i32, i32)) -> Option<u8> {
+    let file = (square % 8) as i32 + d_file;
+    let rank = (square / 8) as i32 + d_rank;
+    ((0..8).contains(&file) && (0..8).contains(&rank)).then(|| (rank * 8 + file) as u8)
+}
+
+// pieces in material order with their squares, a1 = 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    // fixed size, positions get copied millions of times while generating
+    slots: [(Character, u8); MAX_PIECES],
+    len: usize,
+    side: Side,
+}
+
+impl Position {
+    fn from_index(material: &[Character], mut index: usize) -> Position {
+        let mut position = Position {
+            slots: [(Character::King(Side::White), 0); MAX_PIECES],
+            len: material.len(),
+            side: Side::White,
+        };
+        for (slot, &character) in position.pieces_mut().iter_mut().zip(material).rev() {
+            *slot = (character, (index % 64) as u8);
+            index /= 64;
+        }
+        if index != 0 {
+            position.side = Side::Black;
+        }
+        position
+    }
+
+    fn from_game(game: &Game) -> Option<Position> {
+        let mut pieces: Vec<(Character, u8)> = Square::all()
+            .filter_map(|square| {
+                let character = game.board.character_at(Pos::from(square))?;
+                Some((character, square.index()))
+            })
+            .collect();
+        if pieces.len() > MAX_PIECES {
+            return None;
+        }
+        pieces.sort_by_key(|&(c, _)| order(c));
+        let mut position = Position {
+            slots: [(Character::King(Side::White), 0); MAX_PIECES],
+            len: pieces.len(),
+            side: game.whose_turn(),
+        };
+        position.pieces_mut().copy_from_slice(&pieces);
+        Some(position)
+    }
+
+    fn pieces(&self) -> &[(Character, u8)] {
+        &self.slots[..self.len]
+    }
+
+    fn pieces_mut(&mut self) -> &mut [(Character, u8)] {
+        &mut self.slots[..self.len]
+    }
+
+    fn remove(&mut self, index: usize) {
+        self.slots.copy_within(index + 1.., index);
+        self.len -= 1;
+    }
+
+    fn index(&self) -> usize {
+        let side = match self.side {
+            Side::White => 0,
+            Side::Black => 1,
+        };
+        self.pieces()
+            .iter()
+            .fold(side, |index, &(_, square)| index * 64 + square as usize)
+    }
+
+    fn has_material(&self, material: &[Character]) -> bool {
+        self.pieces().len() == material.len()
+            && self
+                .pieces()
+                .iter()
+                .zip(material)
+                .all(|(&(c, _), &m)| c == m)
+    }
+
+    fn material(&self) -> Vec<Character> {
+        self.pieces().iter().map(|&(c, _)| c).collect()
+    }
+
+    fn at(&self, square: u8) -> Option<usize> {
+        self.pieces().iter().position(|&(_, s)| s == square)
+    }
+
+    fn king(&self, side: Side) -> u8 {
+        self.pieces()
+            .iter()
+            .find(|&&(c, _)| c == Character::King(side))
+            .map_or(0, |&(_, square)| square)
+    }
+
+    // walks the squares a character on `from` attacks, stopping at the first piece on each
+    // ray, until `visit` returns true
+    fn visit_attacks(&self, character: Character, from: u8, mut visit: impl FnMut(u8) -> bool) {
+        let (dirs, slides): (&[Dir], bool) = match character {
+            Character::King(_) => (&moves::King, false),
+            Character::Queen(_) => (&moves::Queen, true),
+            Character::Rook(_) => (&moves::Rook, true),
+            Character::Bishop(_) => (&moves::Bishop, true),
+            Character::Knight(_) => (&moves::Knight, false),
+            Character::Pawn(Side::White) => (&[(-1, 1), (1, 1)], false),
+            Character::Pawn(Side::Black) => (&[(-1, -1), (1, -1)], false),
+        };
+        for &dir in dirs {
+            let mut square = from;
+            while let Some(next) = offset(square, dir) {
+                if visit(next) {
+                    return;
+                }
+                if !slides || self.at(next).is_some() {
+                    break;
+                }
+                square = next;
+            }
+        }
+    }
+
+    fn attacks(&self, character: Character, from: u8) -> Vec<u8> {
+        let mut attacks = Vec::new();
+        self.visit_attacks(character, from, |square| {
+            attacks.push(square);
+            false
+        });
+        attacks
+    }
+
+    fn is_in_check(&self, side: Side) -> bool {
+        let king = self.king(side);
+        self.pieces()
+            .iter()
+            .filter(|&&(c, _)| c.side() != side)
+            .any(|&(c, square)| {
+                let mut check = false;
+                self.visit_attacks(c, square, |target| {
+                    check = target == king;
+                    check
+                });
+                check
+            })
+    }
+
+    // no overlapping pieces, no pawns on back ranks and the side not to move isn't in check
+    fn is_valid(&self) -> bool {
+        for (index, &(character, square)) in self.pieces().iter().enumerate() {
+            if self.pieces()[..index].iter().any(|&(_, s)| s == square) {
+                return false;
+            }
+            if matches!(character, Character::Pawn(_)) && !(8..56).contains(&square) {
+                return false;
+            }
+        }
+        !self.is_in_check(self.side.opponent())
+    }
+
+    // (from, to, position after) for every legal move of side to move
+    fn legal_moves(&self) -> Vec<(u8, u8, Position)> {
+        let mut moves = Vec::new();
+        for (index, &(character, from)) in self.pieces().iter().enumerate() {
+            if character.side() != self.side {
+                continue;
+            }
+            let mut targets: Vec<u8> = match character {
+                Character::Pawn(side) => {
+                    let (d_rank, start) = match side {
+                        Side::White => (1, 1),
+                        Side::Black => (-1, 6),
+                    };
+                    let mut targets: Vec<u8> = self
+                        .attacks(character, from)
+                        .into_iter()
+                        .filter(|&to| self.at(to).is_some())
+                        .collect();
+                    if let Some(to) = offset(from, (0, d_rank)).filter(|&to| self.at(to).is_none())
+                    {
+                        targets.push(to);
+                        if from / 8 == start {
+                            targets.extend(
+                                offset(from, (0, 2 * d_rank)).filter(|&to| self.at(to).is_none()),
+                            );
+                        }
+                    }
+                    targets
+                }
+                _ => self.attacks(character, from),
+            };
+            targets.retain(|&to| {
+                self.at(to)
+                    .is_none_or(|i| self.pieces()[i].0.side() != self.side)
+            });
+            for to in targets {
+                let promotes = matches!(character, Character::Pawn(_)) && !(8..56).contains(&to);
+                let characters = if promotes {
+                    promotions(self.side).to_vec()
+                } else {
+                    vec![character]
+                };
+                for moved in characters {
+                    let mut child = *self;
+                    child.pieces_mut()[index] = (moved, to);
+                    if let Some(captured) = self.at(to) {
+                        child.remove(captured);
+                    }
+                    if child.len != self.len || promotes {
+                        child.pieces_mut().sort_by_key(|&(c, _)| order(c));
+                    }
+                    if child.is_in_check(self.side) {
+                        continue;
+                    }
+                    child.side = self.side.opponent();
+                    moves.push((from, to, child));
+                }
+            }
+        }
+        moves
+    }
+
+    // positions one quiet move earlier, captures and promotions come from other tables
+    fn parents(&self) -> Vec<Position> {
+        let mover = self.side.opponent();
+        let mut parents = Vec::new();
+        for (index, &(character, to)) in self.pieces().iter().enumerate() {
+            if character.side() != mover {
+                continue;
+            }
+            let origins: Vec<u8> = match character {
+                Character::Pawn(side) => {
+                    let (d_rank, double_rank) = match side {
+                        Side::White => (-1, 3),
+                        Side::Black => (1, 4),
+                    };
+                    let mut origins = Vec::new();
+                    if let Some(from) = offset(to, (0, d_rank)).filter(|&s| self.at(s).is_none()) {
+                        origins.push(from);
+                        if to / 8 == double_rank {
+                            origins.extend(
+                                offset(from, (0, d_rank)).filter(|&s| self.at(s).is_none()),
+                            );
+                        }
+                    }
+                    origins
+                }
+                _ => self
+                    .attacks(character, to)
+                    .into_iter()
+                    .filter(|&s| self.at(s).is_none())
+                    .collect(),
+            };
+            for from in origins {
+                let mut parent = *self;
+                parent.pieces_mut()[index].1 = from;
+                parent.side = mover;
+                parents.push(parent);
+            }
+        }
+        parents
+    }
+}
+
+#[test]
+fn tablebase_test() {
+    let mut tablebase = Tablebase::new();
+    assert!(tablebase.generate("KQKQQ").is_err());
+    assert!(tablebase.generate("KQ").is_err());
+    assert!(tablebase.generate("").is_err());
+    assert!(tablebase.generate("éKK").is_err());
+    assert_eq!(Dtm::from_entry(Dtm::entry(300)), Dtm::Loss(254));
+    assert_eq!(Dtm::from_entry(Dtm::entry(301)), Dtm::Win(253));
+
+    // a KQK table from a minimal file, the mate is its only entry that isn't a draw
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend([VERSION, 3]);
+    bytes.extend(b"KQK");
+    let mut table = Table::from_bytes(&[bytes.clone(), vec![0; table_size(3)]].concat()).unwrap();
+    let mate = Game::from_fen("Q6k/8/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    let position = Position::from_game(&mate).unwrap();
+    table.entries[position.index()] = 1;
+    let mut tablebase = Tablebase::new();
+    tablebase.insert(table);
+    assert_eq!(tablebase.probe(&mate), Some(Dtm::Loss(0)));
+    let game = Game::from_fen("7k/8/6K1/8/8/8/Q7/8 w - - 0 1").unwrap();
+    assert_eq!(
+        tablebase.best_move(&game),
+        Some(Move::Normal {
+            from: Pos('a', 2),
+            to: Pos('a', 8),
+            promotion: None,
+        })
+    );
+    assert!(Table::from_bytes(&bytes).is_err());
+}
+
+// generates whole tables, slow without optimizations: cargo test --release -- --ignored
+#[test]
+#[ignore]
+fn tablebase_generation_test() {
+    let mut tablebase = Tablebase::new();
+    let table = tablebase.generate("KQvK").unwrap();
+    assert_eq!(table.name(), "KQK");
+    // the longest KQK mate takes 10 moves
+    let longest = table
+        .entries
+        .iter()
+        .map(|&e| Dtm::from_entry(e))
+        .max_by_key(|dtm| match dtm {
+            Dtm::Win(plies) => *plies as i32,
+            _ => 0,
+        });
+    assert_eq!(longest, Some(Dtm::Win(19)));
+
+    let mut game = Game::from_fen("7k/8/6K1/8/8/8/Q7/8 w - - 0 1").unwrap();
+    assert_eq!(tablebase.probe(&game), Some(Dtm::Win(1)));
+    let mate = tablebase.best_move(&game).unwrap();
+    game.make_move(mate).unwrap();
+    assert_eq!(tablebase.probe(&game), Some(Dtm::Loss(0)));
+    assert!(game.is_game_over());
+    let game = Game::from_fen("Q6k/8/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert_eq!(tablebase.probe(&game), Some(Dtm::Loss(0)));
+    // stalemate and a queen left hanging next to the king
+    let game = Game::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert_eq!(tablebase.probe(&game), Some(Dtm::Draw));
+    let game = Game::from_fen("7k/6Q1/8/8/8/8/8/K7 b - - 0 1").unwrap();
+    assert_eq!(tablebase.probe(&game), Some(Dtm::Draw));
+    let game = Game::from_fen("7k/8/8/8/8/8/8/KR6 w - - 0 1").unwrap();
+    assert_eq!(tablebase.probe(&game), None);
+
+    let path = std::env::temp_dir().join("chess_game_engine_kqk.tb");
+    tablebase.table("KQK").unwrap().save(&path).unwrap();
+    let loaded = Table::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.entries, tablebase.table("KQK").unwrap().entries);
+    assert!(Table::from_bytes(&loaded.to_bytes()[..100]).is_err());
+
+    // the pawn promotes to the queen mating at once
+    let game = Game::from_fen("k7/2P5/1K6/8/8/8/8/8 w - - 0 1").unwrap();
+    tablebase.generate("KPK").unwrap();
+    assert_eq!(
+        tablebase.best_move(&game),
+        Some(Move::Normal {
+            from: Pos('c', 7),
+            to: Pos('c', 8),
+            promotion: Some(Character::Queen(Side::White)),
+        })
+    );
+}
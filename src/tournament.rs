@@ -0,0 +1,866 @@
+use std::{
+    cell::RefCell,
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    eco,
+    epd::Epd,
+    errors::GameError,
+    game::{EndReason, Game},
+    moves::Move,
+    pgn::{self, PgnGame},
+    pieces::Side,
+    stats::{Pentanomial, Sprt, SprtStatus},
+};
+
+// the move, or why the player forfeits, ie. "made no move"
+type Choose = Box<dyn FnMut(&Game, Option<Clocks>) -> Result<Move, String>>;
+
+// an engine taking part in a match, ie. this crate's search with some settings or a
+// wrapper talking UCI to a child process
+pub struct Player {
+    pub name: String,
+    choose: Choose,
+    new_game: Box<dyn FnMut()>,
+}
+
+impl Player {
+    pub fn new(name: &str, mut choose: impl FnMut(&Game) -> Option<Move> + 'static) -> Player {
+        Player::timed(name, move |game, _| choose(game))
+    }
+
+    // a player told the time left on both clocks when there's a time control
+    pub fn timed(
+        name: &str,
+        mut choose: impl FnMut(&Game, Option<Clocks>) -> Option<Move> + 'static,
+    ) -> Player {
+        Player {
+            name: name.to_owned(),
+            choose: Box::new(move |game, clocks| {
+                choose(game, clocks).ok_or_else(|| "made no move".to_owned())
+            }),
+            new_game: Box::new(|| {}),
+        }
+    }
+
+    // called before every game the player takes part in, ie. to clear its hash table
+    pub fn on_new_game(mut self, new_game: impl FnMut() + 'static) -> Player {
+        self.new_game = Box::new(new_game);
+        self
+    }
+
+    // an engine speaking UCI, started as `command` with `args` and named as it reports
+    // itself, `go` limits the search without a time control, ie. `movetime 100` or
+    // `depth 8`, an engine not answering within `timeout`, or `timeout` past its clock,
+    // forfeits and every later game too
+    pub fn uci(command: &str, args: &[&str], go: &str, timeout: Duration) -> io::Result<Player> {
+        let engine = Rc::new(RefCell::new(UciEngine::start(command, args, timeout)?));
+        let name = engine.borrow().name.clone();
+        let go = go.to_owned();
+        let choose = {
+            let engine = Rc::clone(&engine);
+            move |game: &Game, clocks| engine.borrow_mut().best_move(game, clocks, &go)
+        };
+        Ok(Player {
+            name,
+            choose: Box::new(choose),
+            new_game: Box::new(move || {
+                // an engine failing here fails again on its first move
+                let _ = engine.borrow_mut().new_game();
+            }),
+        })
+    }
+}
+
+// a child process talking UCI over its standard input and output
+struct UciEngine {
+    name: String,
+    process: Child,
+    input: ChildStdin,
+    // lines the engine writes, read on a thread of their own so waiting can time out
+    output: Receiver<String>,
+    timeout: Duration,
+    // missed a deadline, whatever it answers later belongs to an old request
+    flagged: bool,
+}
+
+impl UciEngine {
+    fn start(command: &str, args: &[&str], timeout: Duration) -> io::Result<UciEngine> {
+        let mut process = Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let input = process.stdin.take().unwrap();
+        let stdout = process.stdout.take().unwrap();
+        let (sender, output) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        let mut engine = UciEngine {
+            name: command.to_owned(),
+            process,
+            input,
+            output,
+            timeout,
+            flagged: false,
+        };
+        engine.send("uci")?;
+        let mut name = None;
+        engine.read_until(timeout, |line| {
+            if let Some(id) = line.strip_prefix("id name ") {
+                name = Some(id.trim().to_owned());
+            }
+            line == "uciok"
+        })?;
+        if let Some(name) = name {
+            engine.name = name;
+        }
+        engine.send("isready")?;
+        engine.read_until(timeout, |line| line == "readyok")?;
+        Ok(engine)
+    }
+
+    fn send(&mut self, command: &str) -> io::Result<()> {
+        writeln!(self.input, "{command}")?;
+        self.input.flush()
+    }
+
+    // reads lines until `done` returns true, the last of them is returned, the engine is
+    // flagged when that takes longer than `deadline`
+    fn read_until(
+        &mut self,
+        deadline: Duration,
+        mut done: impl FnMut(&str) -> bool,
+    ) -> io::Result<String> {
+        if self.flagged {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "engine stopped answering",
+            ));
+        }
+        let end = Instant::now() + deadline;
+        loop {
+            let left = end.saturating_duration_since(Instant::now());
+            match self.output.recv_timeout(left) {
+                Ok(line) if done(line.trim()) => return Ok(line.trim().to_owned()),
+                Ok(_) => {}
+                Err(RecvTimeoutError::Timeout) => {
+                    self.flagged = true;
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "engine didn't answer in time",
+                    ));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "engine closed its output",
+                    ))
+                }
+            }
+        }
+    }
+
+    fn new_game(&mut self) -> io::Result<()> {
+        self.send("ucinewgame")?;
+        self.send("isready")?;
+        self.read_until(self.timeout, |line| line == "readyok")?;
+        Ok(())
+    }
+
+    // the position goes as a FEN, so the engine doesn't see the moves leading to it, a
+    // reply which isn't a move forfeits rather than being taken for no move
+    fn best_move(&mut self, game: &Game, clocks: Option<Clocks>, go: &str) -> Result<Move, String> {
+        let line = self
+            .search(game, clocks, go)
+            .map_err(|error| match error.kind() {
+                io::ErrorKind::TimedOut => "didn't answer in time".to_owned(),
+                _ => format!("failed: {error}"),
+            })?;
+        line.split_whitespace()
+            .nth(1)
+            .and_then(Move::from_uci)
+            .ok_or_else(|| format!("sent `{line}`, which isn't a move"))
+    }
+
+    fn search(&mut self, game: &Game, clocks: Option<Clocks>, go: &str) -> io::Result<String> {
+        // `~` marks promoted pieces for crazyhouse, which UCI engines don't read
+        self.send(&format!("position fen {}", game.to_fen().replace('~', "")))?;
+        let deadline = match clocks {
+            Some(clocks) => {
+                self.send(&format!(
+                    "go wtime {} btime {} winc {} binc {}",
+                    clocks.white.as_millis(),
+                    clocks.black.as_millis(),
+                    clocks.increment.as_millis(),
+                    clocks.increment.as_millis()
+                ))?;
+                clocks.left(game.whose_turn()) + self.timeout
+            }
+            None => {
+                self.send(&format!("go {go}"))?;
+                self.timeout
+            }
+        };
+        self.read_until(deadline, |line| line.starts_with("bestmove"))
+    }
+}
+
+impl Drop for UciEngine {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        // a hung engine wouldn't read `quit`
+        if self.flagged {
+            let _ = self.process.kill();
+        }
+        let _ = self.process.wait();
+    }
+}
+
+// time for the whole game per side, added to after every move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeControl {
+    pub base: Duration,
+    pub increment: Duration,
+}
+
+// time left for both sides when a player is asked to move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clocks {
+    pub white: Duration,
+    pub black: Duration,
+    pub increment: Duration,
+}
+
+impl Clocks {
+    fn left(&self, side: Side) -> Duration {
+        match side {
+            Side::White => self.white,
+            Side::Black => self.black,
+        }
+    }
+
+    fn side(&mut self, side: Side) -> &mut Duration {
+        match side {
+            Side::White => &mut self.white,
+            Side::Black => &mut self.black,
+        }
+    }
+}
+
+// games not over by the rules are stopped as draws by these, or lost on time with a
+// time control
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Adjudication {
+    pub max_plies: Option<usize>,
+    pub fifty_moves: bool,
+    pub threefold: bool,
+    pub time_control: Option<TimeControl>,
+}
+
+impl Default for Adjudication {
+    fn default() -> Self {
+        Adjudication {
+            max_plies: Some(400),
+            fifty_moves: true,
+            threefold: true,
+            time_control: None,
+        }
+    }
+}
+
+impl Adjudication {
+    // why the game is drawn after `plies` moves, if it is
+    pub fn draw(&self, game: &Game, plies: usize) -> Option<&'static str> {
+        if self.max_plies.is_some_and(|max| plies >= max) {
+            Some("adjudication: maximum length")
+        } else if self.fifty_moves && game.halfmove_clock >= 100 {
+            Some("fifty move rule")
+        } else if self.threefold && {
+            let keys = game.position_keys();
+            let current = keys[keys.len() - 1];
+            keys.iter().filter(|&&key| key == current).count() >= 3
+        } {
+            Some("threefold repetition")
+        } else {
+            None
+        }
+    }
+}
+
+// where a game starts: a position of an EPD suite or the moves of a PGN game, neither
+// player chooses these moves
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Opening {
+    pub name: Option<String>,
+    // `None` for the initial position
+    pub fen: Option<String>,
+    pub moves: Vec<Move>,
+}
+
+impl Opening {
+    // the position, named by the `id` opcode
+    pub fn from_epd(epd: &Epd) -> Result<Opening, GameError> {
+        Ok(Opening {
+            name: epd.id().map(str::to_owned),
+            fen: Some(epd.game()?.to_fen()),
+            moves: Vec::new(),
+        })
+    }
+
+    // the game's moves from its start, named by the `Opening` tag
+    pub fn from_pgn(game: &PgnGame) -> Opening {
+        Opening {
+            name: game.tag("Opening").map(str::to_owned),
+            fen: game.tag("FEN").map(str::to_owned),
+            moves: game.moves.clone(),
+        }
+    }
+}
+
+// every game of `text` as an opening, ie. a file of short book lines
+pub fn pgn_openings(text: &str) -> Result<Vec<Opening>, GameError> {
+    Ok(pgn::parse_pgn(text)?
+        .iter()
+        .map(Opening::from_pgn)
+        .collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+}
+
+impl GameResult {
+    pub fn pgn(&self) -> &'static str {
+        match self {
+            Self::WhiteWins => "1-0",
+            Self::BlackWins => "0-1",
+            Self::Draw => "1/2-1/2",
+        }
+    }
+
+    fn win_for(side: Side) -> GameResult {
+        match side {
+            Side::White => Self::WhiteWins,
+            Side::Black => Self::BlackWins,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    pub white: String,
+    pub black: String,
+    // FEN the game started from, `None` for the initial position
+    pub fen: Option<String>,
+    // `id` of the opening position
+    pub opening: Option<String>,
+    // the ECO opening the game went through, it names the game in place of `opening`
+    pub eco: Option<&'static eco::Opening>,
+    // in SAN
+    pub moves: Vec<String>,
+    pub result: GameResult,
+    pub termination: String,
+}
+
+impl GameRecord {
+    pub fn to_pgn(&self, round: usize) -> String {
+        let mut pgn = String::new();
+        let mut tag = |name: &str, value: &str| {
+            pgn.push_str(&format!("[{name} \"{}\"]\n", value.replace('"', "\\\"")))
+        };
+        tag("Event", "Engine match");
+        tag("Site", "?");
+        tag("Date", "????.??.??");
+        tag("Round", &round.to_string());
+        tag("White", &self.white);
+        tag("Black", &self.black);
+        tag("Result", self.result.pgn());
+        if let Some(fen) = &self.fen {
+            tag("SetUp", "1");
+            tag("FEN", fen);
+        }
+        if let Some(eco) = self.eco {
+            tag("ECO", eco.code);
+            tag("Opening", eco.name);
+            if let Some(variation) = eco.variation {
+                tag("Variation", variation);
+            }
+        } else if let Some(opening) = &self.opening {
+            tag("Opening", opening);
+        }
+        tag("Termination", &self.termination);
+        pgn.push('\n');
+
+        // move numbers continue from the FEN, black moving first gets `n...`
+        let mut fields = self.fen.as_deref().unwrap_or("").split_whitespace();
+        let black_first = fields.nth(1) == Some("b");
+        let first_number: usize = fields.nth(3).and_then(|n| n.parse().ok()).unwrap_or(1);
+        let mut tokens = Vec::new();
+        for (ply, san) in self.moves.iter().enumerate() {
+            let ply = ply + black_first as usize;
+            let number = first_number + ply / 2;
+            if ply.is_multiple_of(2) {
+                tokens.push(format!("{number}."));
+            } else if tokens.is_empty() {
+                tokens.push(format!("{number}..."));
+            }
+            tokens.push(san.clone());
+        }
+        tokens.push(self.result.pgn().to_owned());
+
+        // movetext lines stay under 80 characters
+        let mut line = String::new();
+        for token in tokens {
+            if !line.is_empty() && line.len() + 1 + token.len() >= 80 {
+                pgn.push_str(&line);
+                pgn.push('\n');
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&token);
+        }
+        pgn.push_str(&line);
+        pgn.push_str("\n\n");
+        pgn
+    }
+}
+
+// plays one game from `opening`, or the initial position, a player returning no move or
+// an illegal one loses, the opening's moves are part of the record
+pub fn play_game(
+    white: &mut Player,
+    black: &mut Player,
+    opening: Option<&Opening>,
+    adjudication: Adjudication,
+) -> Result<GameRecord, GameError> {
+    let fen = opening.and_then(|opening| opening.fen.clone());
+    let mut game = match &fen {
+        Some(fen) => Game::from_fen(fen)?,
+        None => {
+            let mut game = Game::new();
+            game.start();
+            game
+        }
+    };
+    let mut record = GameRecord {
+        white: white.name.clone(),
+        black: black.name.clone(),
+        fen,
+        opening: opening.and_then(|opening| opening.name.clone()),
+        eco: None,
+        moves: Vec::new(),
+        result: GameResult::Draw,
+        termination: String::new(),
+    };
+    for &mv in opening.map_or(&[][..], |opening| &opening.moves) {
+        let san = game.to_san(mv);
+        game.make_move(mv)?;
+        record.moves.push(san + check_suffix(&game));
+    }
+    (white.new_game)();
+    (black.new_game)();
+
+    let mut clocks = adjudication.time_control.map(|control| Clocks {
+        white: control.base,
+        black: control.base,
+        increment: control.increment,
+    });
+    loop {
+        if let Some(outcome) = game.outcome() {
+            record.result = outcome.winner.map_or(GameResult::Draw, GameResult::win_for);
+            record.termination = format!("{:?}", outcome.reason);
+            break;
+        }
+        if let Some(termination) = adjudication.draw(&game, record.moves.len()) {
+            record.termination = termination.to_owned();
+            break;
+        }
+
+        let side = game.whose_turn();
+        let player = match side {
+            Side::White => &mut *white,
+            Side::Black => &mut *black,
+        };
+        let started = Instant::now();
+        let chosen = (player.choose)(&game, clocks);
+        if let Some(clocks) = &mut clocks {
+            let increment = clocks.increment;
+            let left = clocks.side(side);
+            let Some(rest) = left.checked_sub(started.elapsed()) else {
+                record.result = GameResult::win_for(side.opponent());
+                record.termination = format!("{} lost on time", player.name);
+                break;
+            };
+            *left = rest + increment;
+        }
+        let mv = match chosen {
+            Ok(mv) => mv,
+            Err(forfeit) => {
+                record.result = GameResult::win_for(side.opponent());
+                record.termination = format!("{} {forfeit}", player.name);
+                break;
+            }
+        };
+        let san = game.to_san(mv);
+        if game.make_move(mv).is_err() {
+            record.result = GameResult::win_for(side.opponent());
+            record.termination = format!("{} played the illegal move {san}", player.name);
+            break;
+        }
+        record.moves.push(san + check_suffix(&game));
+    }
+    record.eco = eco::classify(&game);
+    Ok(record)
+}
+
+// `#` or `+` for the move that led to `game`
+fn check_suffix(game: &Game) -> &'static str {
+    let checkmate = game
+        .outcome()
+        .is_some_and(|outcome| outcome.reason == EndReason::Checkmate);
+    match (checkmate, game.is_in_check()) {
+        (true, _) => "#",
+        (false, true) => "+",
+        _ => "",
+    }
+}
+
+// the games of a round robin as (white, black, opening), every pair meets on every
+// opening twice, with colours swapped
+fn schedule(players: usize, openings: &[Opening]) -> Vec<(usize, usize, Option<&Opening>)> {
+    let openings: Vec<Option<&Opening>> = if openings.is_empty() {
+        vec![None]
+    } else {
+        openings.iter().map(Some).collect()
+    };
+    let mut games = Vec::new();
+    for first in 0..players {
+        for second in first + 1..players {
+            for &opening in &openings {
+                games.push((first, second, opening));
+                games.push((second, first, opening));
+            }
+        }
+    }
+    games
+}
+
+// two different elements of `items` at once
+fn pair<T>(items: &mut [T], first: usize, second: usize) -> (&mut T, &mut T) {
+    if first < second {
+        let (left, right) = items.split_at_mut(second);
+        (&mut left[first], &mut right[0])
+    } else {
+        let (left, right) = items.split_at_mut(first);
+        (&mut right[0], &mut left[second])
+    }
+}
+
+// every pair of players meets on every opening twice, with colours swapped
+pub fn round_robin(
+    players: &mut [Player],
+    openings: &[Opening],
+    adjudication: Adjudication,
+) -> Result<Vec<GameRecord>, GameError> {
+    schedule(players.len(), openings)
+        .into_iter()
+        .map(|(white, black, opening)| {
+            let (white, black) = pair(players, white, black);
+            play_game(white, black, opening, adjudication)
+        })
+        .collect()
+}
+
+// `round_robin` with `concurrency` games at a time, each thread makes its players with
+// the factories so an engine process only ever plays one game, records come in the same
+// order
+pub fn round_robin_concurrent(
+    players: &[&(dyn Fn() -> Player + Sync)],
+    openings: &[Opening],
+    adjudication: Adjudication,
+    concurrency: usize,
+) -> Result<Vec<GameRecord>, GameError> {
+    let games = schedule(players.len(), openings);
+    let next = AtomicUsize::new(0);
+    let records = Mutex::new(vec![None; games.len()]);
+    thread::scope(|scope| {
+        for _ in 0..concurrency.max(1) {
+            scope.spawn(|| {
+                let mut own: Vec<Option<Player>> = players.iter().map(|_| None).collect();
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(&(white, black, opening)) = games.get(index) else {
+                        break;
+                    };
+                    for index in [white, black] {
+                        own[index].get_or_insert_with(players[index]);
+                    }
+                    let (white_player, black_player) = pair(&mut own, white, black);
+                    let record = play_game(
+                        white_player.as_mut().unwrap(),
+                        black_player.as_mut().unwrap(),
+                        opening,
+                        adjudication,
+                    );
+                    records.lock().unwrap()[index] = Some(record);
+                }
+            });
+        }
+    });
+    records
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+// `candidate` against `baseline` in pairs over the openings, again from the first when
+// they run out, until the pentanomial SPRT decides or `max_pairs` were played
+pub fn play_sprt(
+    candidate: &mut Player,
+    baseline: &mut Player,
+    openings: &[Opening],
+    adjudication: Adjudication,
+    sprt: Sprt,
+    max_pairs: usize,
+) -> Result<(Vec<GameRecord>, SprtStatus), GameError> {
+    let mut records = Vec::new();
+    let mut openings = openings.iter().map(Some).cycle();
+    for _ in 0..max_pairs {
+        let opening = openings.next().flatten();
+        records.push(play_game(candidate, baseline, opening, adjudication)?);
+        records.push(play_game(baseline, candidate, opening, adjudication)?);
+        let pentanomial = Pentanomial::of(&records, &candidate.name);
+        let status = sprt.status(sprt.llr_pentanomial(&pentanomial));
+        if status != SprtStatus::Continue {
+            return Ok((records, status));
+        }
+    }
+    Ok((records, SprtStatus::Continue))
+}
+
+pub fn write_pgn(records: &[GameRecord], path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    for (round, record) in records.iter().enumerate() {
+        file.write_all(record.to_pgn(round + 1).as_bytes())?;
+    }
+    Ok(())
+}
+
+#[test]
+fn tournament_test() {
+    use crate::{epd::parse_suite, pieces::Character, position::Pos};
+
+    // mates when it can, otherwise plays its first move
+    let mater = Player::new("mater", |game| {
+        let moves = game.all_legal_moves();
+        let mate = moves.iter().copied().find(|&mv| {
+            let mut after = game.clone();
+            after.make_move(mv).is_ok()
+                && after
+                    .outcome()
+                    .is_some_and(|outcome| outcome.reason == EndReason::Checkmate)
+        });
+        mate.or(moves.first().copied())
+    });
+    let first = Player::new("first", |game| game.all_legal_moves().first().copied());
+    let mut players = [mater, first];
+
+    let suite = parse_suite("7k/8/6K1/8/8/8/Q7/8 w - - id \"KQK\";").unwrap();
+    let openings = [Opening::from_epd(&suite[0]).unwrap()];
+    let adjudication = Adjudication {
+        max_plies: Some(20),
+        ..Adjudication::default()
+    };
+    let records = round_robin(&mut players, &openings, adjudication).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].moves, ["Qa8#"]);
+    assert_eq!(records[0].result, GameResult::WhiteWins);
+    assert_eq!(
+        records[0].to_pgn(1),
+        "[Event \"Engine match\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"1\"]\n\
+         [White \"mater\"]\n[Black \"first\"]\n[Result \"1-0\"]\n[SetUp \"1\"]\n\
+         [FEN \"7k/8/6K1/8/8/8/Q7/8 w - - 0 1\"]\n[Opening \"KQK\"]\n\
+         [Termination \"Checkmate\"]\n\n1. Qa8# 1-0\n\n"
+    );
+    assert_eq!(records[1].white, "first");
+    assert!(records[1].moves.len() <= 20);
+
+    // a player without a move forfeits
+    let mut passer = Player::new("passer", |_| None);
+    let mut players = [Player::new("first", |game| {
+        game.all_legal_moves().first().copied()
+    })];
+    let record = play_game(&mut players[0], &mut passer, None, adjudication).unwrap();
+    assert_eq!(record.result, GameResult::WhiteWins);
+    assert_eq!(record.moves.len(), 1);
+
+    // knights on b1 and f3 can both reach d2
+    let game = Game::from_fen("4k3/8/8/8/8/5N2/8/1N2K3 w - - 0 1").unwrap();
+    let nbd2 = Move::Normal {
+        from: Pos('b', 1),
+        to: Pos('d', 2),
+        promotion: None,
+    };
+    assert_eq!(game.to_san(nbd2), "Nbd2");
+    assert_eq!(game.parse_san("Nbd2"), Ok(nbd2));
+
+    // capturing on the promotion square, not pushing onto it
+    let mut game = Game::from_fen("n3k3/1P6/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let bxa8 = game.parse_san("bxa8=Q").unwrap();
+    assert_eq!(game.to_san(bxa8), "bxa8=Q");
+    game.make_move(bxa8).unwrap();
+    assert_eq!(
+        game.board.character_at(Pos('a', 8)),
+        Some(Character::Queen(Side::White))
+    );
+    assert_eq!(game.board.character_at(Pos('b', 8)), None);
+
+    // too slow for its clock
+    let mut slow = Player::new("slow", |game| {
+        std::thread::sleep(Duration::from_millis(30));
+        game.all_legal_moves().first().copied()
+    });
+    let mut first = Player::new("first", |game| game.all_legal_moves().first().copied());
+    let blitz = Adjudication {
+        time_control: Some(TimeControl {
+            base: Duration::from_millis(50),
+            increment: Duration::ZERO,
+        }),
+        ..adjudication
+    };
+    let record = play_game(&mut first, &mut slow, None, blitz).unwrap();
+    assert_eq!(record.result, GameResult::WhiteWins);
+    assert_eq!(record.termination, "slow lost on time");
+    assert_eq!(record.moves.len(), 3);
+
+    // shell scripts standing in for engines, `reply` is what they do on `go`
+    if cfg!(unix) {
+        let script = |reply: &str| {
+            format!(
+                "while read line; do case $line in \
+                 uci) echo 'id name mock'; echo uciok;; isready) echo readyok;; \
+                 go*) {reply};; quit) exit;; esac; done"
+            )
+        };
+        let uci = |reply: &str| {
+            Player::uci(
+                "sh",
+                &["-c", &script(reply)],
+                "movetime 10",
+                Duration::from_secs(5),
+            )
+            .unwrap()
+        };
+        // mating with the queen on a2
+        let mut engine = uci("echo 'bestmove a2a8'");
+        assert_eq!(engine.name, "mock");
+        let record = play_game(&mut engine, &mut first, Some(&openings[0]), blitz).unwrap();
+        assert_eq!(record.moves, ["Qa8#"]);
+
+        // underpromotions are played, a reply that isn't a move forfeits
+        let knight = Opening {
+            fen: Some("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1".to_owned()),
+            ..Opening::default()
+        };
+        let one_ply = Adjudication {
+            max_plies: Some(1),
+            ..adjudication
+        };
+        let mut engine = uci("echo 'bestmove b7b8n'");
+        let record = play_game(&mut engine, &mut first, Some(&knight), one_ply).unwrap();
+        assert_eq!(record.moves, ["b8=N"]);
+        let mut engine = uci("echo 'bestmove b7b8x'");
+        let record = play_game(&mut engine, &mut first, Some(&knight), one_ply).unwrap();
+        assert_eq!(record.result, GameResult::BlackWins);
+        assert_eq!(
+            record.termination,
+            "mock sent `bestmove b7b8x`, which isn't a move"
+        );
+
+        // `ucinewgame` comes before the game
+        let new_game = "while read line; do case $line in \
+            uci) echo uciok;; isready) echo readyok;; ucinewgame) new=1;; \
+            go*) [ \"$new\" ] && echo 'bestmove a2a8' || echo 'bestmove 0000';; \
+            quit) exit;; esac; done";
+        let mut engine = Player::uci(
+            "sh",
+            &["-c", new_game],
+            "movetime 10",
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        let record = play_game(&mut engine, &mut first, Some(&openings[0]), blitz).unwrap();
+        assert_eq!(record.moves, ["Qa8#"]);
+
+        // a hung engine is flagged at the deadline, and forfeits its next game at once
+        let mut hung = Player::uci(
+            "sh",
+            &["-c", &script(":")],
+            "movetime 10",
+            Duration::from_millis(200),
+        )
+        .unwrap();
+        let record = play_game(&mut hung, &mut first, Some(&openings[0]), adjudication).unwrap();
+        assert_eq!(record.termination, "mock didn't answer in time");
+        let started = Instant::now();
+        let record = play_game(&mut hung, &mut first, None, adjudication).unwrap();
+        assert_eq!(record.result, GameResult::BlackWins);
+        assert!(started.elapsed() < Duration::from_millis(200));
+    }
+
+    // PGN openings are part of the movetext, the players go on from there
+    let openings = pgn_openings("[Opening \"Open Game\"]\n1. e4 e5 *\n\n1. d4 d5 *").unwrap();
+    let mut players = [
+        Player::new("first", |game| game.all_legal_moves().first().copied()),
+        Player::new("last", |game| game.all_legal_moves().last().copied()),
+    ];
+    let records = round_robin(&mut players, &openings, adjudication).unwrap();
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[0].moves[..2], ["e4", "e5"]);
+    assert_eq!(records[0].opening.as_deref(), Some("Open Game"));
+    assert_eq!(records[0].fen, None);
+    // the ECO opening the game reached names it in the PGN
+    assert_eq!(records[0].eco.map(|eco| eco.code), Some("C20"));
+    let pgn = records[0].to_pgn(1);
+    assert!(pgn.contains("[ECO \"C20\"]\n[Opening \"King's Pawn Game\"]\n[Termination"));
+    assert!(pgn.contains("\n1. e4 e5 2. "));
+    assert_eq!(records[3].moves[..2], ["d4", "d5"]);
+
+    // the same games on two threads
+    let first = || Player::new("first", |game| game.all_legal_moves().first().copied());
+    let last = || Player::new("last", |game| game.all_legal_moves().last().copied());
+    let concurrent = round_robin_concurrent(&[&first, &last], &openings, adjudication, 2);
+    assert_eq!(concurrent.unwrap(), records);
+
+    // told about each new game
+    let games = Rc::new(RefCell::new(0));
+    let counter = Rc::clone(&games);
+    let counted = first().on_new_game(move || *counter.borrow_mut() += 1);
+    let mut players = [counted, last()];
+    round_robin(&mut players, &openings, adjudication).unwrap();
+    assert_eq!(*games.borrow(), 4);
+}
@@ -0,0 +1,246 @@
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+use crate::moves::Move;
+
+pub const DEFAULT_HASH_MB: usize = 16;
+pub const MAX_HASH_MB: usize = 65536;
+
+// scores beyond `MATE_SCORE - MAX_PLY` are mates, counted in plies from the root
+pub const MATE_SCORE: i32 = 30000;
+pub const MAX_PLY: i32 = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    Exact,
+    // score is at least this, the search failed high
+    Lower,
+    // score is at most this, the search failed low
+    Upper,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub depth: u8,
+    pub bound: Bound,
+    // relative to the probing node, see `TranspositionTable::probe`
+    pub score: i32,
+    pub best_move: Option<Move>,
+}
+
+// key and data are stored xored, so a slot torn by two threads writing at once fails the
+// key check instead of returning another position's data
+#[derive(Default)]
+struct Slot {
+    check: AtomicU64,
+    data: AtomicU64,
+}
+
+// shared by reference between search threads, only resizing and clearing need `&mut`
+pub struct TranspositionTable {
+    slots: Vec<Slot>,
+    generation: AtomicU8,
+}
+
+impl TranspositionTable {
+    pub fn new(size_mb: usize) -> Self {
+        let mut table = TranspositionTable {
+            slots: Vec::new(),
+            generation: AtomicU8::new(0),
+        };
+        table.resize(size_mb);
+        table
+    }
+
+    // as set by the UCI `Hash` option, clamped to 1..=MAX_HASH_MB, drops all entries
+    pub fn resize(&mut self, size_mb: usize) {
+        let size_mb = size_mb.clamp(1, MAX_HASH_MB);
+        let len = size_mb * 1024 * 1024 / std::mem::size_of::<Slot>();
+        self.slots = (0..len).map(|_| Slot::default()).collect();
+    }
+
+    pub fn size_mb(&self) -> usize {
+        self.slots.len() * std::mem::size_of::<Slot>() / (1024 * 1024)
+    }
+
+    pub fn clear(&mut self) {
+        for slot in self.slots.iter_mut() {
+            *slot = Slot::default();
+        }
+        *self.generation.get_mut() = 0;
+    }
+
+    // called once per search, entries of older searches get replaced first
+    pub fn new_search(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn slot(&self, key: u64) -> &Slot {
+        // multiply-shift maps the key onto the table without a modulo
+        let index = ((key as u128 * self.slots.len() as u128) >> 64) as usize;
+        &self.slots[index]
+    }
+
+    // mate scores come back relative to `ply`
+    pub fn probe(&self, key: u64, ply: i32) -> Option<Entry> {
+        let slot = self.slot(key);
+        let data = slot.data.load(Ordering::Relaxed);
+        if data == 0 || slot.check.load(Ordering::Relaxed) ^ data != key {
+            return None;
+        }
+        let (mut entry, _) = unpack(data);
+        entry.score = score_from_table(entry.score, ply);
+        Some(entry)
+    }
+
+    // always replaces entries of the same position or older searches, otherwise only
+    // when searched at least as deep
+    pub fn store(&self, key: u64, ply: i32, mut entry: Entry) {
+        let slot = self.slot(key);
+        let generation = self.generation.load(Ordering::Relaxed) & 63;
+        let old = slot.data.load(Ordering::Relaxed);
+        if old != 0 {
+            let same_key = slot.check.load(Ordering::Relaxed) ^ old == key;
+            let (old_entry, old_generation) = unpack(old);
+            if !same_key && old_generation == generation && old_entry.depth > entry.depth {
+                return;
+            }
+            // keep the best move when a shallower search of the position found none
+            if same_key && entry.best_move.is_none() {
+                entry.best_move = old_entry.best_move;
+            }
+        }
+        entry.score = score_to_table(entry.score, ply);
+        let data = pack(entry, generation);
+        slot.check.store(key ^ data, Ordering::Relaxed);
+        slot.data.store(data, Ordering::Relaxed);
+    }
+
+    // permille of slots filled by the current search, as UCI reports `hashfull`
+    pub fn hashfull(&self) -> usize {
+        let generation = self.generation.load(Ordering::Relaxed) & 63;
+        let sample = self.slots.len().min(1000);
+        let used = self.slots[..sample]
+            .iter()
+            .filter(|slot| {
+                let data = slot.data.load(Ordering::Relaxed);
+                data != 0 && unpack(data).1 == generation
+            })
+            .count();
+        used * 1000 / sample.max(1)
+    }
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_HASH_MB)
+    }
+}
+
+// mates are stored as distance from the stored node, so they stay right when the position
+// is reached again at another ply
+pub fn score_to_table(score: i32, ply: i32) -> i32 {
+    if score >= MATE_SCORE - MAX_PLY {
+        score + ply
+    } else if score <= -MATE_SCORE + MAX_PLY {
+        score - ply
+    } else {
+        score
+    }
+}
+
+pub fn score_from_table(score: i32, ply: i32) -> i32 {
+    if score >= MATE_SCORE - MAX_PLY {
+        score - ply
+    } else if score <= -MATE_SCORE + MAX_PLY {
+        score + ply
+    } else {
+        score
+    }
+}
+
+// move 16 bits, score 16, depth 8, bound 2, generation 6 and a set top bit so that a
+// filled slot is never 0
+fn pack(entry: Entry, generation: u8) -> u64 {
+    let best_move = entry.best_move.map_or(0xffff, |mv| mv.encode()) as u64;
+    let score = entry.score.clamp(i16::MIN as i32, i16::MAX as i32) as i16 as u16 as u64;
+    let bound = match entry.bound {
+        Bound::Exact => 0,
+        Bound::Lower => 1,
+        Bound::Upper => 2,
+    };
+    best_move
+        | score << 16
+        | (entry.depth as u64) << 32
+        | bound << 40
+        | ((generation & 63) as u64) << 42
+        | 1 << 63
+}
+
+fn unpack(data: u64) -> (Entry, u8) {
+    let bound = match (data >> 40) & 3 {
+        0 => Bound::Exact,
+        1 => Bound::Lower,
+        _ => Bound::Upper,
+    };
+    let entry = Entry {
+        depth: (data >> 32) as u8,
+        bound,
+        score: (data >> 16) as u16 as i16 as i32,
+        best_move: Move::decode(data as u16),
+    };
+    (entry, ((data >> 42) & 63) as u8)
+}
+
+#[test]
+fn transposition_test() {
+    use crate::{game::Game, position::Pos};
+
+    let mut table = TranspositionTable::new(1);
+    assert_eq!(table.size_mb(), 1);
+    let game = Game::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    let key = game.zobrist_key();
+    assert_eq!(table.probe(key, 0), None);
+
+    let best_move = Some(Move::Normal {
+        from: Pos('h', 1),
+        to: Pos('h', 8),
+        promotion: None,
+    });
+    let entry = Entry {
+        depth: 6,
+        bound: Bound::Lower,
+        score: -150,
+        best_move,
+    };
+    table.store(key, 0, entry);
+    assert_eq!(table.probe(key, 0), Some(entry));
+    assert_eq!(table.probe(key ^ 1, 0), None);
+
+    // a shallower search of another position in the same slot doesn't evict it
+    let colliding = (1..)
+        .map(|n: u64| key.wrapping_add(n))
+        .find(|&other| std::ptr::eq(table.slot(other), table.slot(key)))
+        .unwrap();
+    let shallow = Entry { depth: 2, ..entry };
+    table.store(colliding, 0, shallow);
+    assert_eq!(table.probe(key, 0), Some(entry));
+    table.new_search();
+    table.store(colliding, 0, shallow);
+    assert_eq!(table.probe(key, 0), None);
+
+    // mate in 3 from a node at ply 4 is mate in 5 from the root at ply 2
+    let mate = Entry {
+        depth: 3,
+        bound: Bound::Exact,
+        score: MATE_SCORE - 7,
+        best_move: None,
+    };
+    table.store(key, 4, mate);
+    assert_eq!(table.probe(key, 2).unwrap().score, MATE_SCORE - 5);
+    assert_eq!(table.probe(key, 4).unwrap().score, MATE_SCORE - 7);
+
+    table.clear();
+    assert_eq!(table.probe(key, 0), None);
+    table.resize(4);
+    assert_eq!(table.size_mb(), 4);
+}
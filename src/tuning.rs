@@ -0,0 +1,285 @@
+use crate::{
+    epd::Epd,
+    errors::GameError,
+    game::Game,
+    moves::Move,
+    pieces::{Character, Side},
+};
+
+// terms of the linear evaluation being tuned, material per character and a bonus per
+// square attacked by knights, bishops, rooks and queens
+pub const TERMS: [&str; 6] = ["pawn", "knight", "bishop", "rook", "queen", "mobility"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Weights(pub [i32; 6]);
+
+impl Default for Weights {
+    // material as `Character::value` has it, mobility left for the tuner
+    fn default() -> Self {
+        let value = |character: Character| character.value();
+        Weights([
+            value(Character::Pawn(Side::White)),
+            value(Character::Knight(Side::White)),
+            value(Character::Bishop(Side::White)),
+            value(Character::Rook(Side::White)),
+            value(Character::Queen(Side::White)),
+            0,
+        ])
+    }
+}
+
+impl Weights {
+    // centipawns for white
+    pub fn evaluate(&self, game: &Game) -> i32 {
+        self.score(&features(game))
+    }
+
+    fn score(&self, features: &[i32; 6]) -> i32 {
+        self.0.iter().zip(features).map(|(w, f)| w * f).sum()
+    }
+
+    // `name = value` lines
+    pub fn to_config(&self) -> String {
+        TERMS
+            .iter()
+            .zip(self.0)
+            .map(|(name, weight)| format!("{name} = {weight}\n"))
+            .collect()
+    }
+
+    // terms missing from the config keep their default
+    pub fn from_config(config: &str) -> Result<Weights, GameError> {
+        let mut weights = Weights::default();
+        for line in config.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || GameError::InvalidNotation(line.to_owned());
+            let (name, weight) = line.split_once('=').ok_or_else(invalid)?;
+            let term = TERMS
+                .iter()
+                .position(|term| *term == name.trim())
+                .ok_or_else(invalid)?;
+            weights.0[term] = weight.trim().parse().map_err(|_| invalid())?;
+        }
+        Ok(weights)
+    }
+
+    // constants to paste into an evaluation module
+    pub fn to_rust(&self) -> String {
+        TERMS
+            .iter()
+            .zip(self.0)
+            .map(|(name, weight)| format!("pub const {}: i32 = {weight};\n", name.to_uppercase()))
+            .collect()
+    }
+}
+
+// white's terms minus black's, in `TERMS` order
+fn features(game: &Game) -> [i32; 6] {
+    let mut features = [0; 6];
+    for side in [Side::White, Side::Black] {
+        let sign = if side == Side::White { 1 } else { -1 };
+        for pos in game.board.positions(side) {
+            let term = match game.board.character_at(pos) {
+                Some(Character::Pawn(_)) => 0,
+                Some(Character::Knight(_)) => 1,
+                Some(Character::Bishop(_)) => 2,
+                Some(Character::Rook(_)) => 3,
+                Some(Character::Queen(_)) => 4,
+                _ => continue,
+            };
+            features[term] += sign;
+            if term > 0 {
+                let mobility = game
+                    .board
+                    .attacks_from(pos)
+                    .into_iter()
+                    .filter(|&to| game.board.character_at(to).is_none_or(|c| c.side() != side))
+                    .count();
+                features[5] += sign * mobility as i32;
+            }
+        }
+    }
+    features
+}
+
+// neither in check nor with a capture winning material, so the static evaluation can be
+// compared with the result
+pub fn is_quiet(game: &Game) -> bool {
+    let side = game.whose_turn();
+    !game.is_in_check()
+        && game.board.positions(side).into_iter().all(|from| {
+            game.board.attacks_from(from).into_iter().all(|to| {
+                game.board.character_at(to).is_none_or(|c| {
+                    c.side() == side
+                        || game.see(Move::Normal {
+                            from,
+                            to,
+                            promotion: None,
+                        }) <= 0
+                })
+            })
+        })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelledPosition {
+    features: [i32; 6],
+    // for white, 1 won, 0.5 drawn and 0 lost
+    pub result: f64,
+}
+
+// one position per line, either `<fen> [1.0]` or EPD with the result as `c9 "1-0";`,
+// positions which aren't quiet are left out
+pub fn parse_positions(text: &str) -> Result<Vec<LabelledPosition>, GameError> {
+    let mut positions = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || GameError::InvalidNotation(line.to_owned());
+        let (game, result) = match line.rsplit_once('[') {
+            Some((fen, result)) => {
+                let result = result.trim_end_matches(']').trim();
+                (Game::from_fen(fen.trim())?, result.to_owned())
+            }
+            None => {
+                let epd = Epd::parse(line)?;
+                let result = epd.comment(9).ok_or_else(invalid)?.to_owned();
+                (epd.game()?, result)
+            }
+        };
+        let result = match result.as_str() {
+            "1-0" => 1.,
+            "0-1" => 0.,
+            "1/2-1/2" => 0.5,
+            result => result.parse().map_err(|_| invalid())?,
+        };
+        if is_quiet(&game) {
+            positions.push(LabelledPosition {
+                features: features(&game),
+                result,
+            });
+        }
+    }
+    Ok(positions)
+}
+
+// Texel's method, the evaluation is mapped to an expected result by a sigmoid and the mean
+// squared error against the game results is minimized
+pub struct Tuner {
+    positions: Vec<LabelledPosition>,
+    // scales the sigmoid, see `Tuner::fit_scale`
+    pub scale: f64,
+}
+
+impl Tuner {
+    pub fn new(positions: Vec<LabelledPosition>) -> Tuner {
+        Tuner {
+            positions,
+            scale: 1.,
+        }
+    }
+
+    pub fn error(&self, weights: &Weights) -> f64 {
+        let total: f64 = self
+            .positions
+            .iter()
+            .map(|position| {
+                let eval = weights.score(&position.features) as f64;
+                let expected = 1. / (1. + 10f64.powf(-self.scale * eval / 400.));
+                (position.result - expected).powi(2)
+            })
+            .sum();
+        total / self.positions.len().max(1) as f64
+    }
+
+    // the scale best fitting `weights`, searched once before tuning so that the untuned
+    // evaluation already predicts results as well as it can
+    pub fn fit_scale(&mut self, weights: &Weights) -> f64 {
+        let mut best = (self.error(weights), self.scale);
+        for step in [0.1, 0.01, 0.001] {
+            let around = best.1;
+            for i in -10..=10 {
+                self.scale = (around + i as f64 * step).max(step);
+                let error = self.error(weights);
+                if error < best.0 {
+                    best = (error, self.scale);
+                }
+            }
+        }
+        self.scale = best.1;
+        self.scale
+    }
+
+    // local search, each weight is moved by `step` either way while the error drops, the
+    // step halving down to 1 once no move helps
+    pub fn tune(&self, weights: Weights, max_rounds: usize) -> Weights {
+        let mut best = (self.error(&weights), weights);
+        let mut step = 16;
+        for _ in 0..max_rounds {
+            let mut improved = false;
+            for term in 0..TERMS.len() {
+                for delta in [step, -step] {
+                    let mut candidate = best.1;
+                    candidate.0[term] += delta;
+                    let error = self.error(&candidate);
+                    if error < best.0 {
+                        best = (error, candidate);
+                        improved = true;
+                        break;
+                    }
+                }
+            }
+            if !improved {
+                if step == 1 {
+                    break;
+                }
+                step /= 2;
+            }
+        }
+        best.1
+    }
+}
+
+#[test]
+fn tuning_test() {
+    let positions = parse_positions(
+        "# a knight up wins, a pawn up draws\n\
+         4k3/8/8/8/8/8/3N4/4K3 w - - [1.0]\n\
+         4k3/3n4/8/8/8/8/8/4K3 w - - [0.0]\n\
+         4k3/8/8/8/8/8/3P4/4K3 w - - c9 \"1/2-1/2\";\n\
+         4k3/3p4/8/8/8/8/8/4K3 w - - [0.5]\n\
+         4k3/8/8/8/3n4/8/8/3QK3 w - - [1.0]\n",
+    )
+    .unwrap();
+    // the queen can take the undefended knight, so the last one isn't quiet
+    assert_eq!(positions.len(), 4);
+    assert_eq!(positions[2].result, 0.5);
+
+    let mut tuner = Tuner::new(positions);
+    let weights = Weights::default();
+    tuner.fit_scale(&weights);
+    let tuned = tuner.tune(weights, 200);
+    assert!(tuner.error(&tuned) < tuner.error(&weights));
+    // pawns are worth less once a pawn up draws
+    assert!(tuned.0[0] < weights.0[0]);
+
+    let config = tuned.to_config();
+    assert!(config.starts_with(&format!("pawn = {}\n", tuned.0[0])));
+    assert_eq!(Weights::from_config(&config), Ok(tuned));
+    assert_eq!(
+        Weights::from_config("queen = 950"),
+        Ok(Weights([100, 300, 300, 500, 950, 0]))
+    );
+    assert!(Weights::from_config("king = 1").is_err());
+    assert!(weights.to_rust().contains("pub const ROOK: i32 = 500;\n"));
+
+    let game = Game::from_fen("4k3/8/8/8/8/8/3N4/4K3 w - - 0 1").unwrap();
+    // the knight attacks b1, b3, c4, e4, f1 and f3
+    assert_eq!(
+        Weights([100, 300, 300, 500, 900, 4]).evaluate(&game),
+        300 + 4 * 6
+    );
+}
@@ -0,0 +1,185 @@
+use crate::{
+    errors::SetupError,
+    game::Game,
+    pieces::{Character, Side},
+    position::Pos,
+};
+
+#[cfg(test)]
+use crate::variants::{Horde, RacingKings};
+
+// standard setup rules, variants pick the ones which apply to them
+pub fn validate(game: &Game) -> Vec<SetupError> {
+    let mut problems = Vec::new();
+    for side in [Side::White, Side::Black] {
+        problems.extend(kings(game, side, 1));
+        problems.extend(pawns_on_ranks(game, side, &[1, 8]));
+        problems.extend(material(game, side));
+    }
+    problems.extend(checks(game));
+    problems.extend(castling(game));
+    problems.extend(en_passant(game));
+    problems
+}
+
+pub fn kings(game: &Game, side: Side, expected: usize) -> Option<SetupError> {
+    let kings = game
+        .board
+        .positions(side)
+        .into_iter()
+        .filter(|&pos| game.board.character_at(pos) == Some(Character::King(side)))
+        .count();
+    if kings < expected {
+        Some(SetupError::MissingKing(side))
+    } else if kings > expected {
+        Some(SetupError::TooManyKings(side))
+    } else {
+        None
+    }
+}
+
+pub fn pawns_on_ranks(game: &Game, side: Side, ranks: &[u8]) -> Vec<SetupError> {
+    game.board
+        .positions(side)
+        .into_iter()
+        .filter(|&pos| game.board.character_at(pos) == Some(Character::Pawn(side)))
+        .filter(|pos| ranks.contains(&pos.rank()))
+        .map(SetupError::PawnOnBackRank)
+        .collect()
+}
+
+pub fn material(game: &Game, side: Side) -> Vec<SetupError> {
+    let mut problems = Vec::new();
+    let characters: Vec<Character> = game
+        .board
+        .positions(side)
+        .into_iter()
+        .filter_map(|pos| game.board.character_at(pos))
+        .collect();
+    let count = |character: Character| characters.iter().filter(|&&c| c == character).count();
+
+    let pawns = count(Character::Pawn(side));
+    if pawns > 8 {
+        problems.push(SetupError::TooManyPawns(side));
+    }
+    if characters.len() > 16 {
+        problems.push(SetupError::TooManyPieces(side));
+    }
+    let promoted = count(Character::Queen(side)).saturating_sub(1)
+        + count(Character::Rook(side)).saturating_sub(2)
+        + count(Character::Bishop(side)).saturating_sub(2)
+        + count(Character::Knight(side)).saturating_sub(2);
+    if promoted > 8usize.saturating_sub(pawns) {
+        problems.push(SetupError::TooManyPromotedPieces(side));
+    }
+    problems
+}
+
+pub fn checks(game: &Game) -> Vec<SetupError> {
+    let mut problems = Vec::new();
+    let side = game.whose_turn();
+    if game.variant().is_in_check(&game.board, side.opponent()) {
+        problems.push(SetupError::OpponentInCheck);
+    }
+    if let Some(king) = game.board.king_position(side) {
+        if game.board.attackers(king, side.opponent()).len() > 2 {
+            problems.push(SetupError::ImpossibleCheck);
+        }
+    }
+    problems
+}
+
+pub fn castling(game: &Game) -> Option<SetupError> {
+    let mut consistent = game.castling;
+    consistent.retain_consistent(&game.board);
+    if consistent == game.castling {
+        None
+    } else {
+        Some(SetupError::InvalidCastlingRights)
+    }
+}
+
+// en passant square must sit behind an opponent pawn which could just have double pushed
+pub fn en_passant(game: &Game) -> Option<SetupError> {
+    let pos = game.en_passant?;
+    let (rank, d_rank) = match game.whose_turn() {
+        Side::White => (6, 1),
+        Side::Black => (3, -1),
+    };
+    let pusher = game.whose_turn().opponent();
+    let is_empty = |pos: Option<Pos>| pos.is_some_and(|pos| game.board.character_at(pos).is_none());
+    let is_valid = pos.rank() == rank
+        && is_empty(Some(pos))
+        && is_empty(pos.d_pos(0, d_rank))
+        && pos
+            .d_pos(0, -d_rank)
+            .is_some_and(|pos| game.board.character_at(pos) == Some(Character::Pawn(pusher)));
+    if is_valid {
+        None
+    } else {
+        Some(SetupError::InvalidEnPassant(pos))
+    }
+}
+
+#[test]
+fn validate_test() {
+    let mut game = Game::new();
+    game.start();
+    assert!(game.validate().is_empty());
+    game.pick("e2").unwrap().place_at(&mut game, "e4").unwrap();
+    assert_eq!(game.en_passant, Some(Pos('e', 3)));
+    assert!(game.validate().is_empty());
+    game.pick("g8").unwrap().place_at(&mut game, "f6").unwrap();
+    assert_eq!(game.en_passant, None);
+
+    let game = Game::from_fen("4k3/8/8/8/8/8/8/4K2K w - - 0 1").unwrap();
+    assert_eq!(game.validate(), vec![SetupError::TooManyKings(Side::White)]);
+
+    let game = Game::from_fen("8/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(game.validate(), vec![SetupError::MissingKing(Side::Black)]);
+
+    let game = Game::from_fen("4k3/8/8/8/8/8/8/P3K3 w - - 0 1").unwrap();
+    assert_eq!(
+        game.validate(),
+        vec![SetupError::PawnOnBackRank(Pos('a', 1))]
+    );
+
+    // black is in check while white is to move
+    let game = Game::from_fen("4k3/8/8/8/8/8/8/R3K2R w - - 0 1").unwrap();
+    assert_eq!(game.validate(), vec![]);
+    let game = Game::from_fen("4k3/8/8/8/8/8/8/4RK2 w - - 0 1").unwrap();
+    assert_eq!(game.validate(), vec![SetupError::OpponentInCheck]);
+
+    let game = Game::from_fen("4k3/8/8/8/8/5n2/3p2n1/4K3 w - - 0 1").unwrap();
+    assert_eq!(game.validate(), vec![SetupError::ImpossibleCheck]);
+
+    let game = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w KQ - 0 1").unwrap();
+    assert_eq!(game.validate(), vec![SetupError::InvalidCastlingRights]);
+
+    let game = Game::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+    assert!(game.validate().is_empty());
+    let game = Game::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - e6 0 1").unwrap();
+    assert_eq!(
+        game.validate(),
+        vec![SetupError::InvalidEnPassant(Pos('e', 6))]
+    );
+
+    let game = Game::from_fen("QQQQ4/8/8/7k/8/8/PPPPPPP1/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        game.validate(),
+        vec![SetupError::TooManyPromotedPieces(Side::White)]
+    );
+}
+
+#[test]
+fn validate_variants_test() {
+    let mut game = Game::with_variant(Horde);
+    game.start();
+    assert!(game.validate().is_empty());
+
+    let mut game = Game::with_variant(RacingKings);
+    game.start();
+    assert!(game.validate().is_empty());
+    let game = Game::from_fen_with("k7/8/8/8/8/8/8/R1K5 b - - 0 1", RacingKings).unwrap();
+    assert_eq!(game.validate(), vec![SetupError::ImpossibleCheck]);
+}
@@ -0,0 +1,682 @@
+use crate::{
+    chess_board::ChessBoard,
+    errors::{GameError, SetupError},
+    game::{EndReason, Game, Outcome},
+    moves::{dirs, Move},
+    pieces::{Character, Side},
+    position::Pos,
+    validation,
+};
+
+// rules which differ from standard chess, every hook defaults to standard rules, `Send`
+// and `Sync` so games can go to search threads
+pub trait Variant: VariantClone + Send + Sync {
+    fn name(&self) -> &'static str;
+
+    // initial position placed by `Game::start`
+    fn setup(&self) -> ChessBoard {
+        let mut board = ChessBoard::new();
+        board.place_character_init();
+        board
+    }
+
+    // whether captured pieces go to a pocket and can be dropped back
+    fn drops(&self) -> bool {
+        false
+    }
+
+    fn is_in_check(&self, board: &ChessBoard, side: Side) -> bool {
+        board.is_in_check(side)
+    }
+
+    // `after` is the board once `mv` and its side effects are applied
+    fn is_legal(&self, game: &Game, _mv: Move, after: &ChessBoard) -> bool {
+        !self.is_in_check(after, game.whose_turn())
+    }
+
+    // whether kings may castle, the rights are still read from FEN otherwise
+    fn castling(&self) -> bool {
+        true
+    }
+
+    // pieces a pawn reaching the last rank may become, the first is the default
+    fn promotions(&self, side: Side) -> Vec<Character> {
+        vec![
+            Character::Queen(side),
+            Character::Rook(side),
+            Character::Bishop(side),
+            Character::Knight(side),
+        ]
+    }
+
+    // side effects of a capture on `at`, applied after the capturing piece landed
+    fn after_capture(&self, _board: &mut ChessBoard, _at: Pos) {}
+
+    // bookkeeping once `side` has made a move
+    fn record_move(&mut self, _board: &ChessBoard, _side: Side) {}
+
+    fn outcome(&self, game: &Game) -> Option<Outcome> {
+        standard_outcome(game)
+    }
+
+    // setup rules checked by `Game::validate`
+    fn validate(&self, game: &Game) -> Vec<SetupError> {
+        validation::validate(game)
+    }
+
+    // extra FEN field, ie. remaining checks in three-check
+    fn fen_field(&self) -> Option<String> {
+        None
+    }
+
+    fn set_fen_field(&mut self, field: &str) -> Result<(), GameError> {
+        Err(GameError::InvalidFen(field.to_owned()))
+    }
+}
+
+// lets `Game` be cloned with its boxed variant, implemented for every `Clone` variant
+pub trait VariantClone {
+    fn clone_box(&self) -> Box<dyn Variant>;
+}
+
+impl<T: Variant + Clone + 'static> VariantClone for T {
+    fn clone_box(&self) -> Box<dyn Variant> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Variant> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+// checkmate and stalemate for side to move
+pub fn standard_outcome(game: &Game) -> Option<Outcome> {
+    if game.has_legal_move() {
+        return None;
+    }
+    let side = game.whose_turn();
+    if game.is_in_check() {
+        Some(Outcome {
+            winner: Some(side.opponent()),
+            reason: EndReason::Checkmate,
+        })
+    } else {
+        Some(Outcome {
+            winner: None,
+            reason: EndReason::Stalemate,
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Standard;
+
+impl Variant for Standard {
+    fn name(&self) -> &'static str {
+        "standard"
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Crazyhouse;
+
+impl Variant for Crazyhouse {
+    fn name(&self) -> &'static str {
+        "crazyhouse"
+    }
+
+    fn drops(&self) -> bool {
+        true
+    }
+
+    // captured pieces change colour, so there are no material limits
+    fn validate(&self, game: &Game) -> Vec<SetupError> {
+        let mut problems = Vec::new();
+        for side in [Side::White, Side::Black] {
+            problems.extend(validation::kings(game, side, 1));
+            problems.extend(validation::pawns_on_ranks(game, side, &[1, 8]));
+        }
+        problems.extend(validation::checks(game));
+        problems.extend(validation::castling(game));
+        problems.extend(validation::en_passant(game));
+        problems
+    }
+}
+
+// captures explode, removing the capturer and every non pawn around
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Atomic;
+
+impl Variant for Atomic {
+    fn name(&self) -> &'static str {
+        "atomic"
+    }
+
+    fn is_in_check(&self, board: &ChessBoard, side: Side) -> bool {
+        let Some(king) = board.king_position(side) else {
+            return false;
+        };
+        // connected kings can't be checked, kings can't capture
+        if let Some(other) = board.king_position(side.opponent()) {
+            if king.is_adjacent(other) {
+                return false;
+            }
+        }
+        board
+            .attackers(king, side.opponent())
+            .into_iter()
+            .any(|pos| !matches!(board.character_at(pos), Some(Character::King(_))))
+    }
+
+    fn is_legal(&self, game: &Game, mv: Move, after: &ChessBoard) -> bool {
+        let side = game.whose_turn();
+        if let Move::Normal { from, to, .. } = mv {
+            let is_king = matches!(game.board.character_at(from), Some(Character::King(_)));
+            if is_king && game.board.character_at(to).is_some() {
+                return false;
+            }
+        }
+        if after.king_position(side).is_none() {
+            return false;
+        }
+        if after.king_position(side.opponent()).is_none() {
+            return true;
+        }
+        !self.is_in_check(after, side)
+    }
+
+    fn after_capture(&self, board: &mut ChessBoard, at: Pos) {
+        let _ = board.pick_character(at);
+        board.set_promoted(at, false);
+        let around = [
+            dirs::TOP_LEFT,
+            dirs::TOP,
+            dirs::TOP_RIGHT,
+            dirs::MID_LEFT,
+            dirs::MID_RIGHT,
+            dirs::BOT_LEFT,
+            dirs::BOT,
+            dirs::BOT_RIGHT,
+        ];
+        for (d_file, d_rank) in around {
+            if let Some(pos) = at.d_pos(d_file, d_rank) {
+                if !matches!(board.character_at(pos), None | Some(Character::Pawn(_))) {
+                    let _ = board.pick_character(pos);
+                    board.set_promoted(pos, false);
+                }
+            }
+        }
+    }
+
+    fn outcome(&self, game: &Game) -> Option<Outcome> {
+        let side = game.whose_turn();
+        if game.board.king_position(side).is_none() {
+            return Some(Outcome {
+                winner: Some(side.opponent()),
+                reason: EndReason::KingExploded,
+            });
+        }
+        standard_outcome(game)
+    }
+}
+
+// bringing the king to one of the four centre squares wins
+#[derive(Debug, Default, Clone, Copy)]
+pub struct KingOfTheHill;
+
+impl KingOfTheHill {
+    pub const HILL: [Pos; 4] = [Pos('d', 4), Pos('e', 4), Pos('d', 5), Pos('e', 5)];
+}
+
+impl Variant for KingOfTheHill {
+    fn name(&self) -> &'static str {
+        "kingofthehill"
+    }
+
+    fn outcome(&self, game: &Game) -> Option<Outcome> {
+        for side in [Side::White, Side::Black] {
+            if let Some(king) = game.board.king_position(side) {
+                if Self::HILL.contains(&king) {
+                    return Some(Outcome {
+                        winner: Some(side),
+                        reason: EndReason::KingOfTheHill,
+                    });
+                }
+            }
+        }
+        standard_outcome(game)
+    }
+}
+
+// giving the third check wins, counts are kept as checks given by white and black
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ThreeCheck {
+    pub checks: [u8; 2],
+}
+
+impl ThreeCheck {
+    pub fn checks_given(&self, side: Side) -> u8 {
+        match side {
+            Side::White => self.checks[0],
+            Side::Black => self.checks[1],
+        }
+    }
+}
+
+impl Variant for ThreeCheck {
+    fn name(&self) -> &'static str {
+        "threecheck"
+    }
+
+    fn record_move(&mut self, board: &ChessBoard, side: Side) {
+        if self.is_in_check(board, side.opponent()) {
+            match side {
+                Side::White => self.checks[0] += 1,
+                Side::Black => self.checks[1] += 1,
+            }
+        }
+    }
+
+    fn outcome(&self, game: &Game) -> Option<Outcome> {
+        for side in [Side::White, Side::Black] {
+            if self.checks_given(side) >= 3 {
+                return Some(Outcome {
+                    winner: Some(side),
+                    reason: EndReason::ThreeChecks,
+                });
+            }
+        }
+        standard_outcome(game)
+    }
+
+    // remaining checks as `white+black`, ie. `3+3` at start
+    fn fen_field(&self) -> Option<String> {
+        let remaining = |side| 3u8.saturating_sub(self.checks_given(side));
+        Some(format!(
+            "{}+{}",
+            remaining(Side::White),
+            remaining(Side::Black)
+        ))
+    }
+
+    fn set_fen_field(&mut self, field: &str) -> Result<(), GameError> {
+        let invalid = || GameError::InvalidFen(field.to_owned());
+        let (white, black) = field.split_once('+').ok_or_else(invalid)?;
+        let parse = |remaining: &str| match remaining.parse::<u8>() {
+            Ok(remaining) if remaining <= 3 => Ok(3 - remaining),
+            _ => Err(invalid()),
+        };
+        self.checks = [parse(white)?, parse(black)?];
+        Ok(())
+    }
+}
+
+// captures are forced, the king is an ordinary piece and losing everything wins
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Antichess;
+
+impl Variant for Antichess {
+    fn name(&self) -> &'static str {
+        "antichess"
+    }
+
+    fn is_in_check(&self, _board: &ChessBoard, _side: Side) -> bool {
+        false
+    }
+
+    fn castling(&self) -> bool {
+        false
+    }
+
+    fn is_legal(&self, game: &Game, mv: Move, _after: &ChessBoard) -> bool {
+        if game.is_capture(mv) {
+            return true;
+        }
+        !game
+            .board
+            .positions(game.whose_turn())
+            .into_iter()
+            .any(|from| {
+                game.pseudo_moves(from).into_iter().any(|to| {
+                    game.is_capture(Move::Normal {
+                        from,
+                        to,
+                        promotion: None,
+                    })
+                })
+            })
+    }
+
+    // the king is an ordinary piece, so pawns may become one
+    fn promotions(&self, side: Side) -> Vec<Character> {
+        vec![
+            Character::Queen(side),
+            Character::Rook(side),
+            Character::Bishop(side),
+            Character::Knight(side),
+            Character::King(side),
+        ]
+    }
+
+    fn outcome(&self, game: &Game) -> Option<Outcome> {
+        let side = game.whose_turn();
+        if game.board.positions(side).is_empty() {
+            return Some(Outcome {
+                winner: Some(side),
+                reason: EndReason::PiecesLost,
+            });
+        }
+        if !game.has_legal_move() {
+            return Some(Outcome {
+                winner: Some(side),
+                reason: EndReason::Stalemate,
+            });
+        }
+        None
+    }
+
+    // any number of kings, including none
+    fn validate(&self, game: &Game) -> Vec<SetupError> {
+        let mut problems = Vec::new();
+        for side in [Side::White, Side::Black] {
+            problems.extend(validation::pawns_on_ranks(game, side, &[1, 8]));
+        }
+        problems.extend(validation::en_passant(game));
+        problems
+    }
+}
+
+// white has 36 pawns and no king, black wins by capturing all of them
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Horde;
+
+impl Horde {
+    pub const PLACEMENT: &'static str =
+        "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP";
+}
+
+impl Variant for Horde {
+    fn name(&self) -> &'static str {
+        "horde"
+    }
+
+    fn setup(&self) -> ChessBoard {
+        ChessBoard::from_fen_placement(Self::PLACEMENT).unwrap()
+    }
+
+    fn outcome(&self, game: &Game) -> Option<Outcome> {
+        if game.board.positions(Side::White).is_empty() {
+            return Some(Outcome {
+                winner: Some(Side::Black),
+                reason: EndReason::PiecesLost,
+            });
+        }
+        standard_outcome(game)
+    }
+
+    fn validate(&self, game: &Game) -> Vec<SetupError> {
+        let mut problems = Vec::new();
+        problems.extend(validation::kings(game, Side::White, 0));
+        problems.extend(validation::kings(game, Side::Black, 1));
+        // the horde starts with pawns on the first rank
+        problems.extend(validation::pawns_on_ranks(game, Side::White, &[8]));
+        problems.extend(validation::pawns_on_ranks(game, Side::Black, &[1, 8]));
+        problems.extend(validation::material(game, Side::Black));
+        problems.extend(validation::checks(game));
+        problems.extend(validation::castling(game));
+        problems.extend(validation::en_passant(game));
+        problems
+    }
+}
+
+// checks are never allowed, first king on the eighth rank wins,
+// unless black's king gets there on the very next move
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RacingKings;
+
+impl RacingKings {
+    pub const PLACEMENT: &'static str = "8/8/8/8/8/8/krbnNBRK/qrbnNBRQ";
+
+    fn reached_goal(board: &ChessBoard, side: Side) -> bool {
+        board
+            .king_position(side)
+            .is_some_and(|king| king.rank() == 8)
+    }
+}
+
+impl Variant for RacingKings {
+    fn name(&self) -> &'static str {
+        "racingkings"
+    }
+
+    fn setup(&self) -> ChessBoard {
+        ChessBoard::from_fen_placement(Self::PLACEMENT).unwrap()
+    }
+
+    fn is_legal(&self, game: &Game, _mv: Move, after: &ChessBoard) -> bool {
+        let side = game.whose_turn();
+        !self.is_in_check(after, side) && !self.is_in_check(after, side.opponent())
+    }
+
+    fn outcome(&self, game: &Game) -> Option<Outcome> {
+        let white = Self::reached_goal(&game.board, Side::White);
+        let black = Self::reached_goal(&game.board, Side::Black);
+        let win = |side| {
+            Some(Outcome {
+                winner: Some(side),
+                reason: EndReason::GoalReached,
+            })
+        };
+        match (white, black) {
+            (true, true) => Some(Outcome {
+                winner: None,
+                reason: EndReason::GoalReached,
+            }),
+            (false, true) => win(Side::Black),
+            (true, false) => {
+                // black gets one more move to draw by reaching the goal as well
+                let catching_up = game.whose_turn() == Side::Black
+                    && game.board.king_position(Side::Black).is_some_and(|king| {
+                        game.legal_moves(king).iter().any(|pos| pos.rank() == 8)
+                    });
+                if catching_up {
+                    None
+                } else {
+                    win(Side::White)
+                }
+            }
+            (false, false) => standard_outcome(game),
+        }
+    }
+
+    fn validate(&self, game: &Game) -> Vec<SetupError> {
+        let mut problems = validation::validate(game);
+        if self.is_in_check(&game.board, game.whose_turn()) {
+            problems.push(SetupError::ImpossibleCheck);
+        }
+        problems
+    }
+}
+
+#[test]
+fn standard_test() {
+    let mut game = Game::new();
+    game.start();
+    game.pick("f2").unwrap().place_at(&mut game, "f3").unwrap();
+    game.pick("e7").unwrap().place_at(&mut game, "e5").unwrap();
+    game.pick("g2").unwrap().place_at(&mut game, "g4").unwrap();
+    game.pick("d8").unwrap().place_at(&mut game, "h4").unwrap();
+
+    assert!(game.is_game_over());
+    assert_eq!(
+        game.outcome(),
+        Some(Outcome {
+            winner: Some(Side::Black),
+            reason: EndReason::Checkmate
+        })
+    );
+    assert_eq!(game.pick("a2").unwrap_err(), GameError::GameOver);
+
+    // pinned bishop can't leave the king
+    let mut game = Game::from_fen("4r2k/8/8/8/8/8/4B3/4K3 w - - 0 1").unwrap();
+    let bishop = game.pick("e2").unwrap();
+    assert_eq!(
+        bishop.place_at(&mut game, "d3").unwrap_err().to_string(),
+        "Bishop on e2 cannot move to d3"
+    );
+    assert_eq!(
+        game.board.character_at(Pos('e', 2)),
+        Some(Character::Bishop(Side::White))
+    );
+    assert!(game.legal_moves(Pos('e', 2)).is_empty());
+}
+
+#[test]
+fn atomic_test() {
+    let mut game = Game::from_fen_with("4k3/3p4/8/8/8/8/8/3QK3 w - - 0 1", Atomic).unwrap();
+    game.pick("d1").unwrap().place_at(&mut game, "d7").unwrap();
+    assert_eq!(game.board.character_at(Pos('d', 7)), None);
+    assert_eq!(game.board.character_at(Pos('e', 8)), None);
+    assert_eq!(
+        game.outcome(),
+        Some(Outcome {
+            winner: Some(Side::White),
+            reason: EndReason::KingExploded
+        })
+    );
+
+    // kings can't capture
+    let mut game = Game::from_fen_with("7k/8/8/8/8/8/3p4/4K3 w - - 0 1", Atomic).unwrap();
+    let king = game.pick("e1").unwrap();
+    assert_eq!(
+        king.place_at(&mut game, "d2"),
+        Err(GameError::InvalidMove {
+            character: Character::King(Side::White),
+            from: Pos('e', 1),
+            to: Pos('d', 2)
+        })
+    );
+
+    // connected kings aren't in check
+    let game = Game::from_fen_with("8/8/8/8/8/8/r3k3/4K3 w - - 0 1", Atomic).unwrap();
+    assert!(!game.is_in_check());
+}
+
+#[test]
+fn king_of_the_hill_test() {
+    let mut game = Game::from_fen_with("7k/8/8/8/8/3K4/8/8 w - - 0 1", KingOfTheHill).unwrap();
+    game.pick("d3").unwrap().place_at(&mut game, "d4").unwrap();
+    assert_eq!(
+        game.outcome(),
+        Some(Outcome {
+            winner: Some(Side::White),
+            reason: EndReason::KingOfTheHill
+        })
+    );
+}
+
+#[test]
+fn three_check_test() {
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 1+3 0 1").unwrap();
+    assert_eq!(game.variant().name(), "threecheck");
+    assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/8/R3K3 w - - 1+3 0 1");
+
+    game.pick("a1").unwrap().place_at(&mut game, "a8").unwrap();
+    assert_eq!(game.to_fen(), "R3k3/8/8/8/8/8/8/4K3 b - - 0+3 1 1");
+    assert_eq!(
+        game.outcome(),
+        Some(Outcome {
+            winner: Some(Side::White),
+            reason: EndReason::ThreeChecks
+        })
+    );
+    assert!(Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 4+3 0 1").is_err());
+}
+
+#[test]
+fn antichess_test() {
+    let mut game = Game::from_fen_with("8/8/8/p7/8/8/8/R5N1 w - - 0 1", Antichess).unwrap();
+    // capture is forced
+    let knight = game.pick("g1").unwrap();
+    assert_eq!(
+        knight.place_at(&mut game, "f3").unwrap_err().to_string(),
+        "Knight on g1 cannot move to f3"
+    );
+
+    game.pick("a1").unwrap().place_at(&mut game, "a5").unwrap();
+    assert_eq!(
+        game.outcome(),
+        Some(Outcome {
+            winner: Some(Side::Black),
+            reason: EndReason::PiecesLost
+        })
+    );
+}
+
+#[test]
+fn horde_test() {
+    let mut game = Game::with_variant(Horde);
+    game.start();
+    let pawns = game.board.positions(Side::White);
+    assert_eq!(pawns.len(), 36);
+    assert!(game.board.king_position(Side::White).is_none());
+
+    // pawns on the first rank may double push
+    let mut game = Game::from_fen_with("4k3/8/8/8/8/8/8/P7 w - - 0 1", Horde).unwrap();
+    assert_eq!(
+        game.legal_moves(Pos('a', 1)),
+        vec![Pos('a', 2), Pos('a', 3)]
+    );
+    game.pick("a1").unwrap().place_at(&mut game, "a3").unwrap();
+
+    let mut game = Game::from_fen_with("r3k3/8/8/8/8/8/8/P7 b - - 0 1", Horde).unwrap();
+    game.pick("a8").unwrap().place_at(&mut game, "a1").unwrap();
+    assert_eq!(
+        game.outcome(),
+        Some(Outcome {
+            winner: Some(Side::Black),
+            reason: EndReason::PiecesLost
+        })
+    );
+}
+
+#[test]
+fn racing_kings_test() {
+    let mut game = Game::with_variant(RacingKings);
+    game.start();
+    assert_eq!(game.to_fen(), "8/8/8/8/8/8/krbnNBRK/qrbnNBRQ w - - 0 1");
+
+    // giving check isn't allowed
+    let mut game = Game::from_fen_with("k7/8/8/8/8/8/8/2K1R3 w - - 0 1", RacingKings).unwrap();
+    let rook = game.pick("e1").unwrap();
+    assert_eq!(
+        rook.place_at(&mut game, "e8").unwrap_err().to_string(),
+        "Rook on e1 cannot move to e8"
+    );
+    game.pick("e1").unwrap().place_at(&mut game, "e7").unwrap();
+
+    // black catches up on the following move
+    let mut game = Game::from_fen_with("8/1K4k1/8/8/8/8/8/8 w - - 0 1", RacingKings).unwrap();
+    game.pick("b7").unwrap().place_at(&mut game, "b8").unwrap();
+    assert!(!game.is_game_over());
+    game.pick("g7").unwrap().place_at(&mut game, "g8").unwrap();
+    assert_eq!(
+        game.outcome(),
+        Some(Outcome {
+            winner: None,
+            reason: EndReason::GoalReached
+        })
+    );
+
+    let mut game = Game::from_fen_with("8/1K6/8/8/8/6k1/8/8 w - - 0 1", RacingKings).unwrap();
+    game.pick("b7").unwrap().place_at(&mut game, "b8").unwrap();
+    assert_eq!(
+        game.outcome(),
+        Some(Outcome {
+            winner: Some(Side::White),
+            reason: EndReason::GoalReached
+        })
+    );
+}
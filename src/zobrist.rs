@@ -0,0 +1,169 @@
+use crate::{
+    game::Game,
+    pieces::{Character, Side},
+    position::Pos,
+};
+
+// pockets hold at most 16 of a kind, counted from 1
+const POCKET_COUNTS: usize = 17;
+
+struct Keys {
+    pieces: [[u64; 64]; 12],
+    pockets: [[u64; POCKET_COUNTS]; 12],
+    castling: [u64; 4],
+    en_passant: [u64; 8],
+    black_to_move: u64,
+}
+
+// splitmix64, so the keys are fixed at compile time and identical across runs
+pub(crate) const fn next(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+const fn keys() -> Keys {
+    let mut state = 0x2545_f491_4f6c_dd1d;
+    let mut keys = Keys {
+        pieces: [[0; 64]; 12],
+        pockets: [[0; POCKET_COUNTS]; 12],
+        castling: [0; 4],
+        en_passant: [0; 8],
+        black_to_move: 0,
+    };
+    let mut character = 0;
+    while character < 12 {
+        let mut index = 0;
+        while index < 64 {
+            keys.pieces[character][index] = next(&mut state);
+            index += 1;
+        }
+        let mut count = 0;
+        while count < POCKET_COUNTS {
+            keys.pockets[character][count] = next(&mut state);
+            count += 1;
+        }
+        character += 1;
+    }
+    let mut index = 0;
+    while index < 4 {
+        keys.castling[index] = next(&mut state);
+        index += 1;
+    }
+    let mut index = 0;
+    while index < 8 {
+        keys.en_passant[index] = next(&mut state);
+        index += 1;
+    }
+    keys.black_to_move = next(&mut state);
+    keys
+}
+
+static KEYS: Keys = keys();
+
+fn character_index(character: Character) -> usize {
+    let kind = match character {
+        Character::King(_) => 0,
+        Character::Queen(_) => 1,
+        Character::Rook(_) => 2,
+        Character::Bishop(_) => 3,
+        Character::Knight(_) => 4,
+        Character::Pawn(_) => 5,
+    };
+    match character.side() {
+        Side::White => kind,
+        Side::Black => kind + 6,
+    }
+}
+
+// file of the en passant square, only when a pawn of side to move stands beside the
+// double-pushed pawn, as Polyglot hashes it, so move orders with and without a pointless
+// double push still transpose
+pub(crate) fn en_passant_file(game: &Game) -> Option<usize> {
+    let Pos(file, rank) = game.en_passant?;
+    let side = game.whose_turn();
+    let pushed_rank = match rank {
+        3 => 4,
+        6 => 5,
+        _ => return None,
+    };
+    let pawn = Some(Character::Pawn(side));
+    [-1, 1]
+        .into_iter()
+        .filter_map(|d_file| Pos(file, pushed_rank).d_pos(d_file, 0))
+        .any(|pos| game.board.character_at(pos) == pawn)
+        .then_some((file as u8 - b'a') as usize)
+}
+
+// hash of everything deciding the moves from here: placement, pockets, side to move,
+// castling rights and en passant file, clocks and variant counters are left out
+pub fn key(game: &Game) -> u64 {
+    let mut key = 0;
+    for (index, character) in game.board.get_matrix().into_iter().enumerate() {
+        if let Some(character) = character {
+            key ^= KEYS.pieces[character_index(character)][index];
+        }
+    }
+    for side in [Side::White, Side::Black] {
+        let mut counts = [0; 12];
+        for &character in game.pocket(side) {
+            let index = character_index(character);
+            counts[index] += 1;
+            key ^= KEYS.pockets[index][counts[index].min(POCKET_COUNTS - 1)];
+        }
+    }
+    let castling = game.castling;
+    let rights = [
+        castling.white_king_side,
+        castling.white_queen_side,
+        castling.black_king_side,
+        castling.black_queen_side,
+    ];
+    for (index, right) in rights.into_iter().enumerate() {
+        if right {
+            key ^= KEYS.castling[index];
+        }
+    }
+    if let Some(file) = en_passant_file(game) {
+        key ^= KEYS.en_passant[file];
+    }
+    if game.whose_turn() == Side::Black {
+        key ^= KEYS.black_to_move;
+    }
+    key
+}
+
+#[test]
+fn key_test() {
+    let mut game = Game::new();
+    game.start();
+    let start = key(&game);
+    assert_eq!(start, key(&Game::from_fen(&game.to_fen()).unwrap()));
+
+    // same position reached through different move orders
+    game.pick("g1").unwrap().place_at(&mut game, "f3").unwrap();
+    game.pick("g8").unwrap().place_at(&mut game, "f6").unwrap();
+    game.pick("f3").unwrap().place_at(&mut game, "g1").unwrap();
+    game.pick("f6").unwrap().place_at(&mut game, "g8").unwrap();
+    assert_eq!(key(&game), start);
+
+    let black = Game::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    assert_ne!(key(&black), start);
+    let no_castling =
+        Game::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1").unwrap();
+    assert_ne!(key(&no_castling), start);
+
+    // a double push nothing can capture en passant leaves no trace
+    let pushed = Game::from_fen("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1").unwrap();
+    let placed = Game::from_fen("4k3/8/8/8/4P3/8/8/4K3 b - - 0 1").unwrap();
+    assert_eq!(key(&pushed), key(&placed));
+    let capturable = Game::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").unwrap();
+    let stale = Game::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - - 0 1").unwrap();
+    assert_ne!(key(&capturable), key(&stale));
+
+    let pocket = Game::from_fen("4k3/8/8/8/8/8/8/4K3[PP] w - - 0 1").unwrap();
+    let single = Game::from_fen("4k3/8/8/8/8/8/8/4K3[P] w - - 0 1").unwrap();
+    assert_ne!(key(&pocket), key(&single));
+}